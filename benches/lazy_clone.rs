@@ -0,0 +1,56 @@
+//! Compares `Str::lazy_clone` with `Str::clone` in a pipeline that reads
+//! every copy but only keeps some of them.
+//!
+//! Run with `cargo bench --bench lazy_clone`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use ostr::Str;
+
+const ROUNDS: usize = 200;
+
+/// Returns the fastest of `ROUNDS` runs of `f`.
+fn fastest(mut f: impl FnMut()) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn main() {
+    let words: Vec<Str> = (0..10_000)
+        .map(|n| Str::new(&format!("word number {} of the pipeline", n)))
+        .collect();
+
+    for keep_every in [1, 10, 100] {
+        let eager = fastest(|| {
+            let kept: Vec<Str> = words
+                .iter()
+                .map(Str::clone)
+                .enumerate()
+                .filter(|(n, s)| n % keep_every == 0 && !s.is_empty())
+                .map(|(_, s)| s)
+                .collect();
+            black_box(kept);
+        });
+        let lazy = fastest(|| {
+            let kept: Vec<Str> = words
+                .iter()
+                .map(Str::lazy_clone)
+                .enumerate()
+                .filter(|(n, s)| n % keep_every == 0 && !s.as_str().is_empty())
+                .map(|(_, s)| s.into_str())
+                .collect();
+            black_box(kept);
+        });
+        println!(
+            "keep 1 in {:>3}: clone {:>10?}  lazy_clone {:>10?}",
+            keep_every, eager, lazy
+        );
+    }
+}
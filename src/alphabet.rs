@@ -0,0 +1,505 @@
+use crate::Str;
+
+const NATO_LETTERS: [&str; 26] = [
+    "Alpha", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel",
+    "India", "Juliet", "Kilo", "Lima", "Mike", "November", "Oscar", "Papa",
+    "Quebec", "Romeo", "Sierra", "Tango", "Uniform", "Victor", "Whiskey",
+    "X-ray", "Yankee", "Zulu",
+];
+
+const NATO_DIGITS: [&str; 10] = [
+    "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight",
+    "Nine",
+];
+
+const MORSE: [(char, &str); 54] = [
+    ('A', ".-"),
+    ('B', "-..."),
+    ('C', "-.-."),
+    ('D', "-.."),
+    ('E', "."),
+    ('F', "..-."),
+    ('G', "--."),
+    ('H', "...."),
+    ('I', ".."),
+    ('J', ".---"),
+    ('K', "-.-"),
+    ('L', ".-.."),
+    ('M', "--"),
+    ('N', "-."),
+    ('O', "---"),
+    ('P', ".--."),
+    ('Q', "--.-"),
+    ('R', ".-."),
+    ('S', "..."),
+    ('T', "-"),
+    ('U', "..-"),
+    ('V', "...-"),
+    ('W', ".--"),
+    ('X', "-..-"),
+    ('Y', "-.--"),
+    ('Z', "--.."),
+    ('0', "-----"),
+    ('1', ".----"),
+    ('2', "..---"),
+    ('3', "...--"),
+    ('4', "....-"),
+    ('5', "....."),
+    ('6', "-...."),
+    ('7', "--..."),
+    ('8', "---.."),
+    ('9', "----."),
+    ('.', ".-.-.-"),
+    (',', "--..--"),
+    ('?', "..--.."),
+    ('\'', ".----."),
+    ('!', "-.-.--"),
+    ('/', "-..-."),
+    ('(', "-.--."),
+    (')', "-.--.-"),
+    ('&', ".-..."),
+    (':', "---..."),
+    (';', "-.-.-."),
+    ('=', "-...-"),
+    ('+', ".-.-."),
+    ('-', "-....-"),
+    ('_', "..--.-"),
+    ('"', ".-..-."),
+    ('$', "...-..-"),
+    ('@', ".--.-."),
+];
+
+/// Error returned by the Morse code methods of [`Str`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MorseError {
+    /// The character has no Morse code.
+    UnsupportedChar(char),
+    /// A signal run at the given index has a length that is not a valid
+    /// Morse timing.
+    InvalidTiming(usize),
+    /// A decoded sequence of dots and dashes is not a known Morse code.
+    UnknownCode(Str),
+}
+
+impl std::fmt::Display for MorseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedChar(c) => write!(f, "no Morse code for {:?}", c),
+            Self::InvalidTiming(at) => {
+                write!(f, "invalid Morse timing at signal index {}", at)
+            }
+            Self::UnknownCode(code) => write!(f, "unknown Morse code {}", code),
+        }
+    }
+}
+
+impl std::error::Error for MorseError {}
+
+fn morse_code(c: char) -> Result<&'static str, MorseError> {
+    let upper = c.to_ascii_uppercase();
+    match MORSE.iter().find(|(k, _)| *k == upper) {
+        Some((_, code)) => Ok(code),
+        None => Err(MorseError::UnsupportedChar(c)),
+    }
+}
+
+/// Calls `f` with the Morse code of each character of each whitespace
+/// separated word.
+fn for_each_morse_word(
+    s: &str,
+    mut f: impl FnMut(&[&'static str]),
+) -> Result<(), MorseError> {
+    for word in s.split_whitespace() {
+        let codes = word
+            .chars()
+            .map(morse_code)
+            .collect::<Result<Vec<_>, _>>()?;
+        f(&codes);
+    }
+    Ok(())
+}
+
+/// North American Braille ASCII, indexed by the dot pattern of the cell, as
+/// used by the Unicode braille block starting at U+2800.
+const BRAILLE_ASCII: &[u8; 64] =
+    b" A1B'K2L@CIF/MSP\"E3H9O6R^DJG>NTQ,*5<-U8V.%[$+X!&;:4\\0Z7(_?W]#Y)=";
+
+/// Error returned by [`Str::to_braille_ascii`] and
+/// [`Str::from_braille_ascii`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrailleError {
+    /// The character has no Braille ASCII equivalent.
+    UnsupportedChar(char),
+}
+
+impl std::fmt::Display for BrailleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedChar(c) => {
+                write!(f, "no Braille ASCII equivalent for {:?}", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BrailleError {}
+
+/// Error returned by [`Str::to_nato_phonetic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NatoError {
+    /// The character is neither an ASCII letter nor an ASCII digit.
+    UnsupportedChar(char),
+}
+
+impl std::fmt::Display for NatoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedChar(c) => {
+                write!(f, "no NATO phonetic word for {:?}", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NatoError {}
+
+impl Str {
+    /// Spells the string using the NATO phonetic alphabet.
+    ///
+    /// ASCII letters (in any case) and digits are converted to their code
+    /// words, which are joined with spaces. Any other character, including
+    /// whitespace, is an error.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{NatoError, Str};
+    /// let s = Str::new("R2D2");
+    /// assert_eq!(s.to_nato_phonetic().unwrap().as_ref(), "Romeo Two Delta Two");
+    ///
+    /// let s = Str::new("R2-D2");
+    /// assert_eq!(s.to_nato_phonetic(), Err(NatoError::UnsupportedChar('-')));
+    /// ```
+    pub fn to_nato_phonetic(&self) -> Result<Str, NatoError> {
+        let mut out = String::new();
+        for c in self.as_ref().chars() {
+            let word = match c {
+                'a'..='z' | 'A'..='Z' => {
+                    NATO_LETTERS[(c.to_ascii_uppercase() as u8 - b'A') as usize]
+                }
+                '0'..='9' => NATO_DIGITS[(c as u8 - b'0') as usize],
+                _ => return Err(NatoError::UnsupportedChar(c)),
+            };
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(word);
+        }
+        Ok(Str::new(&out))
+    }
+
+    /// Converts the string to Morse code text.
+    ///
+    /// Letters are case insensitive. Codes within a word are separated by a
+    /// space and words by `" / "`; runs of whitespace count as a single word
+    /// separator.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("SOS help");
+    /// assert_eq!(s.to_morse().unwrap().as_ref(), "... --- ... / .... . .-.. .--.");
+    /// ```
+    pub fn to_morse(&self) -> Result<Str, MorseError> {
+        let mut words = Vec::new();
+        for_each_morse_word(self.as_ref(), |codes| {
+            words.push(codes.join(" "))
+        })?;
+        Ok(Str::new(&words.join(" / ")))
+    }
+
+    /// Converts the string to an on/off Morse signal with standard timing,
+    /// one `bool` per time unit.
+    ///
+    /// A dot is `[true, false]` and a dash is `[true, true, true, false]`:
+    /// each element is followed by a one unit gap. Characters are separated
+    /// by a three unit gap and words by a seven unit gap, counting the gap
+    /// that follows the last element. Use
+    /// [`Str::from_morse_digital_signal`] to decode the signal.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let signal = Str::new("ET").to_morse_digital_signal().unwrap();
+    /// assert_eq!(signal, [true, false, false, false, true, true, true, false]);
+    /// ```
+    pub fn to_morse_digital_signal(&self) -> Result<Vec<bool>, MorseError> {
+        let mut signal = Vec::new();
+        for_each_morse_word(self.as_ref(), |codes| {
+            if !signal.is_empty() {
+                signal.extend([false; 6]);
+            }
+            for (i, code) in codes.iter().enumerate() {
+                if i > 0 {
+                    signal.extend([false; 2]);
+                }
+                for element in code.bytes() {
+                    let on = if element == b'.' { 1 } else { 3 };
+                    signal.extend(std::iter::repeat_n(true, on));
+                    signal.push(false);
+                }
+            }
+        })?;
+        Ok(signal)
+    }
+
+    /// Decodes a signal produced by [`Str::to_morse_digital_signal`].
+    ///
+    /// Letters are decoded in upper case and words are joined with a single
+    /// space. Leading and trailing gaps are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let signal = Str::new("sos").to_morse_digital_signal().unwrap();
+    /// assert_eq!(Str::from_morse_digital_signal(&signal).unwrap().as_ref(), "SOS");
+    /// ```
+    pub fn from_morse_digital_signal(
+        signal: &[bool],
+    ) -> Result<Str, MorseError> {
+        let mut out = String::new();
+        let mut code = String::new();
+        let flush = |code: &mut String, out: &mut String| {
+            if code.is_empty() {
+                return Ok(());
+            }
+            match MORSE.iter().find(|(_, c)| *c == code.as_str()) {
+                Some((k, _)) => out.push(*k),
+                None => return Err(MorseError::UnknownCode(Str::new(code))),
+            }
+            code.clear();
+            Ok(())
+        };
+
+        let mut at = 0;
+        while at < signal.len() {
+            let on = signal[at];
+            let len = signal[at..].iter().take_while(|&&s| s == on).count();
+            let first = at == 0;
+            let last = at + len == signal.len();
+            match (on, len) {
+                (true, 1) => code.push('.'),
+                (true, 3) => code.push('-'),
+                (false, 1) => {}
+                (false, _) if first || last => {}
+                (false, 3) => flush(&mut code, &mut out)?,
+                (false, 7) => {
+                    flush(&mut code, &mut out)?;
+                    out.push(' ');
+                }
+                _ => return Err(MorseError::InvalidTiming(at)),
+            }
+            at += len;
+        }
+        flush(&mut code, &mut out)?;
+        Ok(Str::new(&out))
+    }
+
+    /// Converts printable ASCII to Unicode braille cells using the Braille
+    /// ASCII mapping.
+    ///
+    /// Braille ASCII has no case, so lower case letters map to the same
+    /// cells as upper case ones, and `` ` { | } ~ `` map like
+    /// `@ [ \ ] ^`. Any other character is an error.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("Hello");
+    /// assert_eq!(s.to_braille_ascii().unwrap().as_ref(), "⠓⠑⠇⠇⠕");
+    /// ```
+    pub fn to_braille_ascii(&self) -> Result<Str, BrailleError> {
+        let mut out = String::with_capacity(self.len() * 3);
+        for c in self.as_ref().chars() {
+            let ascii = match c {
+                ' '..='_' => c as u8,
+                '`'..='~' => c as u8 - 0x20,
+                _ => return Err(BrailleError::UnsupportedChar(c)),
+            };
+            let Some(dots) = BRAILLE_ASCII.iter().position(|&b| b == ascii)
+            else {
+                return Err(BrailleError::UnsupportedChar(c));
+            };
+            out.push(char::from_u32(0x2800 + dots as u32).unwrap());
+        }
+        Ok(Str::new(&out))
+    }
+
+    /// Converts six-dot Unicode braille cells (U+2800 to U+283F) back to
+    /// Braille ASCII, producing upper case letters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("⠓⠑⠇⠇⠕");
+    /// assert_eq!(s.from_braille_ascii().unwrap().as_ref(), "HELLO");
+    /// ```
+    pub fn from_braille_ascii(&self) -> Result<Str, BrailleError> {
+        let out = self
+            .as_ref()
+            .chars()
+            .map(|c| match c as u32 {
+                cell @ 0x2800..=0x283F => {
+                    Ok(BRAILLE_ASCII[(cell - 0x2800) as usize] as char)
+                }
+                _ => Err(BrailleError::UnsupportedChar(c)),
+            })
+            .collect::<Result<String, _>>()?;
+        Ok(Str::new(&out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_morse() {
+        let morse = |s: &str| Str::new(s).to_morse().unwrap().to_string();
+        assert_eq!(morse("SOS"), "... --- ...");
+        assert_eq!(morse("Hi  there"), ".... .. / - .... . .-. .");
+        assert_eq!(morse(" R2 "), ".-. ..---");
+        assert_eq!(morse(""), "");
+        assert_eq!(
+            Str::new("a#b").to_morse(),
+            Err(MorseError::UnsupportedChar('#'))
+        );
+    }
+
+    #[test]
+    fn test_to_morse_digital_signal_sos() {
+        let signal = Str::new("SOS").to_morse_digital_signal().unwrap();
+        let rendered: String = signal
+            .iter()
+            .map(|&on| if on { '1' } else { '0' })
+            .collect();
+        assert_eq!(rendered, "1010100011101110111000101010");
+    }
+
+    #[test]
+    fn test_to_morse_digital_signal_word_gap() {
+        let signal = Str::new("E E").to_morse_digital_signal().unwrap();
+        let mut expected = vec![true];
+        expected.extend([false; 7]);
+        expected.extend([true, false]);
+        assert_eq!(signal, expected);
+        assert!(Str::new("").to_morse_digital_signal().unwrap().is_empty());
+        assert_eq!(
+            Str::new("é").to_morse_digital_signal(),
+            Err(MorseError::UnsupportedChar('é'))
+        );
+    }
+
+    #[test]
+    fn test_morse_digital_signal_round_trip() {
+        for message in ["SOS", "hello world", "CQ DX 73", "A. B, C? 0123456789"]
+        {
+            let signal = Str::new(message).to_morse_digital_signal().unwrap();
+            let decoded = Str::from_morse_digital_signal(&signal).unwrap();
+            assert_eq!(decoded.as_ref(), message.to_uppercase());
+        }
+    }
+
+    #[test]
+    fn test_from_morse_digital_signal_errors() {
+        let decode = |signal: &[u8]| {
+            let signal: Vec<bool> = signal.iter().map(|&b| b == 1).collect();
+            Str::from_morse_digital_signal(&signal)
+        };
+        assert_eq!(decode(&[1, 1, 0]), Err(MorseError::InvalidTiming(0)));
+        assert_eq!(decode(&[1, 0, 0, 1, 0]), Err(MorseError::InvalidTiming(1)));
+        assert_eq!(
+            decode(&[1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0]),
+            Err(MorseError::UnknownCode(Str::new("......")))
+        );
+        assert_eq!(decode(&[0, 0, 1, 0, 0]).unwrap().as_ref(), "E");
+    }
+
+    #[test]
+    fn test_braille_ascii_table() {
+        let mut sorted = BRAILLE_ASCII.to_vec();
+        sorted.sort_unstable();
+        let expected: Vec<u8> = (b' '..=b'_').collect();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn test_to_braille_ascii_alphabet_and_digits() {
+        let s = Str::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+        assert_eq!(
+            s.to_braille_ascii().unwrap().as_ref(),
+            "⠁⠃⠉⠙⠑⠋⠛⠓⠊⠚⠅⠇⠍⠝⠕⠏⠟⠗⠎⠞⠥⠧⠺⠭⠽⠵"
+        );
+        let s = Str::new("#1234567890");
+        assert_eq!(s.to_braille_ascii().unwrap().as_ref(), "⠼⠂⠆⠒⠲⠢⠖⠶⠦⠔⠴");
+        assert_eq!(
+            Str::new("abc xyz").to_braille_ascii(),
+            Str::new("ABC XYZ").to_braille_ascii()
+        );
+    }
+
+    #[test]
+    fn test_braille_ascii_round_trip() {
+        let printable: String = (' '..='_').collect();
+        let s = Str::new(&printable);
+        let braille = s.to_braille_ascii().unwrap();
+        assert_eq!(braille.char_count(), 64);
+        assert_eq!(braille.from_braille_ascii().unwrap(), s);
+
+        let lower = Str::new("hello, world!");
+        let round = lower.to_braille_ascii().unwrap().from_braille_ascii();
+        assert_eq!(round.unwrap().as_ref(), "HELLO, WORLD!");
+    }
+
+    #[test]
+    fn test_braille_ascii_errors() {
+        assert_eq!(
+            Str::new("café").to_braille_ascii(),
+            Err(BrailleError::UnsupportedChar('é'))
+        );
+        assert_eq!(
+            Str::new("a\nb").to_braille_ascii(),
+            Err(BrailleError::UnsupportedChar('\n'))
+        );
+        assert_eq!(
+            Str::new("\x7f").to_braille_ascii(),
+            Err(BrailleError::UnsupportedChar('\x7f'))
+        );
+        assert_eq!(
+            Str::new("⠁A").from_braille_ascii(),
+            Err(BrailleError::UnsupportedChar('A'))
+        );
+        assert_eq!(
+            Str::new("⡀").from_braille_ascii(),
+            Err(BrailleError::UnsupportedChar('⡀'))
+        );
+    }
+
+    #[test]
+    fn test_to_nato_phonetic() {
+        let nato =
+            |s: &str| Str::new(s).to_nato_phonetic().unwrap().to_string();
+        assert_eq!(nato("SOS"), "Sierra Oscar Sierra");
+        assert_eq!(nato("sos"), "Sierra Oscar Sierra");
+        assert_eq!(nato("R2D2"), "Romeo Two Delta Two");
+        assert_eq!(nato("az09"), "Alpha Zulu Zero Nine");
+        assert_eq!(nato(""), "");
+    }
+
+    #[test]
+    fn test_to_nato_phonetic_unsupported() {
+        let err = |s: &str| Str::new(s).to_nato_phonetic().unwrap_err();
+        assert_eq!(err("a b"), NatoError::UnsupportedChar(' '));
+        assert_eq!(err("hi!"), NatoError::UnsupportedChar('!'));
+        assert_eq!(err("é"), NatoError::UnsupportedChar('é'));
+    }
+}
@@ -0,0 +1,868 @@
+use std::collections::HashMap;
+
+use crate::Str;
+
+/// Classification returned by [`Str::detect_encoding_hint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingHint {
+    /// Every byte is printable ASCII or ASCII whitespace.
+    PureAscii,
+    /// At least one multi-byte UTF-8 sequence is present.
+    Utf8MultiBytePresent,
+    /// At least one control character other than `\t`, `\n` or `\r` is
+    /// present.
+    ContainsControlChars,
+}
+
+/// Guess returned by [`Str::detect_base_encoding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BaseEncoding {
+    /// An even number of hex digits.
+    Hex,
+    /// Standard or URL-safe base64, optionally padded with `=`.
+    Base64,
+    /// Groups of `0` and `1` digits, optionally separated by whitespace.
+    Binary,
+    /// Anything else.
+    PlainText,
+}
+
+/// Coarse character class counted by [`Str::count_by_category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnicodeCategory {
+    /// Alphabetic characters.
+    Letter,
+    /// Numeric characters.
+    Number,
+    /// Whitespace, including line breaks and tabs.
+    Whitespace,
+    /// Control characters that are not whitespace.
+    Control,
+    /// ASCII punctuation.
+    Punctuation,
+    /// Everything else, such as symbols, marks and non-ASCII punctuation.
+    Other,
+}
+
+impl UnicodeCategory {
+    /// Classifies `c` using the standard library character predicates.
+    fn of(c: char) -> Self {
+        if c.is_alphabetic() {
+            Self::Letter
+        } else if c.is_numeric() {
+            Self::Number
+        } else if c.is_whitespace() {
+            Self::Whitespace
+        } else if c.is_control() {
+            Self::Control
+        } else if c.is_ascii_punctuation() {
+            Self::Punctuation
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// Unicode general category counted by [`Str::rune_category_counts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuneCategory {
+    /// An uppercase letter (`Lu`).
+    UppercaseLetter,
+    /// A lowercase letter (`Ll`).
+    LowercaseLetter,
+    /// A digraph whose first part is uppercase, such as `ǅ` (`Lt`).
+    TitlecaseLetter,
+    /// A modifier letter (`Lm`).
+    ModifierLetter,
+    /// Any other letter, such as CJK ideographs and letters of scripts
+    /// without case (`Lo`).
+    OtherLetter,
+    /// A combining mark that takes no space (`Mn`).
+    NonspacingMark,
+    /// A combining mark that takes space (`Mc`).
+    SpacingMark,
+    /// A combining mark enclosing the character before it (`Me`).
+    EnclosingMark,
+    /// A decimal digit (`Nd`).
+    DecimalNumber,
+    /// A number made of letters, such as Roman numerals (`Nl`).
+    LetterNumber,
+    /// Any other number, such as superscripts or fractions (`No`).
+    OtherNumber,
+    /// Punctuation connecting words, such as `_` (`Pc`).
+    ConnectorPunctuation,
+    /// A dash or hyphen (`Pd`).
+    DashPunctuation,
+    /// An opening bracket (`Ps`).
+    OpenPunctuation,
+    /// A closing bracket (`Pe`).
+    ClosePunctuation,
+    /// An opening quotation mark (`Pi`).
+    InitialPunctuation,
+    /// A closing quotation mark (`Pf`).
+    FinalPunctuation,
+    /// Any other punctuation (`Po`).
+    OtherPunctuation,
+    /// A mathematical symbol (`Sm`).
+    MathSymbol,
+    /// A currency symbol (`Sc`).
+    CurrencySymbol,
+    /// A non-letter modifier symbol, such as `^` (`Sk`).
+    ModifierSymbol,
+    /// Any other symbol, such as emoji (`So`).
+    OtherSymbol,
+    /// A space character (`Zs`).
+    SpaceSeparator,
+    /// The line separator U+2028 (`Zl`).
+    LineSeparator,
+    /// The paragraph separator U+2029 (`Zp`).
+    ParagraphSeparator,
+    /// A C0 or C1 control character (`Cc`).
+    Control,
+    /// An invisible formatting character, such as U+200D (`Cf`).
+    Format,
+    /// A surrogate code point (`Cs`). Never counted, as a `char` cannot be a
+    /// surrogate.
+    Surrogate,
+    /// A character reserved for private use (`Co`).
+    PrivateUse,
+    /// A noncharacter or a code point not assigned yet (`Cn`).
+    Unassigned,
+}
+
+#[cfg(feature = "unicode-general-category")]
+impl RuneCategory {
+    /// Returns the general category of `c`.
+    fn of(c: char) -> Self {
+        use unicode_general_category::GeneralCategory as G;
+
+        match unicode_general_category::get_general_category(c) {
+            G::UppercaseLetter => Self::UppercaseLetter,
+            G::LowercaseLetter => Self::LowercaseLetter,
+            G::TitlecaseLetter => Self::TitlecaseLetter,
+            G::ModifierLetter => Self::ModifierLetter,
+            G::OtherLetter => Self::OtherLetter,
+            G::NonspacingMark => Self::NonspacingMark,
+            G::SpacingMark => Self::SpacingMark,
+            G::EnclosingMark => Self::EnclosingMark,
+            G::DecimalNumber => Self::DecimalNumber,
+            G::LetterNumber => Self::LetterNumber,
+            G::OtherNumber => Self::OtherNumber,
+            G::ConnectorPunctuation => Self::ConnectorPunctuation,
+            G::DashPunctuation => Self::DashPunctuation,
+            G::OpenPunctuation => Self::OpenPunctuation,
+            G::ClosePunctuation => Self::ClosePunctuation,
+            G::InitialPunctuation => Self::InitialPunctuation,
+            G::FinalPunctuation => Self::FinalPunctuation,
+            G::OtherPunctuation => Self::OtherPunctuation,
+            G::MathSymbol => Self::MathSymbol,
+            G::CurrencySymbol => Self::CurrencySymbol,
+            G::ModifierSymbol => Self::ModifierSymbol,
+            G::OtherSymbol => Self::OtherSymbol,
+            G::SpaceSeparator => Self::SpaceSeparator,
+            G::LineSeparator => Self::LineSeparator,
+            G::ParagraphSeparator => Self::ParagraphSeparator,
+            G::Control => Self::Control,
+            G::Format => Self::Format,
+            G::Surrogate => Self::Surrogate,
+            G::PrivateUse => Self::PrivateUse,
+            G::Unassigned => Self::Unassigned,
+            _ => Self::Unassigned,
+        }
+    }
+}
+
+/// Writing system returned by [`Str::detect_script`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScriptHint {
+    Latin,
+    Cyrillic,
+    Arabic,
+    Hebrew,
+    /// Chinese, Japanese and Korean, including kana and hangul.
+    CJK,
+    Devanagari,
+    /// No single script makes up most of the letters.
+    Mixed,
+    /// There are no letters, or most of them are in another script.
+    Unknown,
+}
+
+impl ScriptHint {
+    /// Returns the script of the letter `c`, or `Unknown` for letters of
+    /// other scripts.
+    fn of(c: char) -> Self {
+        match c as u32 {
+            0x41..=0x5A
+            | 0x61..=0x7A
+            | 0xC0..=0x24F
+            | 0x1E00..=0x1EFF
+            | 0x2C60..=0x2C7F
+            | 0xA720..=0xA7FF
+            | 0xFF21..=0xFF3A
+            | 0xFF41..=0xFF5A => Self::Latin,
+            0x400..=0x52F
+            | 0x1C80..=0x1C8F
+            | 0x2DE0..=0x2DFF
+            | 0xA640..=0xA69F => Self::Cyrillic,
+            0x590..=0x5FF | 0xFB1D..=0xFB4F => Self::Hebrew,
+            0x600..=0x6FF
+            | 0x750..=0x77F
+            | 0x8A0..=0x8FF
+            | 0xFB50..=0xFDFF
+            | 0xFE70..=0xFEFF => Self::Arabic,
+            0x900..=0x97F | 0xA8E0..=0xA8FF => Self::Devanagari,
+            0x1100..=0x11FF
+            | 0x3040..=0x30FF
+            | 0x3130..=0x318F
+            | 0x31F0..=0x31FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xAC00..=0xD7AF
+            | 0xF900..=0xFAFF
+            | 0xFF66..=0xFF9F
+            | 0x20000..=0x3134F => Self::CJK,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl Str {
+    /// Classifies the content of the string by scanning its characters.
+    ///
+    /// Control characters take precedence over multi-byte characters: a
+    /// string containing both is reported as
+    /// [`EncodingHint::ContainsControlChars`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{EncodingHint, Str};
+    /// assert_eq!(Str::new("plain").detect_encoding_hint(), EncodingHint::PureAscii);
+    /// assert_eq!(
+    ///     Str::new("café").detect_encoding_hint(),
+    ///     EncodingHint::Utf8MultiBytePresent,
+    /// );
+    /// ```
+    pub fn detect_encoding_hint(&self) -> EncodingHint {
+        let mut hint = EncodingHint::PureAscii;
+        for c in self.as_ref().chars() {
+            if c.is_control() && !matches!(c, '\t' | '\n' | '\r') {
+                return EncodingHint::ContainsControlChars;
+            }
+            if !c.is_ascii() {
+                hint = EncodingHint::Utf8MultiBytePresent;
+            }
+        }
+        hint
+    }
+
+    /// Guesses whether the string holds binary digits, hex, base64 or plain
+    /// text.
+    ///
+    /// This is a heuristic and may be wrong: many short words are also valid
+    /// hex or base64. Hypotheses are tried from the most to the least
+    /// restrictive character set:
+    /// - binary needs at least 8 `0`/`1` digits, whitespace is ignored;
+    /// - hex needs an even, non-zero number of hex digits;
+    /// - base64 needs a length that is a multiple of 4 and either `=`
+    ///   padding or a mix of upper case, lower case and digits or symbols,
+    ///   which rules out most plain words.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{BaseEncoding, Str};
+    /// assert_eq!(Str::new("deadBEEF").detect_base_encoding(), BaseEncoding::Hex);
+    /// assert_eq!(Str::new("aGk=").detect_base_encoding(), BaseEncoding::Base64);
+    /// assert_eq!(Str::new("hello").detect_base_encoding(), BaseEncoding::PlainText);
+    /// ```
+    pub fn detect_base_encoding(&self) -> BaseEncoding {
+        let s = self.as_ref().trim();
+        let mut digits = s.chars().filter(|c| !c.is_whitespace());
+        let binary =
+            digits.clone().count() >= 8 && digits.all(|c| c == '0' || c == '1');
+        if binary {
+            return BaseEncoding::Binary;
+        }
+
+        let bytes = s.as_bytes();
+        if !bytes.is_empty()
+            && bytes.len() % 2 == 0
+            && bytes.iter().all(u8::is_ascii_hexdigit)
+        {
+            return BaseEncoding::Hex;
+        }
+
+        let body = s.trim_end_matches('=');
+        let padding = s.len() - body.len();
+        let charset = body.bytes().all(|b| {
+            b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'-' | b'_')
+        });
+        let has = |f: fn(&u8) -> bool| body.as_bytes().iter().any(f);
+        let mixed = has(u8::is_ascii_uppercase)
+            && has(u8::is_ascii_lowercase)
+            && has(|b| !b.is_ascii_alphabetic());
+        if !body.is_empty()
+            && charset
+            && padding <= 2
+            && s.len() % 4 == 0
+            && (padding > 0 || mixed)
+        {
+            return BaseEncoding::Base64;
+        }
+
+        BaseEncoding::PlainText
+    }
+
+    /// Counts the characters of the string in each [`UnicodeCategory`].
+    ///
+    /// Categories are approximated with [`char::is_alphabetic`],
+    /// [`char::is_numeric`], [`char::is_whitespace`], [`char::is_control`]
+    /// and [`char::is_ascii_punctuation`], checked in that order. Only
+    /// categories that occur are present in the map.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{Str, UnicodeCategory};
+    /// let counts = Str::new("R2-D2!").count_by_category();
+    /// assert_eq!(counts[&UnicodeCategory::Letter], 2);
+    /// assert_eq!(counts[&UnicodeCategory::Number], 2);
+    /// assert_eq!(counts[&UnicodeCategory::Punctuation], 2);
+    /// ```
+    pub fn count_by_category(&self) -> HashMap<UnicodeCategory, usize> {
+        let mut counts = HashMap::new();
+        for c in self.as_ref().chars() {
+            *counts.entry(UnicodeCategory::of(c)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Counts the characters of the string in each Unicode general
+    /// category, see [`RuneCategory`].
+    ///
+    /// Only categories that occur are present in the map.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{RuneCategory, Str};
+    /// let counts = Str::new("Ab 1²").rune_category_counts();
+    /// assert_eq!(counts[&RuneCategory::UppercaseLetter], 1);
+    /// assert_eq!(counts[&RuneCategory::LowercaseLetter], 1);
+    /// assert_eq!(counts[&RuneCategory::SpaceSeparator], 1);
+    /// assert_eq!(counts[&RuneCategory::DecimalNumber], 1);
+    /// assert_eq!(counts[&RuneCategory::OtherNumber], 1);
+    /// ```
+    #[cfg(feature = "unicode-general-category")]
+    pub fn rune_category_counts(&self) -> HashMap<RuneCategory, usize> {
+        let mut counts = HashMap::new();
+        for c in self.as_ref().chars() {
+            *counts.entry(RuneCategory::of(c)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Returns `true` if the string reads the same forwards and backwards,
+    /// comparing Unicode scalar values.
+    ///
+    /// Case and whitespace are significant. A string with combining marks may
+    /// not be a palindrome at this level even if it looks like one, see
+    /// [`Str::is_grapheme_palindrome`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert!(Str::new("racecar").is_palindrome());
+    /// assert!(!Str::new("Racecar").is_palindrome());
+    /// ```
+    pub fn is_palindrome(&self) -> bool {
+        let s: &str = self.as_ref();
+        s.chars().eq(s.chars().rev())
+    }
+
+    /// Returns `true` if the string reads the same forwards and backwards,
+    /// comparing extended grapheme clusters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("e\u{301}ae\u{301}");
+    /// assert!(s.is_grapheme_palindrome());
+    /// assert!(!s.is_palindrome());
+    /// ```
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn is_grapheme_palindrome(&self) -> bool {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let s: &str = self.as_ref();
+        s.graphemes(true).eq(s.graphemes(true).rev())
+    }
+
+    /// Computes the Jaro similarity between the string and `other`.
+    ///
+    /// The result is in `[0.0, 1.0]`, where `1.0` means the strings are
+    /// identical. Two empty strings are identical, an empty and a non-empty
+    /// string have similarity `0.0`. Characters are compared as Unicode
+    /// scalar values.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let sim = Str::new("MARTHA").jaro_similarity("MARHTA");
+    /// assert!((sim - 0.944).abs() < 0.001);
+    /// ```
+    pub fn jaro_similarity(&self, other: &str) -> f64 {
+        let a: Vec<char> = self.as_ref().chars().collect();
+        let b: Vec<char> = other.chars().collect();
+        if a.is_empty() && b.is_empty() {
+            return 1.0;
+        }
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+
+        let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+        let mut a_matched = vec![false; a.len()];
+        let mut b_matched = vec![false; b.len()];
+        let mut matches = 0;
+        for (i, &c) in a.iter().enumerate() {
+            let lo = i.saturating_sub(window);
+            let hi = (i + window + 1).min(b.len());
+            for j in lo..hi {
+                if !b_matched[j] && b[j] == c {
+                    a_matched[i] = true;
+                    b_matched[j] = true;
+                    matches += 1;
+                    break;
+                }
+            }
+        }
+        if matches == 0 {
+            return 0.0;
+        }
+
+        let a_seq = a.iter().zip(&a_matched).filter(|(_, &m)| m);
+        let b_seq = b.iter().zip(&b_matched).filter(|(_, &m)| m);
+        let transpositions =
+            a_seq.zip(b_seq).filter(|((x, _), (y, _))| x != y).count() / 2;
+
+        let m = matches as f64;
+        (m / a.len() as f64
+            + m / b.len() as f64
+            + (m - transpositions as f64) / m)
+            / 3.0
+    }
+
+    /// Computes the Jaro-Winkler similarity between the string and `other`.
+    ///
+    /// The Jaro similarity is boosted by `prefix_weight` for each character
+    /// of common prefix, up to four characters. `prefix_weight` is clamped to
+    /// `[0.0, 0.25]` so the result stays in `[0.0, 1.0]`; `0.1` is the
+    /// customary value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let sim = Str::new("MARTHA").jaro_winkler_similarity("MARHTA", 0.1);
+    /// assert!((sim - 0.961).abs() < 0.001);
+    /// ```
+    pub fn jaro_winkler_similarity(
+        &self,
+        other: &str,
+        prefix_weight: f64,
+    ) -> f64 {
+        let jaro = self.jaro_similarity(other);
+        let prefix = self
+            .as_ref()
+            .chars()
+            .zip(other.chars())
+            .take(4)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let weight = prefix_weight.clamp(0.0, 0.25);
+        jaro + prefix as f64 * weight * (1.0 - jaro)
+    }
+
+    /// Returns all character n-grams of the string as owned strings.
+    ///
+    /// Returns an empty vector if `n` is 0 or greater than
+    /// [`Str::char_count`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let grams = Str::new("abc").char_ngrams(2);
+    /// assert_eq!(grams, [Str::new("ab"), Str::new("bc")]);
+    /// ```
+    pub fn char_ngrams(&self, n: usize) -> Vec<Str> {
+        if n == 0 {
+            return Vec::new();
+        }
+        self.char_windows(n).map(Str::new).collect()
+    }
+
+    /// Returns all word n-grams of the string as owned strings.
+    ///
+    /// Words are separated by whitespace and joined back with a single space.
+    /// Returns an empty vector if `n` is 0 or greater than the number of
+    /// words.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let grams = Str::new("the quick  fox").word_ngrams(2);
+    /// assert_eq!(grams, [Str::new("the quick"), Str::new("quick fox")]);
+    /// ```
+    pub fn word_ngrams(&self, n: usize) -> Vec<Str> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let words: Vec<&str> = self.as_ref().split_whitespace().collect();
+        words
+            .windows(n)
+            .map(|gram| Str::new(&gram.join(" ")))
+            .collect()
+    }
+
+    /// Detects the dominant writing system of the string.
+    ///
+    /// Only letters are counted, so digits, punctuation and whitespace do
+    /// not affect the result. A script is reported if it covers more than
+    /// 80% of the letters, otherwise the result is [`ScriptHint::Mixed`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{ScriptHint, Str};
+    /// assert_eq!(Str::new("Привет, мир!").detect_script(), ScriptHint::Cyrillic);
+    /// assert_eq!(Str::new("hello 世界").detect_script(), ScriptHint::Mixed);
+    /// ```
+    pub fn detect_script(&self) -> ScriptHint {
+        let mut counts: HashMap<ScriptHint, usize> = HashMap::new();
+        let mut letters = 0;
+        for c in self.as_ref().chars().filter(|c| c.is_alphabetic()) {
+            *counts.entry(ScriptHint::of(c)).or_default() += 1;
+            letters += 1;
+        }
+
+        match counts.into_iter().max_by_key(|&(_, count)| count) {
+            None => ScriptHint::Unknown,
+            Some((script, count)) if count * 5 > letters * 4 => script,
+            Some(_) => ScriptHint::Mixed,
+        }
+    }
+
+    /// Returns the number of bytes of the string that are not ASCII, that
+    /// is, the bytes of multi-byte UTF-8 sequences.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("café").count_non_ascii_bytes(), 2);
+    /// ```
+    #[inline]
+    pub fn count_non_ascii_bytes(&self) -> usize {
+        self.iter_bytes().filter(|b| !b.is_ascii()).count()
+    }
+
+    /// Returns the number of characters of the string that are not ASCII.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("café").count_non_ascii_chars(), 1);
+    /// ```
+    #[inline]
+    pub fn count_non_ascii_chars(&self) -> usize {
+        self.as_ref().chars().filter(|c| !c.is_ascii()).count()
+    }
+
+    /// Returns the fraction of the bytes of the string that are ASCII, or
+    /// 0.0 for an empty string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("café").ascii_ratio(), 0.6);
+    /// ```
+    pub fn ascii_ratio(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let ascii = self.len() - self.count_non_ascii_bytes();
+        ascii as f64 / self.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strs(items: &[&str]) -> Vec<Str> {
+        items.iter().copied().map(Str::new).collect()
+    }
+
+    #[test]
+    fn test_detect_encoding_hint() {
+        let hint = |s: &str| Str::new(s).detect_encoding_hint();
+        assert_eq!(hint(""), EncodingHint::PureAscii);
+        assert_eq!(hint("hello, world!\n\tok\r\n"), EncodingHint::PureAscii);
+        assert_eq!(hint("naïve"), EncodingHint::Utf8MultiBytePresent);
+        assert_eq!(hint("世界 🎉"), EncodingHint::Utf8MultiBytePresent);
+        assert_eq!(hint("nul\0byte"), EncodingHint::ContainsControlChars);
+        assert_eq!(hint("bell\x07"), EncodingHint::ContainsControlChars);
+        assert_eq!(hint("del\x7f"), EncodingHint::ContainsControlChars);
+        assert_eq!(hint("世界\x1b[0m"), EncodingHint::ContainsControlChars);
+        assert_eq!(hint("c1\u{85}"), EncodingHint::ContainsControlChars);
+    }
+
+    #[test]
+    fn test_detect_base_encoding() {
+        let detect = |s: &str| Str::new(s).detect_base_encoding();
+        let sha256 =
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert_eq!(sha256.len(), 64);
+        assert_eq!(detect(sha256), BaseEncoding::Hex);
+        assert_eq!(detect("CAFEBABE"), BaseEncoding::Hex);
+        assert_eq!(detect("SGVsbG8gV29ybGQ="), BaseEncoding::Base64);
+        assert_eq!(detect("SGVsbG8="), BaseEncoding::Base64);
+        assert_eq!(detect("dGVzdA=="), BaseEncoding::Base64);
+        assert_eq!(detect("SGVsbG8gV29ybGQh"), BaseEncoding::Base64);
+        assert_eq!(detect("01001000 01101001"), BaseEncoding::Binary);
+        assert_eq!(detect("0110100001101001"), BaseEncoding::Binary);
+        assert_eq!(
+            detect("The quick brown fox jumps over the lazy dog."),
+            BaseEncoding::PlainText
+        );
+        assert_eq!(detect("test"), BaseEncoding::PlainText);
+        assert_eq!(detect("abc"), BaseEncoding::PlainText);
+        assert_eq!(detect(""), BaseEncoding::PlainText);
+        assert_eq!(detect("a===="), BaseEncoding::PlainText);
+    }
+
+    #[test]
+    fn test_count_by_category_mixed() {
+        let counts = Str::new("Héllo, 世界 42²\t\0€!").count_by_category();
+        assert_eq!(counts[&UnicodeCategory::Letter], 7);
+        assert_eq!(counts[&UnicodeCategory::Number], 3);
+        assert_eq!(counts[&UnicodeCategory::Whitespace], 3);
+        assert_eq!(counts[&UnicodeCategory::Control], 1);
+        assert_eq!(counts[&UnicodeCategory::Punctuation], 2);
+        assert_eq!(counts[&UnicodeCategory::Other], 1);
+    }
+
+    #[test]
+    fn test_count_by_category_ascii_sums_to_len() {
+        let s = Str::new("The quick (brown) fox, 3 times!\r\n\x07~");
+        let counts = s.count_by_category();
+        assert_eq!(counts.values().sum::<usize>(), s.len());
+        assert!(!counts.contains_key(&UnicodeCategory::Other));
+        assert!(Str::new("").count_by_category().is_empty());
+    }
+
+    #[test]
+    fn test_is_palindrome() {
+        assert!(Str::new("racecar").is_palindrome());
+        assert!(Str::new("abba").is_palindrome());
+        assert!(!Str::new("abca").is_palindrome());
+        assert!(Str::new("上海自来水来自海上").is_palindrome());
+        assert!(Str::new("é").is_palindrome());
+        assert!(Str::new("").is_palindrome());
+        assert!(!Str::new("e\u{301}ae\u{301}").is_palindrome());
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn test_is_grapheme_palindrome() {
+        assert!(Str::new("racecar").is_grapheme_palindrome());
+        assert!(!Str::new("abca").is_grapheme_palindrome());
+        assert!(Str::new("e\u{301}ae\u{301}").is_grapheme_palindrome());
+        assert!(Str::new("🇩🇪x🇩🇪").is_grapheme_palindrome());
+        assert!(!Str::new("🇩🇪").is_palindrome());
+        assert!(Str::new("🇩🇪").is_grapheme_palindrome());
+        assert!(Str::new("").is_grapheme_palindrome());
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.001,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_jaro_similarity() {
+        let jaro = |a: &str, b: &str| Str::new(a).jaro_similarity(b);
+        assert_close(jaro("MARTHA", "MARHTA"), 0.944);
+        assert_close(jaro("DIXON", "DICKSONX"), 0.767);
+        assert_close(jaro("JELLYFISH", "SMELLYFISH"), 0.896);
+        assert_close(jaro("", ""), 1.0);
+        assert_close(jaro("abc", "abc"), 1.0);
+        assert_close(jaro("abc", "xyz"), 0.0);
+        assert_close(jaro("abc", ""), 0.0);
+        assert_close(jaro("", "abc"), 0.0);
+        assert_close(jaro("MARTHA", "MARHTA"), jaro("MARHTA", "MARTHA"));
+    }
+
+    #[test]
+    fn test_jaro_winkler_similarity() {
+        let jw = |a: &str, b: &str| Str::new(a).jaro_winkler_similarity(b, 0.1);
+        assert_close(jw("MARTHA", "MARHTA"), 0.961);
+        assert_close(jw("DIXON", "DICKSONX"), 0.813);
+        assert_close(jw("abc", "xyz"), 0.0);
+        assert_close(jw("", ""), 1.0);
+
+        let s = Str::new("prefixed");
+        assert!(
+            s.jaro_winkler_similarity("prefixes", 0.1)
+                > s.jaro_similarity("prefixes")
+        );
+        assert_close(
+            s.jaro_winkler_similarity("prefixes", 0.0),
+            s.jaro_similarity("prefixes"),
+        );
+        assert!(s.jaro_winkler_similarity("prefixes", 10.0) <= 1.0);
+    }
+
+    #[test]
+    fn test_char_ngrams() {
+        let s = Str::new("abc");
+        assert_eq!(s.char_ngrams(2), strs(&["ab", "bc"]));
+
+        let s = Str::new("hello");
+        assert_eq!(s.char_ngrams(3), strs(&["hel", "ell", "llo"]));
+    }
+
+    #[test]
+    fn test_char_ngrams_degenerate() {
+        let s = Str::new("abc");
+        assert!(s.char_ngrams(0).is_empty());
+        assert!(s.char_ngrams(4).is_empty());
+    }
+
+    #[test]
+    fn test_word_ngrams() {
+        let s = Str::new(" one two\tthree four ");
+        assert_eq!(
+            s.word_ngrams(2),
+            strs(&["one two", "two three", "three four"])
+        );
+        assert_eq!(s.word_ngrams(4), strs(&["one two three four"]));
+        assert!(s.word_ngrams(0).is_empty());
+        assert!(s.word_ngrams(5).is_empty());
+    }
+
+    #[test]
+    fn test_count_non_ascii() {
+        let s = Str::new("plain ascii");
+        assert_eq!(s.count_non_ascii_bytes(), 0);
+        assert_eq!(s.count_non_ascii_chars(), 0);
+
+        // "é" takes 2 bytes, "世" and "界" 3 each and "🎉" 4.
+        let s = Str::new("é世界🎉!");
+        assert_eq!(s.count_non_ascii_bytes(), 12);
+        assert_eq!(s.count_non_ascii_chars(), 4);
+
+        let s = Str::new("");
+        assert_eq!(s.count_non_ascii_bytes(), 0);
+        assert_eq!(s.count_non_ascii_chars(), 0);
+    }
+
+    #[test]
+    fn test_ascii_ratio() {
+        assert_eq!(Str::new("").ascii_ratio(), 0.0);
+        assert_eq!(Str::new("abc").ascii_ratio(), 1.0);
+        assert_eq!(Str::new("世界").ascii_ratio(), 0.0);
+        assert_close(Str::new("ab世").ascii_ratio(), 0.4);
+        assert_close(Str::new("Hello, 世界!").ascii_ratio(), 8.0 / 14.0);
+    }
+
+    #[test]
+    fn test_detect_script_single() {
+        let script = |s| Str::new(s).detect_script();
+        assert_eq!(script("hello world"), ScriptHint::Latin);
+        assert_eq!(script("Çà et là, déjà vu! 123"), ScriptHint::Latin);
+        assert_eq!(
+            script("Съешь же ещё этих мягких булок"),
+            ScriptHint::Cyrillic
+        );
+        assert_eq!(script("中文文本处理"), ScriptHint::CJK);
+        assert_eq!(script("ひらがなとカタカナ"), ScriptHint::CJK);
+        assert_eq!(script("안녕하세요"), ScriptHint::CJK);
+        assert_eq!(script("مرحبا بالعالم"), ScriptHint::Arabic);
+        assert_eq!(script("שלום עולם"), ScriptHint::Hebrew);
+        assert_eq!(script("नमस्ते दुनिया"), ScriptHint::Devanagari);
+    }
+
+    #[test]
+    fn test_detect_script_mixed_and_unknown() {
+        let script = |s| Str::new(s).detect_script();
+        assert_eq!(script("hello 世界"), ScriptHint::Mixed);
+        assert_eq!(script("Москва Moscow"), ScriptHint::Mixed);
+        assert_eq!(script("Welcome to 東京 Tokyo, Japan"), ScriptHint::Latin);
+        assert_eq!(script(""), ScriptHint::Unknown);
+        assert_eq!(script("123 !?"), ScriptHint::Unknown);
+        assert_eq!(script("Καλημέρα"), ScriptHint::Unknown);
+    }
+
+    #[cfg(feature = "unicode-general-category")]
+    #[test]
+    fn test_rune_category_counts() {
+        use RuneCategory::*;
+
+        let samples = [
+            ("ABÉП", UppercaseLetter),
+            ("héllo", LowercaseLetter),
+            ("ǅ", TitlecaseLetter),
+            ("ʰー", ModifierLetter),
+            ("世界אب", OtherLetter),
+            ("\u{301}", NonspacingMark),
+            ("\u{903}", SpacingMark),
+            ("\u{20DD}", EnclosingMark),
+            ("42٣", DecimalNumber),
+            ("Ⅻ", LetterNumber),
+            ("²½", OtherNumber),
+            ("_", ConnectorPunctuation),
+            ("-—", DashPunctuation),
+            ("([", OpenPunctuation),
+            (")]", ClosePunctuation),
+            ("«", InitialPunctuation),
+            ("»", FinalPunctuation),
+            ("!,.", OtherPunctuation),
+            ("+=", MathSymbol),
+            ("$€", CurrencySymbol),
+            ("^", ModifierSymbol),
+            ("🎉©", OtherSymbol),
+            (" \u{A0}", SpaceSeparator),
+            ("\u{2028}", LineSeparator),
+            ("\u{2029}", ParagraphSeparator),
+            ("\t\0", Control),
+            ("\u{200D}", Format),
+            ("\u{E000}", PrivateUse),
+            ("\u{FFFF}\u{378}", Unassigned),
+        ];
+        let text: String = samples.iter().map(|&(sample, _)| sample).collect();
+        let counts = Str::new(&text).rune_category_counts();
+        for (sample, category) in samples {
+            assert_eq!(
+                counts[&category],
+                sample.chars().count(),
+                "{:?}",
+                sample
+            );
+        }
+        assert_eq!(counts.len(), samples.len());
+        assert!(!counts.contains_key(&Surrogate));
+    }
+
+    #[cfg(feature = "unicode-general-category")]
+    #[test]
+    fn test_rune_category_counts_letters() {
+        use RuneCategory::*;
+
+        let counts = Str::new("ǅungla ʰ").rune_category_counts();
+        assert_eq!(counts[&TitlecaseLetter], 1);
+        assert_eq!(counts[&LowercaseLetter], 5);
+        assert_eq!(counts[&ModifierLetter], 1);
+        assert!(Str::new("").rune_category_counts().is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+use crate::Str;
+
+/// Splits `s` before every uppercase letter that follows a non-uppercase
+/// character, and before the last letter of an acronym that is followed by
+/// a lowercase letter, as in `XMLParser`.
+fn camel_words(s: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut chars = s.char_indices().peekable();
+    let mut prev: Option<char> = None;
+    while let Some((at, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, next)| next);
+        let boundary = match prev {
+            Some(p) if c.is_uppercase() => {
+                !p.is_uppercase() || next.is_some_and(char::is_lowercase)
+            }
+            _ => false,
+        };
+        if boundary {
+            words.push(&s[start..at]);
+            start = at;
+        }
+        prev = Some(c);
+    }
+    if start < s.len() {
+        words.push(&s[start..]);
+    }
+    words
+}
+
+/// Splits an identifier into its words: runs of alphanumeric characters
+/// further split with [`camel_words`].
+fn identifier_words(s: &str) -> Vec<&str> {
+    s.split(|c: char| !c.is_alphanumeric())
+        .flat_map(camel_words)
+        .collect()
+}
+
+fn push_capitalized(out: &mut String, word: &str) {
+    let mut chars = word.chars();
+    if let Some(first) = chars.next() {
+        out.extend(first.to_uppercase());
+        out.push_str(&chars.as_str().to_lowercase());
+    }
+}
+
+impl Str {
+    /// Splits a camel or pascal case identifier into its words.
+    ///
+    /// A word starts at an uppercase letter that follows a lowercase letter
+    /// or digit, and at an uppercase letter that is followed by a lowercase
+    /// one, so acronyms stay together. No other characters are treated
+    /// specially.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("parseXMLDocument");
+    /// assert_eq!(s.split_camel_case(), ["parse", "XML", "Document"]);
+    /// ```
+    pub fn split_camel_case(&self) -> Vec<&str> {
+        camel_words(self.as_ref())
+    }
+
+    /// Converts an identifier to `SCREAMING_SNAKE_CASE`, as used for
+    /// constants and environment variables.
+    ///
+    /// Words are split on non-alphanumeric characters and on case changes,
+    /// so camel, pascal, snake and kebab case input are all accepted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("helloWorld").to_screaming_snake_case().as_ref(), "HELLO_WORLD");
+    /// ```
+    pub fn to_screaming_snake_case(&self) -> Str {
+        let words: Vec<String> = identifier_words(self.as_ref())
+            .into_iter()
+            .map(str::to_uppercase)
+            .collect();
+        Str::new(&words.join("_"))
+    }
+
+    /// Converts an identifier to `PascalCase`.
+    ///
+    /// Each word is capitalized and the rest of it lowercased, so acronyms
+    /// become `Xml` rather than `XML`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("xml_http-request").to_pascal_case().as_ref(), "XmlHttpRequest");
+    /// ```
+    pub fn to_pascal_case(&self) -> Str {
+        let mut out = String::with_capacity(self.len());
+        for word in identifier_words(self.as_ref()) {
+            push_capitalized(&mut out, word);
+        }
+        Str::new(&out)
+    }
+
+    /// Converts an identifier to `UpperCamelCase`, the same as
+    /// [`Str::to_pascal_case`].
+    #[inline]
+    pub fn to_upper_camel_case(&self) -> Str {
+        self.to_pascal_case()
+    }
+
+    /// Converts an identifier to `lowerCamelCase`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("Hello_World").to_lower_camel_case().as_ref(), "helloWorld");
+    /// ```
+    pub fn to_lower_camel_case(&self) -> Str {
+        let mut out = String::with_capacity(self.len());
+        for (i, word) in identifier_words(self.as_ref()).into_iter().enumerate()
+        {
+            if i == 0 {
+                out.push_str(&word.to_lowercase());
+            } else {
+                push_capitalized(&mut out, word);
+            }
+        }
+        Str::new(&out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUTS: [&str; 5] = [
+        "helloWorldFoo",
+        "hello_world_foo",
+        "HelloWorldFoo",
+        "hello-world foo",
+        "HELLO_WORLD_FOO",
+    ];
+
+    #[test]
+    fn test_split_camel_case() {
+        let split = |s| Str::new(s).split_camel_case().join(" ");
+        assert_eq!(split("XMLHTTPParser"), "XMLHTTP Parser");
+        assert_eq!(split("XMLParser"), "XML Parser");
+        assert_eq!(split("helloWorldFoo"), "hello World Foo");
+        assert_eq!(split("HelloWorld"), "Hello World");
+        assert_eq!(split("ABC"), "ABC");
+        assert_eq!(split("hello"), "hello");
+        assert_eq!(split("http2Request"), "http2 Request");
+        assert_eq!(split("getÜberName"), "get Über Name");
+        assert_eq!(split("A"), "A");
+        assert!(Str::new("").split_camel_case().is_empty());
+    }
+
+    #[test]
+    fn test_split_camel_case_borrows() {
+        let s = Str::new("fooBar");
+        let words = s.split_camel_case();
+        let base = s.as_ref().as_ptr();
+        assert_eq!(words[0].as_ptr(), base);
+        assert_eq!(words[1].as_ptr(), base.wrapping_add(3));
+    }
+
+    #[test]
+    fn test_to_screaming_snake_case() {
+        for input in INPUTS {
+            let s = Str::new(input).to_screaming_snake_case();
+            assert_eq!(s.as_ref(), "HELLO_WORLD_FOO", "{}", input);
+        }
+        let screaming = |s| Str::new(s).to_screaming_snake_case().to_string();
+        assert_eq!(screaming("http2Request"), "HTTP2_REQUEST");
+        assert_eq!(screaming("XMLHttpRequest"), "XML_HTTP_REQUEST");
+        assert_eq!(screaming("__a__b__"), "A_B");
+        assert_eq!(screaming(""), "");
+    }
+
+    #[test]
+    fn test_to_upper_camel_case() {
+        for input in INPUTS {
+            let s = Str::new(input).to_upper_camel_case();
+            assert_eq!(s.as_ref(), "HelloWorldFoo", "{}", input);
+            assert_eq!(s, Str::new(input).to_pascal_case());
+        }
+        let pascal = |s| Str::new(s).to_upper_camel_case().to_string();
+        assert_eq!(pascal("http2Request"), "Http2Request");
+        assert_eq!(pascal("user_id_2fa"), "UserId2fa");
+        assert_eq!(pascal("élan vital"), "ÉlanVital");
+    }
+
+    #[test]
+    fn test_to_lower_camel_case() {
+        for input in INPUTS {
+            let s = Str::new(input).to_lower_camel_case();
+            assert_eq!(s.as_ref(), "helloWorldFoo", "{}", input);
+        }
+        let camel = |s| Str::new(s).to_lower_camel_case().to_string();
+        assert_eq!(camel("http2Request"), "http2Request");
+        assert_eq!(camel("HTTP2_REQUEST"), "http2Request");
+        assert_eq!(camel("X"), "x");
+        assert_eq!(camel(""), "");
+    }
+}
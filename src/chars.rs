@@ -0,0 +1,394 @@
+use crate::Str;
+
+impl Str {
+    /// Splits the string after the first `n` characters.
+    ///
+    /// Unlike [`str::split_at`], `n` is a character count rather than a byte
+    /// index, so finding the split point is O(n). Returns `None` if `n` is
+    /// greater than [`Str::char_count`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("hello");
+    /// assert_eq!(s.split_at_char_count(3), Some(("hel", "lo")));
+    /// assert_eq!(s.split_at_char_count(6), None);
+    /// ```
+    pub fn split_at_char_count(&self, n: usize) -> Option<(&str, &str)> {
+        let s: &str = self.as_ref();
+        let at = match s.char_indices().nth(n) {
+            Some((at, _)) => at,
+            None if s.chars().count() == n => s.len(),
+            None => return None,
+        };
+        Some(s.split_at(at))
+    }
+
+    /// Returns the `n`th character of the string, counting from zero.
+    ///
+    /// This is equivalent to `chars().nth(n)` and is O(n).
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("aé世");
+    /// assert_eq!(s.nth_char(2), Some('世'));
+    /// assert_eq!(s.nth_char(3), None);
+    /// ```
+    #[inline]
+    pub fn nth_char(&self, n: usize) -> Option<char> {
+        self.as_ref().chars().nth(n)
+    }
+
+    /// Returns the `n`th character of the string together with the byte
+    /// offset it starts at.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("aé世");
+    /// assert_eq!(s.nth_char_and_byte_offset(2), Some((3, '世')));
+    /// ```
+    #[inline]
+    pub fn nth_char_and_byte_offset(&self, n: usize) -> Option<(usize, char)> {
+        self.as_ref().char_indices().nth(n)
+    }
+
+    /// Returns the last `n` characters of the string, or the whole string
+    /// if it has fewer than `n` characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("hello 世界");
+    /// assert_eq!(s.last_n_chars(2), "世界");
+    /// assert_eq!(s.last_n_chars(20), "hello 世界");
+    /// ```
+    pub fn last_n_chars(&self, n: usize) -> &str {
+        let s: &str = self.as_ref();
+        let skip = s.chars().count().saturating_sub(n);
+        match s.char_indices().nth(skip) {
+            Some((at, _)) => &s[at..],
+            None => "",
+        }
+    }
+
+    /// Converts a byte offset into the index of the character starting
+    /// there.
+    ///
+    /// The length of the string converts to [`Str::char_count`]. Returns
+    /// `None` if `byte_index` is not a char boundary.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("aé世");
+    /// assert_eq!(s.byte_to_char_index(3), Some(2));
+    /// assert_eq!(s.byte_to_char_index(2), None);
+    /// ```
+    pub fn byte_to_char_index(&self, byte_index: usize) -> Option<usize> {
+        let s: &str = self.as_ref();
+        if !s.is_char_boundary(byte_index) {
+            return None;
+        }
+        Some(s[..byte_index].chars().count())
+    }
+
+    /// Converts a character index into the byte offset the character starts
+    /// at.
+    ///
+    /// [`Str::char_count`] converts to the length of the string. Returns
+    /// `None` if `char_index` is greater than that.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("aé世");
+    /// assert_eq!(s.char_to_byte_index(2), Some(3));
+    /// assert_eq!(s.char_to_byte_index(3), Some(6));
+    /// assert_eq!(s.char_to_byte_index(4), None);
+    /// ```
+    pub fn char_to_byte_index(&self, char_index: usize) -> Option<usize> {
+        self.split_at_char_count(char_index)
+            .map(|(head, _)| head.len())
+    }
+
+    /// Converts a byte offset into a 1-based `(line, column)` position, with
+    /// the column counted in characters.
+    ///
+    /// Lines are separated by `\n`; a `\r` before it counts as the last
+    /// character of its line. Returns `None` if `byte_index` is not a char
+    /// boundary.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("ab\nc世d");
+    /// assert_eq!(s.byte_to_line_col(7), Some((2, 3)));
+    /// ```
+    pub fn byte_to_line_col(
+        &self,
+        byte_index: usize,
+    ) -> Option<(usize, usize)> {
+        let s: &str = self.as_ref();
+        if !s.is_char_boundary(byte_index) {
+            return None;
+        }
+        let before = &s[..byte_index];
+        let line_start = before.rfind('\n').map_or(0, |at| at + 1);
+        let line = before.matches('\n').count() + 1;
+        Some((line, before[line_start..].chars().count() + 1))
+    }
+
+    /// Converts a 1-based `(line, column)` position as returned by
+    /// [`Str::byte_to_line_col`] back into a byte offset.
+    ///
+    /// The column may be one past the last character of the line, which
+    /// refers to the position of its line ending. Returns `None` if the
+    /// position is outside the string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("ab\nc世d");
+    /// assert_eq!(s.line_col_to_byte(2, 3), Some(7));
+    /// assert_eq!(s.line_col_to_byte(1, 3), Some(2));
+    /// assert_eq!(s.line_col_to_byte(1, 4), None);
+    /// ```
+    pub fn line_col_to_byte(&self, line: usize, col: usize) -> Option<usize> {
+        let s: &str = self.as_ref();
+        let line_start = match line {
+            0 => return None,
+            1 => 0,
+            _ => s.match_indices('\n').nth(line - 2)?.0 + 1,
+        };
+        let rest = &s[line_start..];
+        let text = &rest[..rest.find('\n').unwrap_or(rest.len())];
+        let col = col.checked_sub(1)?;
+        let offset = match text.char_indices().nth(col) {
+            Some((at, _)) => at,
+            None if text.chars().count() == col => text.len(),
+            None => return None,
+        };
+        Some(line_start + offset)
+    }
+
+    /// Alternates the characters of the string with those of `other`,
+    /// starting with the string, and appends whatever remains of the longer
+    /// one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("abc").interleave("12345").as_ref(), "a1b2c345");
+    /// ```
+    pub fn interleave(&self, other: &str) -> Str {
+        let mut out = String::with_capacity(self.len() + other.len());
+        let mut left = self.as_ref().chars();
+        let mut right = other.chars();
+        loop {
+            match (left.next(), right.next()) {
+                (None, None) => break,
+                (a, b) => out.extend(a.into_iter().chain(b)),
+            }
+        }
+        Str::new(&out)
+    }
+
+    /// Like [`Str::interleave`], but returns `None` unless the string and
+    /// `other` have the same number of characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("ab").interleave_exact("12").unwrap().as_ref(), "a1b2");
+    /// assert_eq!(Str::new("ab").interleave_exact("123"), None);
+    /// ```
+    pub fn interleave_exact(&self, other: &str) -> Option<Str> {
+        if self.char_count() != other.chars().count() {
+            return None;
+        }
+        Some(self.interleave(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_at_char_count_ascii() {
+        let s = Str::new("hello");
+        assert_eq!(s.split_at_char_count(3), Some(("hel", "lo")));
+    }
+
+    #[test]
+    fn test_split_at_char_count_unicode() {
+        let s = Str::new("aé世b");
+        assert_eq!(s.split_at_char_count(1), Some(("a", "é世b")));
+        assert_eq!(s.split_at_char_count(2), Some(("aé", "世b")));
+        assert_eq!(s.split_at_char_count(3), Some(("aé世", "b")));
+    }
+
+    #[test]
+    fn test_split_at_char_count_edges() {
+        let s = Str::new("aé世b");
+        assert_eq!(s.split_at_char_count(0), Some(("", "aé世b")));
+        assert_eq!(s.split_at_char_count(s.char_count()), Some(("aé世b", "")));
+        assert_eq!(Str::new("").split_at_char_count(0), Some(("", "")));
+    }
+
+    #[test]
+    fn test_split_at_char_count_out_of_range() {
+        let s = Str::new("aé世b");
+        assert_eq!(s.split_at_char_count(5), None);
+        assert_eq!(Str::new("").split_at_char_count(1), None);
+    }
+
+    #[test]
+    fn test_interleave() {
+        let interleave = |a: &str, b| Str::new(a).interleave(b).to_string();
+        assert_eq!(interleave("abc", "123"), "a1b2c3");
+        assert_eq!(interleave("abcde", "12"), "a1b2cde");
+        assert_eq!(interleave("abc", "12345"), "a1b2c345");
+        assert_eq!(interleave("", "123"), "123");
+        assert_eq!(interleave("abc", ""), "abc");
+        assert_eq!(interleave("世界", "ab"), "世a界b");
+    }
+
+    #[test]
+    fn test_interleave_exact() {
+        let s = Str::new("abc");
+        assert_eq!(s.interleave_exact("123"), Some(Str::new("a1b2c3")));
+        assert_eq!(s.interleave_exact("1"), None);
+        assert_eq!(s.interleave_exact("1234"), None);
+        assert_eq!(s.interleave_exact(""), None);
+        assert_eq!(Str::new("").interleave_exact(""), Some(Str::new("")));
+        assert_eq!(
+            Str::new("éè").interleave_exact("世界"),
+            Some(Str::new("é世è界"))
+        );
+    }
+
+    #[test]
+    fn test_nth_char() {
+        let s = Str::new("abc");
+        assert_eq!(s.nth_char(0), Some('a'));
+        assert_eq!(s.nth_char(2), Some('c'));
+        assert_eq!(s.nth_char(3), None);
+
+        let s = Str::new("é世🎉");
+        assert_eq!(s.nth_char(0), Some('é'));
+        assert_eq!(s.nth_char(1), Some('世'));
+        assert_eq!(s.nth_char(2), Some('🎉'));
+        assert_eq!(s.nth_char(3), None);
+        assert_eq!(Str::new("").nth_char(0), None);
+    }
+
+    #[test]
+    fn test_nth_char_and_byte_offset() {
+        let s = Str::new("abc");
+        assert_eq!(s.nth_char_and_byte_offset(1), Some((1, 'b')));
+        assert_eq!(s.nth_char_and_byte_offset(3), None);
+
+        let s = Str::new("é世🎉!");
+        assert_eq!(s.nth_char_and_byte_offset(0), Some((0, 'é')));
+        assert_eq!(s.nth_char_and_byte_offset(1), Some((2, '世')));
+        assert_eq!(s.nth_char_and_byte_offset(2), Some((5, '🎉')));
+        assert_eq!(s.nth_char_and_byte_offset(3), Some((9, '!')));
+        assert_eq!(s.nth_char_and_byte_offset(4), None);
+    }
+
+    #[test]
+    fn test_last_n_chars() {
+        let s = Str::new("hello");
+        assert_eq!(s.last_n_chars(0), "");
+        assert_eq!(s.last_n_chars(3), "llo");
+        assert_eq!(s.last_n_chars(5), "hello");
+        assert_eq!(s.last_n_chars(6), "hello");
+
+        let s = Str::new("é世🎉");
+        assert_eq!(s.last_n_chars(1), "🎉");
+        assert_eq!(s.last_n_chars(2), "世🎉");
+        assert_eq!(s.last_n_chars(3), "é世🎉");
+        assert_eq!(s.last_n_chars(usize::MAX), "é世🎉");
+        assert_eq!(Str::new("").last_n_chars(1), "");
+    }
+
+    // Byte offsets of "aé\n世🎉\nz": a=0, é=1, \n=3, 世=4, 🎉=7, \n=11, z=12.
+    const MULTILINE: &str = "aé\n世🎉\nz";
+
+    #[test]
+    fn test_byte_to_char_index() {
+        let s = Str::new(MULTILINE);
+        let pairs = [
+            (0, 0),
+            (1, 1),
+            (3, 2),
+            (4, 3),
+            (7, 4),
+            (11, 5),
+            (12, 6),
+            (13, 7),
+        ];
+        for (byte, char) in pairs {
+            assert_eq!(s.byte_to_char_index(byte), Some(char), "{}", byte);
+        }
+        for byte in [2, 5, 6, 8, 14] {
+            assert_eq!(s.byte_to_char_index(byte), None, "{}", byte);
+        }
+    }
+
+    #[test]
+    fn test_char_to_byte_index() {
+        let s = Str::new(MULTILINE);
+        let pairs = [
+            (0, 0),
+            (1, 1),
+            (3, 2),
+            (4, 3),
+            (7, 4),
+            (11, 5),
+            (12, 6),
+            (13, 7),
+        ];
+        for (byte, char) in pairs {
+            assert_eq!(s.char_to_byte_index(char), Some(byte), "{}", char);
+        }
+        assert_eq!(s.char_to_byte_index(8), None);
+        assert_eq!(Str::new("").char_to_byte_index(0), Some(0));
+    }
+
+    #[test]
+    fn test_byte_to_line_col() {
+        let s = Str::new(MULTILINE);
+        let positions = [
+            (0, (1, 1)),
+            (1, (1, 2)),
+            (3, (1, 3)),
+            (4, (2, 1)),
+            (7, (2, 2)),
+            (11, (2, 3)),
+            (12, (3, 1)),
+            (13, (3, 2)),
+        ];
+        for (byte, position) in positions {
+            assert_eq!(s.byte_to_line_col(byte), Some(position), "{}", byte);
+            assert_eq!(s.line_col_to_byte(position.0, position.1), Some(byte));
+        }
+        assert_eq!(s.byte_to_line_col(5), None);
+        assert_eq!(s.byte_to_line_col(14), None);
+    }
+
+    #[test]
+    fn test_line_col_to_byte_out_of_range() {
+        let s = Str::new(MULTILINE);
+        assert_eq!(s.line_col_to_byte(0, 1), None);
+        assert_eq!(s.line_col_to_byte(1, 0), None);
+        assert_eq!(s.line_col_to_byte(1, 4), None);
+        assert_eq!(s.line_col_to_byte(3, 3), None);
+        assert_eq!(s.line_col_to_byte(4, 1), None);
+        assert_eq!(Str::new("").line_col_to_byte(1, 1), Some(0));
+        assert_eq!(Str::new("a\n").line_col_to_byte(2, 1), Some(2));
+    }
+}
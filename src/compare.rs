@@ -0,0 +1,299 @@
+use std::cmp::Ordering;
+
+use crate::Str;
+
+/// Splits the ASCII digit run at the start of `s` off the rest.
+fn split_digits(s: &str) -> (&str, &str) {
+    let end = s
+        .bytes()
+        .position(|b| !b.is_ascii_digit())
+        .unwrap_or(s.len());
+    s.split_at(end)
+}
+
+/// Compares two runs of ASCII digits by their numeric value, without
+/// parsing them, so runs of any length are supported.
+fn compare_digits(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// Returns the collator for the root collation order, the Unicode default
+/// order tailored by CLDR, building it on first use.
+#[cfg(feature = "icu_collator")]
+fn root_collator() -> &'static icu_collator::CollatorBorrowed<'static> {
+    static COLLATOR: std::sync::OnceLock<
+        icu_collator::CollatorBorrowed<'static>,
+    > = std::sync::OnceLock::new();
+    COLLATOR.get_or_init(|| {
+        icu_collator::Collator::try_new(Default::default(), Default::default())
+            .expect("root collation data is built in")
+    })
+}
+
+impl Str {
+    /// Compares the string with `other` in natural sort order, where runs of
+    /// ASCII digits compare by numeric value, so `"item9"` sorts before
+    /// `"item10"`.
+    ///
+    /// Everything else compares character by character. When two digit runs
+    /// have the same value but a different number of leading zeros, the
+    /// run with fewer zeros sorts first, but only if the strings are
+    /// otherwise equal. The comparison does not allocate and returns
+    /// [`Ordering::Equal`] only for identical strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let mut files = vec!["file10.txt", "file9.txt", "file1.txt"];
+    /// files.sort_by(|a, b| Str::new(a).compare_natural(b));
+    /// assert_eq!(files, ["file1.txt", "file9.txt", "file10.txt"]);
+    /// ```
+    pub fn compare_natural(&self, other: &str) -> Ordering {
+        let mut a: &str = self.as_ref();
+        let mut b = other;
+        let mut zeros = Ordering::Equal;
+        loop {
+            let (Some(x), Some(y)) = (a.chars().next(), b.chars().next())
+            else {
+                return a.len().cmp(&b.len()).then(zeros);
+            };
+
+            if x.is_ascii_digit() && y.is_ascii_digit() {
+                let (x_digits, x_rest) = split_digits(a);
+                let (y_digits, y_rest) = split_digits(b);
+                match compare_digits(x_digits, y_digits) {
+                    Ordering::Equal => {
+                        zeros = zeros.then(x_digits.len().cmp(&y_digits.len()))
+                    }
+                    unequal => return unequal,
+                }
+                a = x_rest;
+                b = y_rest;
+            } else {
+                match x.cmp(&y) {
+                    Ordering::Equal => {}
+                    unequal => return unequal,
+                }
+                a = &a[x.len_utf8()..];
+                b = &b[y.len_utf8()..];
+            }
+        }
+    }
+
+    /// Returns a key for the string whose byte order is the collation order
+    /// of the string, as compared by [`Str::compare_collation_key`], so
+    /// that keys can be sorted and stored without a collator.
+    ///
+    /// With the `icu_collator` feature the key is the Unicode collation
+    /// algorithm sort key of the string for the root locale, written in
+    /// lower case hex digits. Strings that differ only in ways the
+    /// collation ignores, such as their normalization form, have the same
+    /// key. Without the feature the key is the string itself, which sorts
+    /// by code point.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let mut words = vec![Str::new("cherry"), Str::new("apple"), Str::new("banana")];
+    /// words.sort_by_cached_key(|word| word.to_sortable_key().to_string());
+    /// assert_eq!(words, ["apple", "banana", "cherry"].map(Str::new));
+    /// ```
+    pub fn to_sortable_key(&self) -> Str {
+        #[cfg(feature = "icu_collator")]
+        {
+            let mut key = Vec::new();
+            let Ok(()) =
+                root_collator().write_sort_key_to(self.as_ref(), &mut key);
+            let mut hex = String::with_capacity(key.len() * 2);
+            for b in key {
+                hex.push_str(&format!("{:02x}", b));
+            }
+            Str::new(&hex)
+        }
+        #[cfg(not(feature = "icu_collator"))]
+        {
+            self.clone()
+        }
+    }
+
+    /// Compares `a` and `b` in collation order, giving the same result as
+    /// comparing their [`Str::to_sortable_key`] keys without building them.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// # use std::cmp::Ordering;
+    /// let (a, b) = (Str::new("apple"), Str::new("banana"));
+    /// assert_eq!(Str::compare_collation_key(&a, &b), Ordering::Less);
+    /// ```
+    pub fn compare_collation_key(a: &Str, b: &Str) -> Ordering {
+        #[cfg(feature = "icu_collator")]
+        {
+            root_collator().compare(a.as_ref(), b.as_ref())
+        }
+        #[cfg(not(feature = "icu_collator"))]
+        {
+            a.as_ref().cmp(b.as_ref())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn natural(a: &str, b: &str) -> Ordering {
+        Str::new(a).compare_natural(b)
+    }
+
+    #[test]
+    fn test_compare_natural_numbers() {
+        assert_eq!(natural("item9", "item10"), Ordering::Less);
+        assert_eq!(natural("item10", "item9"), Ordering::Greater);
+        assert_eq!(natural("item10", "item10"), Ordering::Equal);
+        assert_eq!(natural("1.2.10", "1.2.9"), Ordering::Greater);
+        assert_eq!(natural("1.10.0", "1.9.9"), Ordering::Greater);
+        assert_eq!(natural("a2b", "a2c"), Ordering::Less);
+        assert_eq!(natural("a", "a1"), Ordering::Less);
+        assert_eq!(natural("9", "a"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_compare_natural_long_runs() {
+        let big = "1".repeat(50);
+        let bigger = format!("2{}", "0".repeat(49));
+        assert_eq!(natural(&big, &bigger), Ordering::Less);
+        assert_eq!(natural(&format!("x{}", big), "x9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_compare_natural_no_digits() {
+        for (a, b) in
+            [("apple", "banana"), ("abc", "abd"), ("", "a"), ("B", "a")]
+        {
+            assert_eq!(natural(a, b), a.cmp(b));
+            assert_eq!(natural(b, a), b.cmp(a));
+        }
+        assert_eq!(natural("", ""), Ordering::Equal);
+        assert_eq!(natural("世界", "世界"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_compare_natural_leading_zeros() {
+        assert_eq!(natural("007", "7"), Ordering::Greater);
+        assert_eq!(natural("7", "007"), Ordering::Less);
+        assert_eq!(natural("007", "8"), Ordering::Less);
+        assert_eq!(natural("a007b", "a7c"), Ordering::Less);
+        assert_eq!(natural("0", "00"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_compare_natural_sort() {
+        let mut items = vec!["x10", "x2", "x02", "x1", "y", "x", "x2a", "x100"];
+        items.sort_by(|a, b| natural(a, b));
+        assert_eq!(items, ["x", "x1", "x2", "x02", "x2a", "x10", "x100", "y"]);
+    }
+
+    const WORDS: [&str; 16] = [
+        "zebra",
+        "Apple",
+        "apple",
+        "éclair",
+        "eclair",
+        "Zürich",
+        "zulu",
+        "Ångström",
+        "東京",
+        "Москва",
+        "мир",
+        "Αθήνα",
+        "résumé",
+        "resume",
+        "10",
+        "",
+    ];
+
+    fn sorted_by_key(words: &[&str]) -> Vec<Str> {
+        let mut words: Vec<Str> = words.iter().map(|&w| Str::new(w)).collect();
+        words.sort_by_cached_key(|word| word.to_sortable_key().to_string());
+        words
+    }
+
+    fn sorted_by_compare(words: &[&str]) -> Vec<Str> {
+        let mut words: Vec<Str> = words.iter().map(|&w| Str::new(w)).collect();
+        words.sort_by(Str::compare_collation_key);
+        words
+    }
+
+    #[test]
+    fn test_to_sortable_key_matches_compare() {
+        assert_eq!(sorted_by_key(&WORDS), sorted_by_compare(&WORDS));
+        for a in WORDS.map(Str::new) {
+            for b in WORDS.map(Str::new) {
+                let keys = a
+                    .to_sortable_key()
+                    .as_ref()
+                    .cmp(b.to_sortable_key().as_ref());
+                assert_eq!(
+                    keys,
+                    Str::compare_collation_key(&a, &b),
+                    "{} {}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[cfg(not(feature = "icu_collator"))]
+    #[test]
+    fn test_to_sortable_key_code_points() {
+        let mut expected = WORDS;
+        expected.sort();
+        assert_eq!(sorted_by_key(&WORDS), expected.map(Str::new));
+        assert_eq!(Str::new("été").to_sortable_key().as_ref(), "été");
+    }
+
+    #[cfg(feature = "icu_collator")]
+    #[test]
+    fn test_to_sortable_key_collation() {
+        assert_eq!(
+            sorted_by_key(&WORDS),
+            [
+                "",
+                "10",
+                "Ångström",
+                "apple",
+                "Apple",
+                "eclair",
+                "éclair",
+                "resume",
+                "résumé",
+                "zebra",
+                "zulu",
+                "Zürich",
+                "Αθήνα",
+                "мир",
+                "Москва",
+                "東京",
+            ]
+            .map(Str::new)
+        );
+
+        let key = Str::new("ab").to_sortable_key();
+        assert!(key.as_ref().bytes().all(|b| b.is_ascii_hexdigit()));
+        assert_eq!(
+            Str::new("e\u{301}").to_sortable_key(),
+            Str::new("\u{e9}").to_sortable_key()
+        );
+        assert_eq!(
+            Str::compare_collation_key(
+                &Str::new("e\u{301}"),
+                &Str::new("\u{e9}")
+            ),
+            Ordering::Equal
+        );
+    }
+}
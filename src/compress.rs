@@ -0,0 +1,512 @@
+use crate::Str;
+
+/// How far back, in bytes, [`Str::compress_lz77`] looks for matches.
+const LZ77_WINDOW: usize = 4096;
+/// Matches shorter than this are cheaper to store as literals.
+const LZ77_MIN_MATCH: usize = 4;
+const LZ77_MAX_MATCH: usize = 255;
+/// Maximum length in bytes of a string decoded by [`Str::decompress_lz77`].
+const MAX_LZ77_DECODED_LEN: usize = 1 << 26;
+
+const TAG_LITERALS: u8 = 0;
+const TAG_MATCH: u8 = 1;
+
+/// Error returned by [`Str::decompress_lz77`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecompressError {
+    /// The input ends in the middle of a token.
+    Truncated,
+    /// The byte at the given offset is not a valid token tag.
+    InvalidTag(usize),
+    /// The match at the given offset refers back past the start of the
+    /// output.
+    InvalidDistance(usize),
+    /// The decompressed size does not match the size in the header.
+    LengthMismatch,
+    /// The size in the header is larger than the limit of
+    /// [`Str::decompress_lz77`].
+    TooLong(usize),
+    /// The decompressed bytes are not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl std::fmt::Display for DecompressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => f.write_str("compressed data is truncated"),
+            Self::InvalidTag(at) => {
+                write!(f, "invalid token tag at byte {}", at)
+            }
+            Self::InvalidDistance(at) => {
+                write!(f, "match distance out of range at byte {}", at)
+            }
+            Self::LengthMismatch => {
+                f.write_str("decompressed size does not match the header")
+            }
+            Self::TooLong(size) => {
+                write!(f, "decompressed size {} is too large", size)
+            }
+            Self::InvalidUtf8 => {
+                f.write_str("decompressed bytes are not valid UTF-8")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecompressError {}
+
+/// Maximum length in bytes of a string decoded by [`Str::decode_rle`].
+const MAX_RLE_DECODED_LEN: usize = 1 << 26;
+
+/// Error returned by [`Str::decode_rle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RleError {
+    /// No run count starts at the given byte offset.
+    MissingCount(usize),
+    /// The run count ending at the given byte offset is not followed by a
+    /// character.
+    MissingChar(usize),
+    /// The run count at the given byte offset is zero or too large.
+    InvalidCount(usize),
+    /// The run at the given byte offset would make the decoded string longer
+    /// than the limit of [`Str::decode_rle`].
+    TooLong(usize),
+}
+
+impl std::fmt::Display for RleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingCount(at) => {
+                write!(f, "expected run count at byte {}", at)
+            }
+            Self::MissingChar(at) => {
+                write!(f, "expected character after run count at byte {}", at)
+            }
+            Self::InvalidCount(at) => {
+                write!(f, "invalid run count at byte {}", at)
+            }
+            Self::TooLong(at) => {
+                write!(f, "decoded string too long at run at byte {}", at)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RleError {}
+
+fn push_varint(out: &mut Vec<u8>, mut n: usize) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn read_varint(data: &[u8], at: &mut usize) -> Result<usize, DecompressError> {
+    let mut n = 0usize;
+    for shift in (0..usize::BITS).step_by(7) {
+        let b = *data.get(*at).ok_or(DecompressError::Truncated)?;
+        *at += 1;
+        let bits = (b & 0x7f) as usize;
+        if (bits << shift) >> shift != bits {
+            return Err(DecompressError::LengthMismatch);
+        }
+        n |= bits << shift;
+        if b & 0x80 == 0 {
+            return Ok(n);
+        }
+    }
+    Err(DecompressError::LengthMismatch)
+}
+
+fn push_literals(out: &mut Vec<u8>, literals: &[u8]) {
+    if literals.is_empty() {
+        return;
+    }
+    out.push(TAG_LITERALS);
+    push_varint(out, literals.len());
+    out.extend_from_slice(literals);
+}
+
+/// Returns the `(distance, length)` of the longest earlier match for the
+/// bytes starting at `pos`, preferring the closest one, if any.
+fn longest_match(bytes: &[u8], pos: usize) -> Option<(usize, usize)> {
+    let max = LZ77_MAX_MATCH.min(bytes.len() - pos);
+    let mut best = None;
+    let mut best_len = LZ77_MIN_MATCH - 1;
+    for start in (pos.saturating_sub(LZ77_WINDOW)..pos).rev() {
+        let len = (0..max)
+            .take_while(|&i| bytes[start + i] == bytes[pos + i])
+            .count();
+        if len > best_len {
+            best_len = len;
+            best = Some((pos - start, len));
+            if len == max {
+                break;
+            }
+        }
+    }
+    best
+}
+
+impl Str {
+    /// Compresses the string with a simple LZ77 scheme.
+    ///
+    /// The output starts with the uncompressed size and is followed by a
+    /// sequence of tokens, each either a run of literal bytes or a
+    /// back-reference into the previous 4096 bytes, so it can be decoded
+    /// with [`Str::decompress_lz77`] alone. Sizes are stored as LEB128
+    /// varints.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new(&"abc".repeat(100));
+    /// let data = s.compress_lz77();
+    /// assert!(data.len() < 20);
+    /// assert_eq!(Str::decompress_lz77(&data).unwrap(), s);
+    /// ```
+    pub fn compress_lz77(&self) -> Vec<u8> {
+        let bytes = self.as_ref().as_bytes();
+        let mut out = Vec::new();
+        push_varint(&mut out, bytes.len());
+
+        let mut literals_start = 0;
+        let mut pos = 0;
+        while pos < bytes.len() {
+            match longest_match(bytes, pos) {
+                Some((distance, len)) => {
+                    push_literals(&mut out, &bytes[literals_start..pos]);
+                    out.push(TAG_MATCH);
+                    push_varint(&mut out, distance);
+                    push_varint(&mut out, len);
+                    pos += len;
+                    literals_start = pos;
+                }
+                None => pos += 1,
+            }
+        }
+        push_literals(&mut out, &bytes[literals_start..]);
+        out
+    }
+
+    /// Decompresses data produced by [`Str::compress_lz77`].
+    ///
+    /// The decompressed string may be at most 64 MiB long, so that a tiny
+    /// input with a huge size in its header cannot exhaust memory.
+    ///
+    /// # Errors
+    /// Returns a [`DecompressError`] if the data is malformed, does not
+    /// decompress to the size in its header, claims a size above 64 MiB or
+    /// is not valid UTF-8 once decompressed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{DecompressError, Str};
+    /// let data = [3, 0, 1, b'a', 1, 1, 2];
+    /// assert_eq!(Str::decompress_lz77(&data).unwrap().as_ref(), "aaa");
+    /// assert_eq!(
+    ///     Str::decompress_lz77(&[3, 0, 1, b'a']),
+    ///     Err(DecompressError::LengthMismatch),
+    /// );
+    /// ```
+    pub fn decompress_lz77(data: &[u8]) -> Result<Str, DecompressError> {
+        let mut at = 0;
+        let size = read_varint(data, &mut at)?;
+        if size > MAX_LZ77_DECODED_LEN {
+            return Err(DecompressError::TooLong(size));
+        }
+        let mut out: Vec<u8> = Vec::with_capacity(size);
+        while at < data.len() {
+            let tag_at = at;
+            at += 1;
+            match data[tag_at] {
+                TAG_LITERALS => {
+                    let len = read_varint(data, &mut at)?;
+                    let end = at
+                        .checked_add(len)
+                        .ok_or(DecompressError::Truncated)?;
+                    let literals =
+                        data.get(at..end).ok_or(DecompressError::Truncated)?;
+                    out.extend_from_slice(literals);
+                    at = end;
+                }
+                TAG_MATCH => {
+                    let distance = read_varint(data, &mut at)?;
+                    let len = read_varint(data, &mut at)?;
+                    if distance == 0 || distance > out.len() {
+                        return Err(DecompressError::InvalidDistance(tag_at));
+                    }
+                    if len > size - out.len() {
+                        return Err(DecompressError::LengthMismatch);
+                    }
+                    let start = out.len() - distance;
+                    for i in 0..len {
+                        out.push(out[start + i]);
+                    }
+                }
+                _ => return Err(DecompressError::InvalidTag(tag_at)),
+            }
+            if out.len() > size {
+                return Err(DecompressError::LengthMismatch);
+            }
+        }
+
+        if out.len() != size {
+            return Err(DecompressError::LengthMismatch);
+        }
+        match std::str::from_utf8(&out) {
+            Ok(s) => Ok(Str::new(s)),
+            Err(_) => Err(DecompressError::InvalidUtf8),
+        }
+    }
+
+    /// Run-length encodes the string, writing each run of identical
+    /// characters as its decimal count followed by the character.
+    ///
+    /// Digits and `\` are escaped with a preceding `\`, so that a run of
+    /// them cannot be mistaken for part of the count.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("aaabbc").encode_rle().as_ref(), "3a2b1c");
+    /// assert_eq!(Str::new("x111").encode_rle().as_ref(), "1x3\\1");
+    /// ```
+    pub fn encode_rle(&self) -> Str {
+        let mut out = String::new();
+        let mut chars = self.as_ref().chars().peekable();
+        while let Some(c) = chars.next() {
+            let mut count = 1;
+            while chars.next_if_eq(&c).is_some() {
+                count += 1;
+            }
+            out.push_str(&count.to_string());
+            if c.is_ascii_digit() || c == '\\' {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        Str::new(&out)
+    }
+
+    /// Decodes a string produced by [`Str::encode_rle`].
+    ///
+    /// The decoded string may be at most 64 MiB long, so that a tiny input
+    /// with a huge run count cannot exhaust memory.
+    ///
+    /// # Errors
+    /// Returns an [`RleError`] if the input is not valid run-length encoded
+    /// text or decodes to more than 64 MiB.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("12a1b").decode_rle().unwrap().as_ref(), "aaaaaaaaaaaab");
+    /// assert!(Str::new("a3").decode_rle().is_err());
+    /// ```
+    pub fn decode_rle(&self) -> Result<Str, RleError> {
+        let s: &str = self.as_ref();
+        let mut out = String::new();
+        let mut decoded_len = 0usize;
+        let mut chars = s.char_indices().peekable();
+        while let Some(&(start, _)) = chars.peek() {
+            let mut end = start;
+            while let Some((at, _)) = chars.next_if(|(_, c)| c.is_ascii_digit())
+            {
+                end = at + 1;
+            }
+            if end == start {
+                return Err(RleError::MissingCount(start));
+            }
+            let count: usize = match s[start..end].parse() {
+                Ok(0) | Err(_) => return Err(RleError::InvalidCount(start)),
+                Ok(count) => count,
+            };
+            let c = match chars.next() {
+                Some((_, '\\')) => chars.next(),
+                other => other,
+            };
+            let Some((_, c)) = c else {
+                return Err(RleError::MissingChar(end));
+            };
+            decoded_len = count
+                .checked_mul(c.len_utf8())
+                .and_then(|len| decoded_len.checked_add(len))
+                .filter(|&len| len <= MAX_RLE_DECODED_LEN)
+                .ok_or(RleError::TooLong(start))?;
+            out.extend(std::iter::repeat_n(c, count));
+        }
+        Ok(Str::new(&out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(s: &str) -> Vec<u8> {
+        let data = Str::new(s).compress_lz77();
+        assert_eq!(Str::decompress_lz77(&data).unwrap().as_ref(), s);
+        data
+    }
+
+    #[test]
+    fn test_lz77_empty() {
+        assert_eq!(roundtrip(""), [0]);
+    }
+
+    #[test]
+    fn test_lz77_repetitive() {
+        let s = "a".repeat(10_000);
+        assert!(roundtrip(&s).len() < 200);
+
+        let s = "<li>item</li>\n".repeat(500);
+        assert!(roundtrip(&s).len() < s.len() / 10);
+    }
+
+    #[test]
+    fn test_lz77_random_looking() {
+        let mut state = 0x2545_f491u32;
+        let s: String = (0..2000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (b'!' + (state % 90) as u8) as char
+            })
+            .collect();
+        let data = roundtrip(&s);
+        assert!(data.len() <= s.len() + 8);
+    }
+
+    #[test]
+    fn test_lz77_roundtrip_unicode() {
+        for s in ["x", "abcd", "Hello, 世界! 世界! 世界! 🎉🎉🎉🎉", "abababab"]
+        {
+            roundtrip(s);
+        }
+    }
+
+    #[test]
+    fn test_decompress_lz77_invalid() {
+        assert_eq!(Str::decompress_lz77(&[]), Err(DecompressError::Truncated));
+        assert_eq!(
+            Str::decompress_lz77(&[3, 0, 5, b'a']),
+            Err(DecompressError::Truncated)
+        );
+        assert_eq!(
+            Str::decompress_lz77(&[1, 7]),
+            Err(DecompressError::InvalidTag(1))
+        );
+        assert_eq!(
+            Str::decompress_lz77(&[4, 1, 1, 4]),
+            Err(DecompressError::InvalidDistance(1))
+        );
+        assert_eq!(
+            Str::decompress_lz77(&[2, 0, 1, b'a']),
+            Err(DecompressError::LengthMismatch)
+        );
+        assert_eq!(
+            Str::decompress_lz77(&[1, 0, 1, 0xff]),
+            Err(DecompressError::InvalidUtf8)
+        );
+    }
+
+    #[test]
+    fn test_decompress_lz77_too_long() {
+        let mut data = Vec::new();
+        push_varint(&mut data, usize::MAX);
+        data.extend_from_slice(&[TAG_LITERALS, 1, b'a', TAG_MATCH, 1]);
+        push_varint(&mut data, usize::MAX);
+        assert_eq!(
+            Str::decompress_lz77(&data),
+            Err(DecompressError::TooLong(usize::MAX))
+        );
+
+        let mut data = Vec::new();
+        push_varint(&mut data, MAX_LZ77_DECODED_LEN + 1);
+        assert_eq!(
+            Str::decompress_lz77(&data),
+            Err(DecompressError::TooLong(MAX_LZ77_DECODED_LEN + 1))
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_read_varint_overflow() {
+        let mut data = vec![0x80; 9];
+        data.push(0x01);
+        assert_eq!(read_varint(&data, &mut 0), Ok(1 << 63));
+
+        data[9] = 0x02;
+        assert_eq!(
+            read_varint(&data, &mut 0),
+            Err(DecompressError::LengthMismatch)
+        );
+    }
+
+    fn rle_roundtrip(s: &str) -> Str {
+        let encoded = Str::new(s).encode_rle();
+        assert_eq!(encoded.decode_rle().unwrap().as_ref(), s);
+        encoded
+    }
+
+    #[test]
+    fn test_rle_runs() {
+        assert_eq!(rle_roundtrip("aaaa").as_ref(), "4a");
+        assert_eq!(rle_roundtrip(&"z".repeat(123)).as_ref(), "123z");
+        assert_eq!(rle_roundtrip("aaabbc").as_ref(), "3a2b1c");
+        assert_eq!(rle_roundtrip("世世界").as_ref(), "2世1界");
+    }
+
+    #[test]
+    fn test_rle_alternating() {
+        assert_eq!(rle_roundtrip("abab").as_ref(), "1a1b1a1b");
+        assert_eq!(rle_roundtrip("a b").as_ref(), "1a1 1b");
+    }
+
+    #[test]
+    fn test_rle_empty() {
+        assert_eq!(rle_roundtrip("").as_ref(), "");
+    }
+
+    #[test]
+    fn test_rle_digits_and_escapes() {
+        assert_eq!(rle_roundtrip("1112").as_ref(), "3\\11\\2");
+        assert_eq!(rle_roundtrip("a\\\\").as_ref(), "1a2\\\\");
+        rle_roundtrip("2024-01-01 \\n");
+    }
+
+    #[test]
+    fn test_decode_rle_invalid() {
+        let decode = |s| Str::new(s).decode_rle();
+        assert_eq!(decode("a3"), Err(RleError::MissingCount(0)));
+        assert_eq!(decode("3a-"), Err(RleError::MissingCount(2)));
+        assert_eq!(decode("3a12"), Err(RleError::MissingChar(4)));
+        assert_eq!(decode("2\\"), Err(RleError::MissingChar(1)));
+        assert_eq!(decode("0a"), Err(RleError::InvalidCount(0)));
+        assert_eq!(
+            decode("1a99999999999999999999999b"),
+            Err(RleError::InvalidCount(2))
+        );
+    }
+
+    #[test]
+    fn test_decode_rle_too_long() {
+        let decode = |s: &str| Str::new(s).decode_rle();
+        assert_eq!(decode("99999999999a"), Err(RleError::TooLong(0)));
+        assert_eq!(
+            decode(&format!("2a{}b", usize::MAX)),
+            Err(RleError::TooLong(2))
+        );
+        assert_eq!(
+            decode(&format!("{}世", MAX_RLE_DECODED_LEN / 3 + 1)),
+            Err(RleError::TooLong(0))
+        );
+        assert_eq!(
+            decode(&format!("3a{}b", MAX_RLE_DECODED_LEN - 2)),
+            Err(RleError::TooLong(2))
+        );
+    }
+}
@@ -0,0 +1,1003 @@
+use std::ops::Range;
+
+use crate::Str;
+
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// The byte order mark, encoded as `EF BB BF` in UTF-8.
+const BOM: char = '\u{FEFF}';
+
+/// Error returned by [`Str::from_uri_component`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UriDecodeError {
+    /// A `%` at the given byte offset is not followed by two hex digits.
+    InvalidEscape(usize),
+    /// The decoded bytes are not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl std::fmt::Display for UriDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidEscape(at) => {
+                write!(f, "invalid percent escape at byte {}", at)
+            }
+            Self::InvalidUtf8 => {
+                f.write_str("decoded bytes are not valid UTF-8")
+            }
+        }
+    }
+}
+
+impl std::error::Error for UriDecodeError {}
+
+const BASE32_RFC4648: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32_CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+const BASE64_URL: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Error returned by [`Str::decode_arbitrary_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The character at the given byte offset is not in the alphabet.
+    InvalidChar(usize),
+    /// The input length does not match any encoded length.
+    InvalidLength,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidChar(at) => {
+                write!(f, "invalid base64url character at byte {}", at)
+            }
+            Self::InvalidLength => f.write_str("invalid base64url length"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Error returned by the base32 decoding methods of [`Str`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Base32Error {
+    /// The character at the given byte offset is not in the alphabet.
+    InvalidChar(usize),
+    /// The input length or padding does not match any encoded length.
+    InvalidLength,
+    /// The decoded bytes are not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl std::fmt::Display for Base32Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidChar(at) => {
+                write!(f, "invalid base32 character at byte {}", at)
+            }
+            Self::InvalidLength => f.write_str("invalid base32 length"),
+            Self::InvalidUtf8 => {
+                f.write_str("decoded bytes are not valid UTF-8")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Base32Error {}
+
+/// Error returned by [`Str::encode_bcd`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BcdError {
+    /// The character at the given byte offset is not an ASCII digit.
+    InvalidDigit(usize),
+}
+
+impl std::fmt::Display for BcdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDigit(at) => {
+                write!(f, "invalid decimal digit at byte {}", at)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BcdError {}
+
+fn encode_base32(bytes: &[u8], alphabet: &[u8; 32], pad: bool) -> Str {
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    for chunk in bytes.chunks(5) {
+        let mut buf = [0u8; 5];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let bits = buf.iter().fold(0u64, |acc, &b| acc << 8 | b as u64);
+        let symbols = (chunk.len() * 8).div_ceil(5);
+        for i in 0..symbols {
+            let index = (bits >> (35 - i * 5)) & 0x1f;
+            out.push(alphabet[index as usize] as char);
+        }
+        if pad {
+            out.extend(std::iter::repeat_n('=', 8 - symbols));
+        }
+    }
+    Str::new(&out)
+}
+
+/// Decodes a sequence of 5-bit base32 symbol values.
+fn decode_base32(
+    symbols: impl Iterator<Item = Result<u8, Base32Error>>,
+) -> Result<Str, Base32Error> {
+    let mut out = Vec::new();
+    let mut bits = 0u32;
+    let mut count = 0;
+    let mut symbols_len = 0;
+    for value in symbols {
+        bits = bits << 5 | value? as u32;
+        count += 5;
+        symbols_len += 1;
+        if count >= 8 {
+            count -= 8;
+            out.push((bits >> count) as u8);
+            bits &= (1 << count) - 1;
+        }
+    }
+    if matches!(symbols_len % 8, 1 | 3 | 6) {
+        return Err(Base32Error::InvalidLength);
+    }
+
+    match String::from_utf8(out) {
+        Ok(s) => Ok(Str::new(&s)),
+        Err(_) => Err(Base32Error::InvalidUtf8),
+    }
+}
+
+fn xor_bytes(data: &[u8], key: &[u8]) -> Vec<u8> {
+    if key.is_empty() {
+        return data.to_vec();
+    }
+    data.iter()
+        .zip(key.iter().cycle())
+        .map(|(d, k)| d ^ k)
+        .collect()
+}
+
+/// Relative frequencies of the letters `a` to `z` in English text, in
+/// percent.
+const ENGLISH_LETTER_FREQUENCIES: [f64; 26] = [
+    8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094, 6.966, 0.153,
+    0.772, 4.025, 2.406, 6.749, 7.507, 1.929, 0.095, 5.987, 6.327, 9.056,
+    2.758, 0.978, 2.360, 0.150, 1.974, 0.074,
+];
+
+/// Rotates every ASCII letter of `s` back by `shift` places, preserving case.
+fn caesar_shift_back(s: &str, shift: u8) -> String {
+    s.chars()
+        .map(|c| {
+            let base = match c {
+                'a'..='z' => b'a',
+                'A'..='Z' => b'A',
+                _ => return c,
+            };
+            (base + (c as u8 - base + 26 - shift % 26) % 26) as char
+        })
+        .collect()
+}
+
+/// Returns the chi-squared distance between the letter distribution of `s`
+/// and English. Lower scores look more like English.
+fn english_chi_squared(s: &str) -> f64 {
+    let mut counts = [0usize; 26];
+    for c in s.chars().filter(char::is_ascii_alphabetic) {
+        counts[(c.to_ascii_lowercase() as u8 - b'a') as usize] += 1;
+    }
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+
+    counts
+        .iter()
+        .zip(ENGLISH_LETTER_FREQUENCIES)
+        .map(|(&count, freq)| {
+            let expected = total as f64 * freq / 100.0;
+            let diff = count as f64 - expected;
+            diff * diff / expected
+        })
+        .sum()
+}
+
+#[inline]
+fn hex_value(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[inline]
+fn is_uri_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric()
+        || matches!(
+            b,
+            b'-' | b'_' | b'.' | b'!' | b'~' | b'*' | b'\'' | b'(' | b')'
+        )
+}
+
+impl Str {
+    /// Percent-encodes the string as a URI component.
+    ///
+    /// Every byte except `A-Z a-z 0-9 - _ . ! ~ * ' ( )` is replaced with
+    /// `%XX` using uppercase hex digits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("a b&c");
+    /// assert_eq!(s.to_uri_component().as_ref(), "a%20b%26c");
+    /// ```
+    pub fn to_uri_component(&self) -> Str {
+        let bytes = self.as_ref().as_bytes();
+        let mut out = String::with_capacity(bytes.len());
+        for &b in bytes {
+            if is_uri_unreserved(b) {
+                out.push(b as char);
+            } else {
+                out.push('%');
+                out.push(HEX_UPPER[(b >> 4) as usize] as char);
+                out.push(HEX_UPPER[(b & 0xf) as usize] as char);
+            }
+        }
+        Str::new(&out)
+    }
+
+    /// Decodes a percent-encoded URI component.
+    ///
+    /// Hex digits may be upper or lower case. Characters that are not
+    /// escaped are copied as is.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{Str, UriDecodeError};
+    /// let s = Str::new("a%20b%26c");
+    /// assert_eq!(s.from_uri_component().unwrap().as_ref(), "a b&c");
+    ///
+    /// let s = Str::new("100%");
+    /// assert_eq!(s.from_uri_component(), Err(UriDecodeError::InvalidEscape(3)));
+    /// ```
+    pub fn from_uri_component(&self) -> Result<Str, UriDecodeError> {
+        let bytes = self.as_ref().as_bytes();
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] != b'%' {
+                out.push(bytes[i]);
+                i += 1;
+                continue;
+            }
+
+            let hi = bytes.get(i + 1).copied().and_then(hex_value);
+            let lo = bytes.get(i + 2).copied().and_then(hex_value);
+            match (hi, lo) {
+                (Some(hi), Some(lo)) => out.push(hi << 4 | lo),
+                _ => return Err(UriDecodeError::InvalidEscape(i)),
+            }
+            i += 3;
+        }
+
+        match std::str::from_utf8(&out) {
+            Ok(s) => Ok(Str::new(s)),
+            Err(_) => Err(UriDecodeError::InvalidUtf8),
+        }
+    }
+
+    /// Encodes the UTF-8 bytes of the string as base32 using the RFC 4648
+    /// alphabet, padded with `=` to a multiple of 8 characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("foobar").encode_base32_rfc4648().as_ref(), "MZXW6YTBOI======");
+    /// ```
+    #[inline]
+    pub fn encode_base32_rfc4648(&self) -> Str {
+        encode_base32(self.as_ref().as_bytes(), BASE32_RFC4648, true)
+    }
+
+    /// Decodes padded RFC 4648 base32 into a string.
+    ///
+    /// Lower case letters are accepted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{Base32Error, Str};
+    /// let s = Str::new("MZXW6===");
+    /// assert_eq!(s.decode_base32_rfc4648().unwrap().as_ref(), "foo");
+    /// assert_eq!(
+    ///     Str::new("MZXW6").decode_base32_rfc4648(),
+    ///     Err(Base32Error::InvalidLength),
+    /// );
+    /// ```
+    pub fn decode_base32_rfc4648(&self) -> Result<Str, Base32Error> {
+        let s: &str = self.as_ref();
+        let body = s.trim_end_matches('=');
+        let padding = s.len() - body.len();
+        if !s.len().is_multiple_of(8) || !matches!(padding, 0 | 1 | 3 | 4 | 6) {
+            return Err(Base32Error::InvalidLength);
+        }
+
+        decode_base32(body.bytes().enumerate().map(|(at, b)| {
+            match b.to_ascii_uppercase() {
+                b @ b'A'..=b'Z' => Ok(b - b'A'),
+                b @ b'2'..=b'7' => Ok(b - b'2' + 26),
+                _ => Err(Base32Error::InvalidChar(at)),
+            }
+        }))
+    }
+
+    /// Encodes the UTF-8 bytes of the string as unpadded base32 using
+    /// Crockford's alphabet.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("foobar").encode_base32_crockford().as_ref(), "CSQPYRK1E8");
+    /// ```
+    #[inline]
+    pub fn encode_base32_crockford(&self) -> Str {
+        encode_base32(self.as_ref().as_bytes(), BASE32_CROCKFORD, false)
+    }
+
+    /// Decodes unpadded Crockford base32 into a string.
+    ///
+    /// Decoding is case insensitive, `I` and `L` are read as `1`, `O` is
+    /// read as `0` and `-` separators are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("csqp-yrk1-e8");
+    /// assert_eq!(s.decode_base32_crockford().unwrap().as_ref(), "foobar");
+    /// ```
+    pub fn decode_base32_crockford(&self) -> Result<Str, Base32Error> {
+        let symbols = self.as_ref().bytes().enumerate();
+        decode_base32(symbols.filter(|&(_, b)| b != b'-').map(|(at, b)| {
+            match b.to_ascii_uppercase() {
+                b'O' => Ok(0),
+                b'I' | b'L' => Ok(1),
+                b => match BASE32_CROCKFORD.iter().position(|&c| c == b) {
+                    Some(value) => Ok(value as u8),
+                    None => Err(Base32Error::InvalidChar(at)),
+                },
+            }
+        }))
+    }
+
+    /// XORs the UTF-8 bytes of the string with `key`, repeating the key as
+    /// needed.
+    ///
+    /// This is obfuscation, not encryption: it is trivially reversible and
+    /// leaks the key wherever the plaintext is known. The result is returned
+    /// as bytes because it is generally not valid UTF-8. An empty key leaves
+    /// the bytes unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("secret");
+    /// let data = s.xor_encrypt(b"key");
+    /// assert_eq!(Str::xor_decrypt(&data, b"key"), Ok(s));
+    /// ```
+    pub fn xor_encrypt(&self, key: &[u8]) -> Vec<u8> {
+        xor_bytes(self.as_ref().as_bytes(), key)
+    }
+
+    /// Reverses [`Str::xor_encrypt`] and validates that the result is UTF-8.
+    pub fn xor_decrypt(
+        data: &[u8],
+        key: &[u8],
+    ) -> Result<Str, std::str::Utf8Error> {
+        let bytes = xor_bytes(data, key);
+        std::str::from_utf8(&bytes).map(Str::new)
+    }
+
+    /// Encodes arbitrary bytes as text using unpadded base64url (RFC 4648
+    /// section 5).
+    ///
+    /// The output only contains `A-Z a-z 0-9 - _`, so it can be passed
+    /// through URLs, environment variables and shell arguments without
+    /// quoting.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::encode_arbitrary_bytes(&[0xfb, 0xff, 0x00]);
+    /// assert_eq!(s.as_ref(), "-_8A");
+    /// assert_eq!(s.decode_arbitrary_bytes().unwrap(), [0xfb, 0xff, 0x00]);
+    /// ```
+    pub fn encode_arbitrary_bytes(data: &[u8]) -> Str {
+        let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+        for chunk in data.chunks(3) {
+            let mut buf = [0u8; 3];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            let bits =
+                (buf[0] as u32) << 16 | (buf[1] as u32) << 8 | buf[2] as u32;
+            for i in 0..=chunk.len() {
+                let index = (bits >> (18 - i * 6)) & 0x3f;
+                out.push(BASE64_URL[index as usize] as char);
+            }
+        }
+        Str::new(&out)
+    }
+
+    /// Decodes bytes encoded with [`Str::encode_arbitrary_bytes`].
+    pub fn decode_arbitrary_bytes(&self) -> Result<Vec<u8>, DecodeError> {
+        let bytes = self.as_ref().as_bytes();
+        if bytes.len() % 4 == 1 {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let mut out = Vec::with_capacity(bytes.len() / 4 * 3 + 2);
+        let mut bits = 0u32;
+        let mut count = 0;
+        for (at, &b) in bytes.iter().enumerate() {
+            let value = match b {
+                b'A'..=b'Z' => b - b'A',
+                b'a'..=b'z' => b - b'a' + 26,
+                b'0'..=b'9' => b - b'0' + 52,
+                b'-' => 62,
+                b'_' => 63,
+                _ => return Err(DecodeError::InvalidChar(at)),
+            };
+            bits = bits << 6 | value as u32;
+            count += 6;
+            if count >= 8 {
+                count -= 8;
+                out.push((bits >> count) as u8);
+                bits &= (1 << count) - 1;
+            }
+        }
+        Ok(out)
+    }
+
+    /// Converts possibly invalid UTF-8 into a [`Str`], replacing each invalid
+    /// sequence with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// Invalid sequences are delimited like [`String::from_utf8_lossy`]
+    /// does, following the "maximal subpart" practice of the W3C and
+    /// WHATWG encoding standards, so consecutive invalid bytes may produce
+    /// several replacement characters. Also returns the byte range of
+    /// `bytes` that each replacement stands for.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let (s, repaired) = Str::repair_utf8(b"ab\xffc");
+    /// assert_eq!(s.as_ref(), "ab\u{FFFD}c");
+    /// assert_eq!(repaired, [2..3]);
+    /// ```
+    pub fn repair_utf8(bytes: &[u8]) -> (Str, Vec<Range<usize>>) {
+        let mut out = String::with_capacity(bytes.len());
+        let mut repaired = Vec::new();
+        let mut at = 0;
+        while at < bytes.len() {
+            match std::str::from_utf8(&bytes[at..]) {
+                Ok(valid) => {
+                    out.push_str(valid);
+                    break;
+                }
+                Err(err) => {
+                    let valid = err.valid_up_to();
+                    // SAFETY: `from_utf8` validated the bytes up to `valid`.
+                    out.push_str(unsafe {
+                        std::str::from_utf8_unchecked(&bytes[at..at + valid])
+                    });
+                    let invalid =
+                        err.error_len().unwrap_or(bytes.len() - at - valid);
+                    let start = at + valid;
+                    out.push('\u{FFFD}');
+                    repaired.push(start..start + invalid);
+                    at = start + invalid;
+                }
+            }
+        }
+        (Str::new(&out), repaired)
+    }
+
+    /// Decrypts the string as a Caesar cipher with every possible shift.
+    ///
+    /// Returns all 26 `(shift, plaintext)` pairs, where the plaintext is the
+    /// string with each ASCII letter rotated back by `shift` places. The
+    /// pairs are sorted by how closely their letter frequencies match
+    /// English, so the most likely plaintext comes first. Non-letters are
+    /// left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("Wkh txlfn eurzq ira mxpsv ryhu wkh odcb grj");
+    /// let (shift, plain) = &s.caesar_brute_force()[0];
+    /// assert_eq!(*shift, 3);
+    /// assert_eq!(plain.as_ref(), "The quick brown fox jumps over the lazy dog");
+    /// ```
+    pub fn caesar_brute_force(&self) -> Vec<(u8, Str)> {
+        let mut scored: Vec<(f64, u8, String)> = (0..26)
+            .map(|shift| {
+                let plain = caesar_shift_back(self.as_ref(), shift);
+                (english_chi_squared(&plain), shift, plain)
+            })
+            .collect();
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+        scored
+            .into_iter()
+            .map(|(_, shift, plain)| (shift, Str::new(&plain)))
+            .collect()
+    }
+
+    /// Encodes a string of decimal digits as packed BCD, two digits per
+    /// byte with the first digit in the high nibble.
+    ///
+    /// An odd number of digits is padded with a leading zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("1234").encode_bcd().unwrap(), [0x12, 0x34]);
+    /// assert_eq!(Str::new("123").encode_bcd().unwrap(), [0x01, 0x23]);
+    /// ```
+    pub fn encode_bcd(&self) -> Result<Vec<u8>, BcdError> {
+        let bytes = self.as_ref().as_bytes();
+        if let Some(at) = bytes.iter().position(|b| !b.is_ascii_digit()) {
+            return Err(BcdError::InvalidDigit(at));
+        }
+
+        let pad = bytes.len() % 2;
+        let mut out = vec![0u8; bytes.len().div_ceil(2)];
+        for (i, &b) in bytes.iter().enumerate() {
+            let at = i + pad;
+            let shift = if at % 2 == 0 { 4 } else { 0 };
+            out[at / 2] |= (b - b'0') << shift;
+        }
+        Ok(out)
+    }
+
+    /// Decodes packed BCD into a string of decimal digits, two per byte.
+    ///
+    /// Leading zeros are kept, so the padding added by [`Str::encode_bcd`]
+    /// shows up in the output. Nibbles above 9 are treated as filler and
+    /// skipped, following the convention of telephony encodings that pad
+    /// with `0xF`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::decode_bcd(&[0x12, 0x34]).as_ref(), "1234");
+    /// assert_eq!(Str::decode_bcd(&[0x21, 0xF3]).as_ref(), "213");
+    /// ```
+    pub fn decode_bcd(bcd: &[u8]) -> Str {
+        let out: String = bcd
+            .iter()
+            .flat_map(|&b| [b >> 4, b & 0xf])
+            .filter(|&digit| digit <= 9)
+            .map(|digit| (b'0' + digit) as char)
+            .collect();
+        Str::new(&out)
+    }
+
+    /// Returns `true` if the string starts with a UTF-8 byte order mark, the
+    /// bytes `EF BB BF` encoding U+FEFF.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert!(Str::new("\u{FEFF}id,name").has_utf8_bom());
+    /// assert!(!Str::new("id,name").has_utf8_bom());
+    /// ```
+    #[inline]
+    pub fn has_utf8_bom(&self) -> bool {
+        self.as_ref().starts_with(BOM)
+    }
+
+    /// Returns the string without its leading UTF-8 byte order mark, or the
+    /// whole string if it does not start with one.
+    ///
+    /// Only one byte order mark is removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("\u{FEFF}id,name").strip_utf8_bom(), "id,name");
+    /// assert_eq!(Str::new("id,name").strip_utf8_bom(), "id,name");
+    /// ```
+    #[inline]
+    pub fn strip_utf8_bom(&self) -> &str {
+        let s: &str = self.as_ref();
+        s.strip_prefix(BOM).unwrap_or(s)
+    }
+
+    /// Returns the string starting with a UTF-8 byte order mark, prepending
+    /// one unless it already has it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("id,name").with_utf8_bom();
+    /// assert!(s.as_ref().as_bytes().starts_with(&[0xEF, 0xBB, 0xBF]));
+    /// assert_eq!(s.with_utf8_bom(), s);
+    /// ```
+    pub fn with_utf8_bom(&self) -> Str {
+        if self.has_utf8_bom() {
+            return self.clone();
+        }
+        let mut out = String::with_capacity(self.len() + BOM.len_utf8());
+        out.push(BOM);
+        out.push_str(self.as_ref());
+        Str::new(&out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_uri_component_spaces_and_reserved() {
+        let s = Str::new("key=a b&c/d?e#f");
+        assert_eq!(
+            s.to_uri_component().as_ref(),
+            "key%3Da%20b%26c%2Fd%3Fe%23f"
+        );
+    }
+
+    #[test]
+    fn test_to_uri_component_unreserved_untouched() {
+        let s = Str::new("AZaz09-_.!~*'()");
+        assert_eq!(s.to_uri_component(), s);
+    }
+
+    #[test]
+    fn test_to_uri_component_unicode() {
+        let s = Str::new("世界 é");
+        assert_eq!(
+            s.to_uri_component().as_ref(),
+            "%E4%B8%96%E7%95%8C%20%C3%A9"
+        );
+    }
+
+    #[test]
+    fn test_uri_component_round_trip() {
+        for input in ["", "hello world", "a+b=c&d", "Hello, 世界!", "100%"] {
+            let s = Str::new(input);
+            assert_eq!(s.to_uri_component().from_uri_component(), Ok(s));
+        }
+    }
+
+    #[test]
+    fn test_from_uri_component_lowercase_hex() {
+        let s = Str::new("%c3%a9");
+        assert_eq!(s.from_uri_component().unwrap().as_ref(), "é");
+    }
+
+    #[test]
+    fn test_from_uri_component_invalid_escape() {
+        assert_eq!(
+            Str::new("%").from_uri_component(),
+            Err(UriDecodeError::InvalidEscape(0))
+        );
+        assert_eq!(
+            Str::new("ab%2").from_uri_component(),
+            Err(UriDecodeError::InvalidEscape(2))
+        );
+        assert_eq!(
+            Str::new("%zz").from_uri_component(),
+            Err(UriDecodeError::InvalidEscape(0))
+        );
+    }
+
+    const RFC4648_VECTORS: [(&str, &str); 7] = [
+        ("", ""),
+        ("f", "MY======"),
+        ("fo", "MZXQ===="),
+        ("foo", "MZXW6==="),
+        ("foob", "MZXW6YQ="),
+        ("fooba", "MZXW6YTB"),
+        ("foobar", "MZXW6YTBOI======"),
+    ];
+
+    fn is_ascii_upper(s: &Str) -> bool {
+        s.as_ref()
+            .bytes()
+            .all(|b| b.is_ascii() && !b.is_ascii_lowercase())
+    }
+
+    #[test]
+    fn test_base32_rfc4648_vectors() {
+        for (plain, encoded) in RFC4648_VECTORS {
+            let s = Str::new(plain);
+            assert_eq!(s.encode_base32_rfc4648().as_ref(), encoded);
+            assert_eq!(Str::new(encoded).decode_base32_rfc4648(), Ok(s));
+        }
+    }
+
+    #[test]
+    fn test_base32_round_trip() {
+        for input in ["", "\0", "\x7f", "Hello, 世界! 🎉", "padding?"] {
+            let s = Str::new(input);
+            let rfc = s.encode_base32_rfc4648();
+            assert!(is_ascii_upper(&rfc));
+            assert_eq!(rfc.len() % 8, 0);
+            assert_eq!(rfc.decode_base32_rfc4648().as_ref(), Ok(&s));
+
+            let crockford = s.encode_base32_crockford();
+            assert!(is_ascii_upper(&crockford));
+            assert_eq!(crockford.decode_base32_crockford().as_ref(), Ok(&s));
+        }
+    }
+
+    #[test]
+    fn test_base32_crockford_aliases() {
+        assert_eq!(Str::new("f").encode_base32_crockford().as_ref(), "CR");
+        assert_eq!(
+            Str::new("cr").decode_base32_crockford().unwrap().as_ref(),
+            "f"
+        );
+        assert_eq!(
+            Str::new("C5H6-IO").decode_base32_crockford(),
+            Str::new("C5H610").decode_base32_crockford()
+        );
+        assert_eq!(
+            Str::new("C5H6LO").decode_base32_crockford(),
+            Str::new("C5H610").decode_base32_crockford()
+        );
+    }
+
+    #[test]
+    fn test_base32_decode_errors() {
+        let rfc = |s: &str| Str::new(s).decode_base32_rfc4648();
+        assert_eq!(rfc("MZXW6"), Err(Base32Error::InvalidLength));
+        assert_eq!(rfc("MZXW6Y=="), Err(Base32Error::InvalidLength));
+        assert_eq!(rfc("M======="), Err(Base32Error::InvalidLength));
+        assert_eq!(rfc("MZ1W6==="), Err(Base32Error::InvalidChar(2)));
+        assert_eq!(rfc("MZ=W6==="), Err(Base32Error::InvalidChar(2)));
+        assert_eq!(rfc("74======"), Err(Base32Error::InvalidUtf8));
+
+        let crockford = |s: &str| Str::new(s).decode_base32_crockford();
+        assert_eq!(crockford("C"), Err(Base32Error::InvalidLength));
+        assert_eq!(crockford("CU"), Err(Base32Error::InvalidChar(1)));
+        assert_eq!(crockford("CR="), Err(Base32Error::InvalidChar(2)));
+    }
+
+    #[test]
+    fn test_arbitrary_bytes_vectors() {
+        let encode =
+            |data: &[u8]| Str::encode_arbitrary_bytes(data).to_string();
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"f"), "Zg");
+        assert_eq!(encode(b"fo"), "Zm8");
+        assert_eq!(encode(b"foo"), "Zm9v");
+        assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(encode(&[0; 4]), "AAAAAA");
+        assert_eq!(encode(&[0xff; 4]), "_____w");
+    }
+
+    #[test]
+    fn test_arbitrary_bytes_round_trip() {
+        let mut state = 0x2545_f491_u32;
+        let random: Vec<u8> = (0..257)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+        let all: Vec<u8> = (0..=255).collect();
+        let inputs = [&[][..], &[0; 10], &[0xff; 11], &all, &random];
+        for data in inputs {
+            let encoded = Str::encode_arbitrary_bytes(data);
+            assert!(encoded
+                .as_ref()
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+            assert_eq!(encoded.decode_arbitrary_bytes().unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_decode_arbitrary_bytes_errors() {
+        let decode = |s: &str| Str::new(s).decode_arbitrary_bytes();
+        assert_eq!(decode("Z"), Err(DecodeError::InvalidLength));
+        assert_eq!(decode("Zm9vY"), Err(DecodeError::InvalidLength));
+        assert_eq!(decode("Zm9v+g"), Err(DecodeError::InvalidChar(4)));
+        assert_eq!(decode("Zg=="), Err(DecodeError::InvalidChar(2)));
+    }
+
+    #[test]
+    fn test_xor_round_trip() {
+        for input in ["", "a", "Hello, 世界! 🎉", "a longer text than the key"]
+        {
+            let s = Str::new(input);
+            for key in [&b"k"[..], b"key", b"\x00\xff\x80", b""] {
+                let data = s.xor_encrypt(key);
+                assert_eq!(data.len(), s.len());
+                assert_eq!(Str::xor_decrypt(&data, key), Ok(s.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_xor_zero_key_is_identity() {
+        let s = Str::new("identity");
+        assert_eq!(s.xor_encrypt(&[0, 0, 0]), b"identity");
+        assert_eq!(s.xor_encrypt(&[]), b"identity");
+        assert_ne!(s.xor_encrypt(&[0, 1]), s.xor_encrypt(&[0, 0]));
+    }
+
+    #[test]
+    fn test_xor_key_cycles() {
+        let s = Str::new("abcd");
+        assert_eq!(
+            s.xor_encrypt(&[1, 2]),
+            [b'a' ^ 1, b'b' ^ 2, b'c' ^ 1, b'd' ^ 2]
+        );
+    }
+
+    #[test]
+    fn test_xor_decrypt_invalid_utf8() {
+        assert!(Str::xor_decrypt(&[0xff], b"").is_err());
+        assert!(Str::xor_decrypt(&[0xc3 ^ 0x2a], &[0x2a]).is_err());
+    }
+
+    #[test]
+    fn test_from_uri_component_invalid_utf8() {
+        assert_eq!(
+            Str::new("%FF").from_uri_component(),
+            Err(UriDecodeError::InvalidUtf8)
+        );
+    }
+
+    #[test]
+    fn test_encode_bcd() {
+        assert_eq!(Str::new("1234").encode_bcd(), Ok(vec![0x12, 0x34]));
+        assert_eq!(Str::new("0").encode_bcd(), Ok(vec![0x00]));
+        assert_eq!(Str::new("7").encode_bcd(), Ok(vec![0x07]));
+        assert_eq!(Str::new("12345").encode_bcd(), Ok(vec![0x01, 0x23, 0x45]));
+        assert_eq!(Str::new("").encode_bcd(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_encode_bcd_invalid() {
+        assert_eq!(
+            Str::new("1a2").encode_bcd(),
+            Err(BcdError::InvalidDigit(1))
+        );
+        assert_eq!(Str::new("-1").encode_bcd(), Err(BcdError::InvalidDigit(0)));
+        assert_eq!(Str::new("1٣").encode_bcd(), Err(BcdError::InvalidDigit(1)));
+    }
+
+    #[test]
+    fn test_decode_bcd_roundtrip() {
+        for digits in ["", "00", "1234", "9876543210"] {
+            let bcd = Str::new(digits).encode_bcd().unwrap();
+            assert_eq!(Str::decode_bcd(&bcd).as_ref(), digits);
+        }
+        assert_eq!(Str::decode_bcd(&[0x00]).as_ref(), "00");
+        assert_eq!(Str::decode_bcd(&[0xFF, 0x5F]).as_ref(), "5");
+    }
+
+    #[test]
+    fn test_caesar_brute_force_finds_shift() {
+        let plain = "Attack at dawn, retreat at dusk!";
+        for shift in [1, 7, 13, 25] {
+            let cipher = caesar_shift_back(plain, 26 - shift);
+            let results = Str::new(&cipher).caesar_brute_force();
+            assert_eq!(results[0].0, shift);
+            assert_eq!(results[0].1.as_ref(), plain);
+        }
+    }
+
+    #[test]
+    fn test_caesar_brute_force_all_shifts() {
+        let results = Str::new("Guvf vf n frperg zrffntr").caesar_brute_force();
+        assert_eq!(results.len(), 26);
+        let mut shifts: Vec<u8> =
+            results.iter().map(|(shift, _)| *shift).collect();
+        shifts.sort();
+        assert_eq!(shifts, (0..26).collect::<Vec<u8>>());
+
+        let identity = results.iter().find(|(shift, _)| *shift == 0).unwrap();
+        assert_eq!(identity.1.as_ref(), "Guvf vf n frperg zrffntr");
+        assert_eq!(results[0], (13, Str::new("This is a secret message")));
+    }
+
+    #[test]
+    fn test_caesar_brute_force_no_letters() {
+        let results = Str::new("123 ?!").caesar_brute_force();
+        assert_eq!(results.len(), 26);
+        assert!(results.iter().all(|(_, plain)| plain.as_ref() == "123 ?!"));
+    }
+
+    #[test]
+    fn test_repair_utf8_single() {
+        let (s, repaired) = Str::repair_utf8(b"caf\xc3(");
+        assert_eq!(s.as_ref(), "caf\u{FFFD}(");
+        assert_eq!(repaired.len(), 1);
+        assert_eq!(repaired[0], 3..4);
+    }
+
+    #[test]
+    fn test_repair_utf8_consecutive() {
+        // A truncated 3-byte sequence is one maximal subpart, while each
+        // byte that can never start a sequence is replaced on its own.
+        let (s, repaired) = Str::repair_utf8(b"a\xe4\xb8\xff\xfeb");
+        assert_eq!(s.as_ref(), "a\u{FFFD}\u{FFFD}\u{FFFD}b");
+        assert_eq!(repaired, [1..3, 3..4, 4..5]);
+
+        let (s, repaired) = Str::repair_utf8(b"\xed\xa0\x80");
+        assert_eq!(s.as_ref(), "\u{FFFD}\u{FFFD}\u{FFFD}");
+        assert_eq!(repaired, [0..1, 1..2, 2..3]);
+
+        let (s, repaired) = Str::repair_utf8(b"ok\xf0\x9f\x8e");
+        assert_eq!(s.as_ref(), "ok\u{FFFD}");
+        assert_eq!(repaired.len(), 1);
+        assert_eq!(repaired[0], 2..5);
+    }
+
+    #[test]
+    fn test_repair_utf8_valid() {
+        for input in ["", "ascii", "Hello, 世界! 🎉"] {
+            let (s, repaired) = Str::repair_utf8(input.as_bytes());
+            assert_eq!(s.as_ref(), input);
+            assert!(repaired.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_repair_utf8_all_invalid() {
+        let bytes = [0x80, 0xbf, 0xff, 0xc0];
+        let (s, repaired) = Str::repair_utf8(&bytes);
+        assert_eq!(s.as_ref(), "\u{FFFD}".repeat(4));
+        assert_eq!(repaired, [0..1, 1..2, 2..3, 3..4]);
+        assert_eq!(s.as_ref(), String::from_utf8_lossy(&bytes));
+    }
+
+    #[test]
+    fn test_utf8_bom() {
+        let with = Str::new("\u{FEFF}keyword");
+        assert!(with.has_utf8_bom());
+        assert_eq!(with.strip_utf8_bom(), "keyword");
+        assert_eq!(with.with_utf8_bom(), with);
+
+        let without = Str::new("keyword");
+        assert!(!without.has_utf8_bom());
+        assert_eq!(without.strip_utf8_bom(), "keyword");
+        assert_eq!(without.with_utf8_bom(), with);
+        assert_eq!(
+            without.with_utf8_bom().as_ref().as_bytes(),
+            b"\xEF\xBB\xBFkeyword"
+        );
+    }
+
+    #[test]
+    fn test_utf8_bom_edge_cases() {
+        let empty = Str::new("");
+        assert!(!empty.has_utf8_bom());
+        assert_eq!(empty.strip_utf8_bom(), "");
+        assert_eq!(empty.with_utf8_bom().as_ref(), "\u{FEFF}");
+
+        let only = Str::new("\u{FEFF}");
+        assert!(only.has_utf8_bom());
+        assert_eq!(only.strip_utf8_bom(), "");
+
+        let twice = Str::new("\u{FEFF}\u{FEFF}x");
+        assert_eq!(twice.strip_utf8_bom(), "\u{FEFF}x");
+
+        let inner = Str::new("x\u{FEFF}");
+        assert!(!inner.has_utf8_bom());
+        assert_eq!(inner.strip_utf8_bom(), "x\u{FEFF}");
+
+        let utf16_bom = Str::new("\u{FFFE}x");
+        assert!(!utf16_bom.has_utf8_bom());
+    }
+}
@@ -0,0 +1,1034 @@
+use crate::Str;
+
+/// Joins the digits of `n` into groups of `first` digits at the right and
+/// `rest` digits to the left of it.
+fn group_digits(n: i64, sep: char, first: usize, rest: usize) -> Str {
+    let digits = Str::new(&n.unsigned_abs().to_string());
+    let mut out = String::with_capacity(digits.len() * 2);
+    if n < 0 {
+        out.push('-');
+    }
+
+    let at = digits.len().saturating_sub(first);
+    let (head, tail) = digits.as_ref().split_at(at);
+    if !head.is_empty() {
+        let head = Str::new(head);
+        for (i, group) in head.rchunks_by_char_count(rest).rev().enumerate() {
+            if i > 0 {
+                out.push(sep);
+            }
+            out.push_str(group);
+        }
+        out.push(sep);
+    }
+    out.push_str(tail);
+    Str::new(&out)
+}
+
+const NANOS_PER_MICRO: u64 = 1_000;
+const NANOS_PER_MILLI: u64 = 1_000_000;
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+const NANOS_PER_MIN: u64 = 60 * NANOS_PER_SEC;
+const NANOS_PER_HOUR: u64 = 60 * NANOS_PER_MIN;
+
+/// Formats `bytes` with the largest unit that keeps the value at or above
+/// one, rounded to two decimal places.
+fn format_bytes(bytes: u64, base: u64, units: [&str; 6]) -> Str {
+    if bytes < base {
+        return Str::new(&format!("{} B", bytes));
+    }
+
+    let mut scale = base;
+    let mut unit = 0;
+    while unit + 1 < units.len() && bytes / scale >= base {
+        scale *= base;
+        unit += 1;
+    }
+
+    let mut value = bytes as f64 / scale as f64;
+    if (value * 100.0).round() / 100.0 >= base as f64 && unit + 1 < units.len()
+    {
+        value /= base as f64;
+        unit += 1;
+    }
+    Str::new(&format!("{:.2} {}", value, units[unit]))
+}
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty",
+    "ninety",
+];
+
+const SCALES: [&str; 7] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+/// Appends the words for `n`, which must be below 1000 and non-zero.
+fn push_hundreds(out: &mut Vec<String>, n: u64) {
+    let (hundreds, rest) = (n / 100, n % 100);
+    if hundreds > 0 {
+        out.push(ONES[hundreds as usize].into());
+        out.push("hundred".into());
+    }
+    match rest {
+        0 => {}
+        1..=19 => out.push(ONES[rest as usize].into()),
+        _ if rest % 10 == 0 => out.push(TENS[(rest / 10) as usize].into()),
+        _ => out.push(format!(
+            "{}-{}",
+            TENS[(rest / 10) as usize],
+            ONES[(rest % 10) as usize]
+        )),
+    }
+}
+
+/// Error returned by [`Str::format_in_base`] and [`Str::parse_in_base`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BaseError {
+    /// The base is not between 2 and 36.
+    InvalidBase(u32),
+    /// The string to parse is empty.
+    Empty,
+    /// The character at the given byte offset is not a digit of the base.
+    InvalidDigit(usize),
+    /// The value does not fit in a `u64`.
+    Overflow,
+}
+
+impl std::fmt::Display for BaseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidBase(base) => {
+                write!(f, "base {} is not between 2 and 36", base)
+            }
+            Self::Empty => {
+                write!(f, "cannot parse a number from an empty string")
+            }
+            Self::InvalidDigit(at) => write!(f, "invalid digit at byte {}", at),
+            Self::Overflow => write!(f, "number does not fit in 64 bits"),
+        }
+    }
+}
+
+impl std::error::Error for BaseError {}
+
+/// Error returned by [`Str::parse_hex_color`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HexColorError {
+    /// The string does not start with `#`.
+    MissingHash,
+    /// The given number of digits is not 3, 6 or 8.
+    InvalidLength(usize),
+    /// The character at the given byte offset is not a hex digit.
+    InvalidDigit(usize),
+}
+
+impl std::fmt::Display for HexColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingHash => write!(f, "hex color does not start with `#`"),
+            Self::InvalidLength(len) => {
+                write!(f, "hex color has {} digits instead of 3, 6 or 8", len)
+            }
+            Self::InvalidDigit(at) => {
+                write!(f, "invalid hex digit at byte {}", at)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HexColorError {}
+
+impl Str {
+    /// Formats `n` with a comma between every group of three digits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::format_with_commas(-1234567).as_ref(), "-1,234,567");
+    /// ```
+    #[inline]
+    pub fn format_with_commas(n: i64) -> Str {
+        Self::format_with_separator(n, ',', 3)
+    }
+
+    /// Formats `n` with `sep` between every group of `group_size` digits,
+    /// counting from the right.
+    ///
+    /// # Panics
+    /// Panics if `group_size` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::format_with_separator(1234567, '_', 4).as_ref(), "123_4567");
+    /// ```
+    pub fn format_with_separator(n: i64, sep: char, group_size: usize) -> Str {
+        assert!(group_size != 0, "group size must be non-zero");
+        group_digits(n, sep, group_size, group_size)
+    }
+
+    /// Formats `n` using the Indian numbering system: the last three digits
+    /// form one group and the remaining digits are grouped in pairs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::format_indian(123456789).as_ref(), "12,34,56,789");
+    /// ```
+    #[inline]
+    pub fn format_indian(n: i64) -> Str {
+        group_digits(n, ',', 3, 2)
+    }
+
+    /// Inserts `sep` between groups of `n` characters, counted from the end
+    /// of the string, so the first group may be shorter.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("1234567");
+    /// assert_eq!(s.insert_separator_every_n_chars(",", 3).as_ref(), "1,234,567");
+    /// ```
+    pub fn insert_separator_every_n_chars(&self, sep: &str, n: usize) -> Str {
+        let groups: Vec<&str> = self.rchunks_by_char_count(n).rev().collect();
+        Str::new(&groups.join(sep))
+    }
+
+    /// Inserts `sep` between groups of `n` characters, counted from the
+    /// start of the string, so the last group may be shorter.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("DE89370400440532013000");
+    /// assert_eq!(
+    ///     s.insert_separator_every_n_from_left(" ", 4).as_ref(),
+    ///     "DE89 3704 0044 0532 0130 00"
+    /// );
+    /// ```
+    pub fn insert_separator_every_n_from_left(
+        &self,
+        sep: &str,
+        n: usize,
+    ) -> Str {
+        assert!(n != 0, "group size must be non-zero");
+        let mut out = String::with_capacity(self.len() * 2);
+        for (i, c) in self.as_ref().chars().enumerate() {
+            if i > 0 && i % n == 0 {
+                out.push_str(sep);
+            }
+            out.push(c);
+        }
+        Str::new(&out)
+    }
+
+    /// Formats a byte count using SI prefixes, where `1 KB` is 1000 bytes.
+    ///
+    /// Counts below 1000 are printed as whole bytes, larger counts are
+    /// rounded to two decimal places.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::format_bytes_si(999).as_ref(), "999 B");
+    /// assert_eq!(Str::format_bytes_si(1_234_567).as_ref(), "1.23 MB");
+    /// ```
+    #[inline]
+    pub fn format_bytes_si(bytes: u64) -> Str {
+        format_bytes(bytes, 1000, ["KB", "MB", "GB", "TB", "PB", "EB"])
+    }
+
+    /// Formats a byte count using IEC prefixes, where `1 KiB` is 1024 bytes.
+    ///
+    /// Counts below 1024 are printed as whole bytes, larger counts are
+    /// rounded to two decimal places.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::format_bytes_iec(1023).as_ref(), "1023 B");
+    /// assert_eq!(Str::format_bytes_iec(1536).as_ref(), "1.50 KiB");
+    /// ```
+    #[inline]
+    pub fn format_bytes_iec(bytes: u64) -> Str {
+        format_bytes(bytes, 1024, ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"])
+    }
+
+    /// Spells out `n` in English words.
+    ///
+    /// Tens and units are joined with a hyphen and no `and` is inserted, so
+    /// 123 is `"one hundred twenty-three"`. Negative numbers are prefixed
+    /// with `minus`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::number_to_words(-1001).as_ref(), "minus one thousand one");
+    /// ```
+    pub fn number_to_words(n: i64) -> Str {
+        if n == 0 {
+            return Str::new(ONES[0]);
+        }
+
+        let mut groups = Vec::new();
+        let mut rest = n.unsigned_abs();
+        while rest > 0 {
+            groups.push(rest % 1000);
+            rest /= 1000;
+        }
+
+        let mut words = Vec::new();
+        if n < 0 {
+            words.push("minus".to_string());
+        }
+        for (scale, &group) in groups.iter().enumerate().rev() {
+            if group == 0 {
+                continue;
+            }
+            push_hundreds(&mut words, group);
+            if scale > 0 {
+                words.push(SCALES[scale].into());
+            }
+        }
+        Str::new(&words.join(" "))
+    }
+
+    /// Spells out `n` as an English ordinal, such as `"first"` or
+    /// `"twenty-third"`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::ordinal_words(23).as_ref(), "twenty-third");
+    /// assert_eq!(Str::ordinal_words(100).as_ref(), "one hundredth");
+    /// ```
+    pub fn ordinal_words(n: i64) -> Str {
+        let words = Self::number_to_words(n);
+        let words: &str = words.as_ref();
+        let split = words.rfind([' ', '-']).map_or(0, |at| at + 1);
+        let (head, last) = words.split_at(split);
+        let last = match last {
+            "one" => "first".to_string(),
+            "two" => "second".to_string(),
+            "three" => "third".to_string(),
+            "five" => "fifth".to_string(),
+            "eight" => "eighth".to_string(),
+            "nine" => "ninth".to_string(),
+            "twelve" => "twelfth".to_string(),
+            _ => match last.strip_suffix('y') {
+                Some(stem) => format!("{}ieth", stem),
+                None => format!("{}th", last),
+            },
+        };
+        Str::new(&format!("{}{}", head, last))
+    }
+
+    /// Formats the time elapsed since `start`, see
+    /// [`Str::format_elapsed_duration`].
+    #[inline]
+    pub fn format_elapsed(start: std::time::Instant) -> Str {
+        Self::format_elapsed_duration(start.elapsed())
+    }
+
+    /// Formats the time elapsed between `start` and `end`, see
+    /// [`Str::format_elapsed_duration`]. Returns `"0ns"` if `end` is before
+    /// `start`.
+    #[inline]
+    pub fn format_elapsed_until(
+        start: std::time::Instant,
+        end: std::time::Instant,
+    ) -> Str {
+        Self::format_elapsed_duration(end.saturating_duration_since(start))
+    }
+
+    /// Formats a measured duration for timing displays.
+    ///
+    /// Durations below a microsecond are printed in whole nanoseconds,
+    /// below a millisecond in truncated whole microseconds, and longer
+    /// durations in milliseconds or seconds rounded to two decimal places.
+    /// The unit is picked after rounding, so a duration just below a second
+    /// prints as `1.00s` rather than `1000.00ms`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// # use std::time::Duration;
+    /// let fmt = |d| Str::format_elapsed_duration(d).to_string();
+    /// assert_eq!(fmt(Duration::from_nanos(42_300)), "42µs");
+    /// assert_eq!(fmt(Duration::from_micros(1_234)), "1.23ms");
+    /// assert_eq!(fmt(Duration::from_millis(5_670)), "5.67s");
+    /// ```
+    pub fn format_elapsed_duration(elapsed: std::time::Duration) -> Str {
+        let nanos = elapsed.as_nanos();
+        let out = if nanos < NANOS_PER_MICRO as u128 {
+            format!("{}ns", nanos)
+        } else if nanos < NANOS_PER_MILLI as u128 {
+            format!("{}µs", nanos / NANOS_PER_MICRO as u128)
+        } else {
+            // Hundredths of a millisecond, rounded half up, so that the unit
+            // is picked after rounding.
+            let centi_millis = (nanos + 5_000) / 10_000;
+            if centi_millis < 100_000 {
+                format!("{}.{:02}ms", centi_millis / 100, centi_millis % 100)
+            } else {
+                let centi_secs = (nanos + 5_000_000) / 10_000_000;
+                format!("{}.{:02}s", centi_secs / 100, centi_secs % 100)
+            }
+        };
+        Str::new(&out)
+    }
+
+    /// Formats the set bits of `value` as a `|` separated list of labels.
+    ///
+    /// `labels[i]` names bit `i`. Set bits without a label are collected into
+    /// a single trailing `0x{hex}` entry. A value with no bits set formats as
+    /// `"0"`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let labels = ["READ", "WRITE", "EXEC"];
+    /// assert_eq!(Str::format_flags(0b101, &labels).as_ref(), "READ|EXEC");
+    /// assert_eq!(Str::format_flags(0x11, &labels).as_ref(), "READ|0x10");
+    /// assert_eq!(Str::format_flags(0, &labels).as_ref(), "0");
+    /// ```
+    pub fn format_flags(value: u64, labels: &[&str]) -> Str {
+        if value == 0 {
+            return Str::new("0");
+        }
+
+        let mut out = String::new();
+        let mut unknown = value;
+        for (bit, label) in labels.iter().enumerate().take(64) {
+            let mask = 1 << bit;
+            if value & mask == 0 {
+                continue;
+            }
+            if !out.is_empty() {
+                out.push('|');
+            }
+            out.push_str(label);
+            unknown &= !mask;
+        }
+
+        if unknown != 0 {
+            if !out.is_empty() {
+                out.push('|');
+            }
+            out.push_str(&format!("{:#x}", unknown));
+        }
+        Str::new(&out)
+    }
+
+    /// Formats `n` in `base`, using the digits `0-9` and then the upper case
+    /// letters `A-Z`.
+    ///
+    /// # Errors
+    /// Returns [`BaseError::InvalidBase`] unless `base` is between 2 and 36.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::format_in_base(255, 16).unwrap().as_ref(), "FF");
+    /// assert_eq!(Str::format_in_base(255, 36).unwrap().as_ref(), "73");
+    /// ```
+    pub fn format_in_base(n: u64, base: u32) -> Result<Str, BaseError> {
+        if !(2..=36).contains(&base) {
+            return Err(BaseError::InvalidBase(base));
+        }
+
+        let mut digits = Vec::with_capacity(64);
+        let mut rest = n;
+        loop {
+            let digit = char::from_digit((rest % base as u64) as u32, base);
+            digits.push(digit.unwrap().to_ascii_uppercase());
+            rest /= base as u64;
+            if rest == 0 {
+                break;
+            }
+        }
+        let out: String = digits.iter().rev().collect();
+        Ok(Str::new(&out))
+    }
+
+    /// Parses the string as a number in `base`, the reverse of
+    /// [`Str::format_in_base`].
+    ///
+    /// Letter digits may be in either case. Signs, prefixes such as `0x` and
+    /// surrounding whitespace are not accepted.
+    ///
+    /// # Errors
+    /// Returns a [`BaseError`] if the base is not between 2 and 36, the
+    /// string is empty or holds a character that is not a digit of the
+    /// base, or the value does not fit in a `u64`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{BaseError, Str};
+    /// assert_eq!(Str::new("ff").parse_in_base(16), Ok(255));
+    /// assert_eq!(Str::new("12").parse_in_base(2), Err(BaseError::InvalidDigit(1)));
+    /// ```
+    pub fn parse_in_base(&self, base: u32) -> Result<u64, BaseError> {
+        if !(2..=36).contains(&base) {
+            return Err(BaseError::InvalidBase(base));
+        }
+        let s: &str = self.as_ref();
+        if s.is_empty() {
+            return Err(BaseError::Empty);
+        }
+
+        s.char_indices().try_fold(0u64, |n, (at, c)| {
+            let digit = c.to_digit(base).ok_or(BaseError::InvalidDigit(at))?;
+            n.checked_mul(base as u64)
+                .and_then(|n| n.checked_add(digit as u64))
+                .ok_or(BaseError::Overflow)
+        })
+    }
+
+    /// Formats a duration given in nanoseconds for humans.
+    ///
+    /// The unit is picked from the magnitude: `ns` below a microsecond, `µs`
+    /// below a millisecond, `ms` below a second, `s` below a minute, minutes
+    /// and seconds below an hour, and hours and minutes above that. Smaller
+    /// parts are truncated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::format_duration(142_500_000).as_ref(), "142ms");
+    /// assert_eq!(Str::format_duration(9_252_000_000_000).as_ref(), "2h 34m");
+    /// ```
+    pub fn format_duration(nanos: u64) -> Str {
+        let out = if nanos < NANOS_PER_MICRO {
+            format!("{}ns", nanos)
+        } else if nanos < NANOS_PER_MILLI {
+            format!("{}µs", nanos / NANOS_PER_MICRO)
+        } else if nanos < NANOS_PER_SEC {
+            format!("{}ms", nanos / NANOS_PER_MILLI)
+        } else if nanos < NANOS_PER_MIN {
+            format!("{}s", nanos / NANOS_PER_SEC)
+        } else if nanos < NANOS_PER_HOUR {
+            let secs = nanos % NANOS_PER_MIN / NANOS_PER_SEC;
+            format!("{}m {}s", nanos / NANOS_PER_MIN, secs)
+        } else {
+            let mins = nanos % NANOS_PER_HOUR / NANOS_PER_MIN;
+            format!("{}h {}m", nanos / NANOS_PER_HOUR, mins)
+        };
+        Str::new(&out)
+    }
+
+    /// Formats a duration given in nanoseconds as a single number with a
+    /// unit suffix.
+    ///
+    /// The value keeps at most one truncated decimal digit, which is omitted
+    /// when it is zero. Units are `ns`, `µs`, `ms`, `s`, `m` and `h`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::format_duration_compact(142_500_000).as_ref(), "142.5ms");
+    /// assert_eq!(Str::format_duration_compact(90 * 1_000_000_000).as_ref(), "1.5m");
+    /// ```
+    pub fn format_duration_compact(nanos: u64) -> Str {
+        let (unit, suffix) = if nanos < NANOS_PER_MICRO {
+            (1, "ns")
+        } else if nanos < NANOS_PER_MILLI {
+            (NANOS_PER_MICRO, "µs")
+        } else if nanos < NANOS_PER_SEC {
+            (NANOS_PER_MILLI, "ms")
+        } else if nanos < NANOS_PER_MIN {
+            (NANOS_PER_SEC, "s")
+        } else if nanos < NANOS_PER_HOUR {
+            (NANOS_PER_MIN, "m")
+        } else {
+            (NANOS_PER_HOUR, "h")
+        };
+
+        let whole = nanos / unit;
+        let tenths = nanos % unit / (unit / 10).max(1);
+        let out = match (unit, tenths) {
+            (1, _) | (_, 0) => format!("{}{}", whole, suffix),
+            _ => format!("{}.{}{}", whole, tenths, suffix),
+        };
+        Str::new(&out)
+    }
+
+    /// Parses a CSS hex color in the `#RGB`, `#RRGGBB` or `#RRGGBBAA` form
+    /// into its red, green and blue components.
+    ///
+    /// Digits may be in either case. Each digit of the short form is
+    /// repeated, so `#fa0` is `#ffaa00`. The alpha component of the long
+    /// form is checked but not returned.
+    ///
+    /// # Errors
+    /// Returns a [`HexColorError`] if the string does not start with `#`,
+    /// does not have 3, 6 or 8 digits after it, or holds a character that
+    /// is not a hex digit.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{HexColorError, Str};
+    /// assert_eq!(Str::new("#1a2b3c").parse_hex_color(), Ok((26, 43, 60)));
+    /// assert_eq!(Str::new("#FFF").parse_hex_color(), Ok((255, 255, 255)));
+    /// assert_eq!(
+    ///     Str::new("#GGG").parse_hex_color(),
+    ///     Err(HexColorError::InvalidDigit(1)),
+    /// );
+    /// ```
+    pub fn parse_hex_color(&self) -> Result<(u8, u8, u8), HexColorError> {
+        let s: &str = self.as_ref();
+        let digits = s.strip_prefix('#').ok_or(HexColorError::MissingHash)?;
+        let len = digits.chars().count();
+        if !matches!(len, 3 | 6 | 8) {
+            return Err(HexColorError::InvalidLength(len));
+        }
+
+        let nibbles = digits
+            .bytes()
+            .enumerate()
+            .map(|(at, b)| {
+                (b as char)
+                    .to_digit(16)
+                    .map(|digit| digit as u8)
+                    .ok_or(HexColorError::InvalidDigit(at + 1))
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        let channel = |i: usize| match len {
+            3 => nibbles[i] * 17,
+            _ => nibbles[2 * i] << 4 | nibbles[2 * i + 1],
+        };
+        Ok((channel(0), channel(1), channel(2)))
+    }
+
+    /// Formats a color as a CSS hex color in the `#rrggbb` form, with lower
+    /// case digits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::format_hex_color(26, 43, 60).as_ref(), "#1a2b3c");
+    /// ```
+    pub fn format_hex_color(r: u8, g: u8, b: u8) -> Str {
+        Str::new(&format!("#{:02x}{:02x}{:02x}", r, g, b))
+    }
+
+    /// Formats a color as a CSS hex color in the `#rgb` form if it can be
+    /// written that way, and in the `#rrggbb` form otherwise, with lower
+    /// case digits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::format_hex_color_short(255, 170, 0).as_ref(), "#fa0");
+    /// assert_eq!(Str::format_hex_color_short(255, 170, 1).as_ref(), "#ffaa01");
+    /// ```
+    pub fn format_hex_color_short(r: u8, g: u8, b: u8) -> Str {
+        if [r, g, b].iter().all(|&channel| channel % 17 == 0) {
+            Str::new(&format!("#{:x}{:x}{:x}", r / 17, g / 17, b / 17))
+        } else {
+            Self::format_hex_color(r, g, b)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_format_with_commas() {
+        assert_eq!(Str::format_with_commas(0).as_ref(), "0");
+        assert_eq!(Str::format_with_commas(999).as_ref(), "999");
+        assert_eq!(Str::format_with_commas(1000).as_ref(), "1,000");
+        assert_eq!(Str::format_with_commas(1234567).as_ref(), "1,234,567");
+        assert_eq!(Str::format_with_commas(-1234).as_ref(), "-1,234");
+        assert_eq!(Str::format_with_commas(-999).as_ref(), "-999");
+    }
+
+    #[test]
+    fn test_format_with_commas_limits() {
+        assert_eq!(
+            Str::format_with_commas(i64::MAX).as_ref(),
+            "9,223,372,036,854,775,807"
+        );
+        assert_eq!(
+            Str::format_with_commas(i64::MIN).as_ref(),
+            "-9,223,372,036,854,775,808"
+        );
+    }
+
+    #[test]
+    fn test_format_with_separator() {
+        assert_eq!(
+            Str::format_with_separator(1234567, ' ', 3).as_ref(),
+            "1 234 567"
+        );
+        assert_eq!(
+            Str::format_with_separator(12345, '.', 2).as_ref(),
+            "1.23.45"
+        );
+        assert_eq!(Str::format_with_separator(-5, '.', 1).as_ref(), "-5");
+    }
+
+    #[test]
+    fn test_format_duration_boundaries() {
+        let fmt = |n| Str::format_duration(n).to_string();
+        assert_eq!(fmt(0), "0ns");
+        assert_eq!(fmt(999), "999ns");
+        assert_eq!(fmt(1_000), "1µs");
+        assert_eq!(fmt(999_999), "999µs");
+        assert_eq!(fmt(1_000_000), "1ms");
+        assert_eq!(fmt(999_999_999), "999ms");
+        assert_eq!(fmt(NANOS_PER_SEC), "1s");
+        assert_eq!(fmt(NANOS_PER_MIN - 1), "59s");
+        assert_eq!(fmt(NANOS_PER_MIN), "1m 0s");
+        assert_eq!(fmt(NANOS_PER_HOUR - 1), "59m 59s");
+        assert_eq!(fmt(NANOS_PER_HOUR), "1h 0m");
+        assert_eq!(fmt(2 * NANOS_PER_HOUR + 34 * NANOS_PER_MIN + 12), "2h 34m");
+        assert_eq!(fmt(u64::MAX), "5124095h 34m");
+    }
+
+    #[test]
+    fn test_format_duration_compact() {
+        let fmt = |n| Str::format_duration_compact(n).to_string();
+        assert_eq!(fmt(0), "0ns");
+        assert_eq!(fmt(999), "999ns");
+        assert_eq!(fmt(1_000), "1µs");
+        assert_eq!(fmt(1_550), "1.5µs");
+        assert_eq!(fmt(142_500_000), "142.5ms");
+        assert_eq!(fmt(999_999_999), "999.9ms");
+        assert_eq!(fmt(NANOS_PER_SEC), "1s");
+        assert_eq!(fmt(12_340_000_000), "12.3s");
+        assert_eq!(fmt(90 * NANOS_PER_SEC), "1.5m");
+        assert_eq!(fmt(NANOS_PER_HOUR), "1h");
+        assert_eq!(fmt(NANOS_PER_HOUR * 5 / 2), "2.5h");
+    }
+
+    #[test]
+    fn test_format_bytes_si() {
+        let fmt = |n| Str::format_bytes_si(n).to_string();
+        assert_eq!(fmt(0), "0 B");
+        assert_eq!(fmt(999), "999 B");
+        assert_eq!(fmt(1000), "1.00 KB");
+        assert_eq!(fmt(1023), "1.02 KB");
+        assert_eq!(fmt(1024), "1.02 KB");
+        assert_eq!(fmt(999_999), "1.00 MB");
+        assert_eq!(fmt(1_000_000), "1.00 MB");
+        assert_eq!(fmt(1_000_000_000), "1.00 GB");
+        assert_eq!(fmt(1_000_000_000_000), "1.00 TB");
+        assert_eq!(fmt(1_000_000_000_000_000), "1.00 PB");
+        assert_eq!(fmt(1_000_000_000_000_000_000), "1.00 EB");
+        assert_eq!(fmt(u64::MAX), "18.45 EB");
+    }
+
+    #[test]
+    fn test_format_bytes_iec() {
+        let fmt = |n| Str::format_bytes_iec(n).to_string();
+        assert_eq!(fmt(0), "0 B");
+        assert_eq!(fmt(1023), "1023 B");
+        assert_eq!(fmt(1024), "1.00 KiB");
+        assert_eq!(fmt(1_000_000), "976.56 KiB");
+        assert_eq!(fmt(1 << 20), "1.00 MiB");
+        assert_eq!(fmt((1 << 20) - 1), "1.00 MiB");
+        assert_eq!(fmt(1 << 30), "1.00 GiB");
+        assert_eq!(fmt(1 << 40), "1.00 TiB");
+        assert_eq!(fmt(1 << 50), "1.00 PiB");
+        assert_eq!(fmt(1 << 60), "1.00 EiB");
+        assert_eq!(fmt(u64::MAX), "16.00 EiB");
+    }
+
+    const PERMS: [&str; 3] = ["READ", "WRITE", "EXEC"];
+
+    #[test]
+    fn test_format_flags() {
+        assert_eq!(
+            Str::format_flags(0b111, &PERMS).as_ref(),
+            "READ|WRITE|EXEC"
+        );
+        assert_eq!(Str::format_flags(0b010, &PERMS).as_ref(), "WRITE");
+        assert_eq!(Str::format_flags(0, &PERMS).as_ref(), "0");
+        assert_eq!(Str::format_flags(0, &[]).as_ref(), "0");
+    }
+
+    #[test]
+    fn test_format_flags_unknown_bits() {
+        assert_eq!(
+            Str::format_flags(0b1001 | 1 << 63, &PERMS).as_ref(),
+            "READ|0x8000000000000008"
+        );
+        assert_eq!(Str::format_flags(0b1000, &PERMS).as_ref(), "0x8");
+        assert_eq!(
+            Str::format_flags(u64::MAX, &[]).as_ref(),
+            "0xffffffffffffffff"
+        );
+    }
+
+    #[test]
+    fn test_format_elapsed_duration() {
+        let fmt = |d| Str::format_elapsed_duration(d).to_string();
+        assert_eq!(fmt(Duration::ZERO), "0ns");
+        assert_eq!(fmt(Duration::from_nanos(999)), "999ns");
+        assert_eq!(fmt(Duration::from_nanos(1_000)), "1µs");
+        assert_eq!(fmt(Duration::from_nanos(999_999)), "999µs");
+        assert_eq!(fmt(Duration::from_millis(1)), "1.00ms");
+        assert_eq!(fmt(Duration::from_micros(1_234)), "1.23ms");
+        assert_eq!(fmt(Duration::from_micros(999_990)), "999.99ms");
+        assert_eq!(fmt(Duration::from_secs(1)), "1.00s");
+        assert_eq!(fmt(Duration::from_millis(5_670)), "5.67s");
+        assert_eq!(fmt(Duration::from_secs(3_600)), "3600.00s");
+    }
+
+    #[test]
+    fn test_format_elapsed_duration_unit_boundaries() {
+        let fmt =
+            |nanos| Str::format_elapsed_duration(Duration::from_nanos(nanos));
+        assert_eq!(fmt(999_994_000).as_ref(), "999.99ms");
+        assert_eq!(fmt(999_995_000).as_ref(), "1.00s");
+        assert_eq!(fmt(999_999_999).as_ref(), "1.00s");
+        assert_eq!(fmt(999_999).as_ref(), "999µs");
+        assert_eq!(fmt(1_000_000).as_ref(), "1.00ms");
+        assert_eq!(fmt(1_004_999).as_ref(), "1.00ms");
+        assert_eq!(fmt(1_005_000).as_ref(), "1.01ms");
+        assert_eq!(fmt(59_994_999_999).as_ref(), "59.99s");
+        assert_eq!(fmt(59_995_000_000).as_ref(), "60.00s");
+    }
+
+    #[test]
+    fn test_format_elapsed_until() {
+        let start = Instant::now();
+        let end = start + Duration::from_micros(1_500);
+        assert_eq!(Str::format_elapsed_until(start, end).as_ref(), "1.50ms");
+        assert_eq!(Str::format_elapsed_until(end, start).as_ref(), "0ns");
+        assert!(!Str::format_elapsed(start).is_empty());
+    }
+
+    #[test]
+    fn test_number_to_words() {
+        let words = |n| Str::number_to_words(n).to_string();
+        assert_eq!(words(0), "zero");
+        assert_eq!(words(1), "one");
+        assert_eq!(words(13), "thirteen");
+        assert_eq!(words(40), "forty");
+        assert_eq!(words(99), "ninety-nine");
+        assert_eq!(words(100), "one hundred");
+        assert_eq!(words(123), "one hundred twenty-three");
+        assert_eq!(words(1000), "one thousand");
+        assert_eq!(words(1001), "one thousand one");
+        assert_eq!(words(1_000_010), "one million ten");
+        assert_eq!(words(-42), "minus forty-two");
+    }
+
+    #[test]
+    fn test_number_to_words_limits() {
+        assert_eq!(
+            Str::number_to_words(i64::MAX).as_ref(),
+            "nine quintillion two hundred twenty-three quadrillion \
+             three hundred seventy-two trillion thirty-six billion \
+             eight hundred fifty-four million seven hundred seventy-five \
+             thousand eight hundred seven"
+        );
+        assert_eq!(
+            Str::number_to_words(i64::MIN).as_ref(),
+            "minus nine quintillion two hundred twenty-three quadrillion \
+             three hundred seventy-two trillion thirty-six billion \
+             eight hundred fifty-four million seven hundred seventy-five \
+             thousand eight hundred eight"
+        );
+    }
+
+    #[test]
+    fn test_ordinal_words() {
+        let ordinal = |n| Str::ordinal_words(n).to_string();
+        assert_eq!(ordinal(0), "zeroth");
+        assert_eq!(ordinal(1), "first");
+        assert_eq!(ordinal(2), "second");
+        assert_eq!(ordinal(3), "third");
+        assert_eq!(ordinal(4), "fourth");
+        assert_eq!(ordinal(5), "fifth");
+        assert_eq!(ordinal(8), "eighth");
+        assert_eq!(ordinal(9), "ninth");
+        assert_eq!(ordinal(12), "twelfth");
+        assert_eq!(ordinal(20), "twentieth");
+        assert_eq!(ordinal(23), "twenty-third");
+        assert_eq!(ordinal(101), "one hundred first");
+        assert_eq!(ordinal(1000), "one thousandth");
+        assert_eq!(ordinal(-1), "minus first");
+    }
+
+    #[test]
+    fn test_insert_separator_every_n_chars() {
+        let group = |s, sep, n| {
+            Str::new(s)
+                .insert_separator_every_n_chars(sep, n)
+                .to_string()
+        };
+        assert_eq!(group("4111111111111111", " ", 4), "4111 1111 1111 1111");
+        assert_eq!(group("9780306406157", "-", 3), "9-780-306-406-157");
+        assert_eq!(group("", "-", 3), "");
+        assert_eq!(group("abc", "-", 3), "abc");
+        assert_eq!(group("abcd", "", 1), "abcd");
+        assert_eq!(group("世界世界世", "·", 2), "世·界世·界世");
+    }
+
+    #[test]
+    fn test_insert_separator_every_n_from_left() {
+        let group = |s, sep, n| {
+            Str::new(s)
+                .insert_separator_every_n_from_left(sep, n)
+                .to_string()
+        };
+        assert_eq!(group("4111111111111111", " ", 4), "4111 1111 1111 1111");
+        assert_eq!(group("9780306406157", "-", 3), "978-030-640-615-7");
+        assert_eq!(group("", "-", 3), "");
+        assert_eq!(group("abc", "-", 3), "abc");
+        assert_eq!(group("abcd", ", ", 1), "a, b, c, d");
+        assert_eq!(group("世界世界世", "·", 2), "世界·世界·世");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_separator_every_zero_chars() {
+        Str::new("abc").insert_separator_every_n_from_left("-", 0);
+    }
+
+    #[test]
+    fn test_format_indian() {
+        assert_eq!(Str::format_indian(0).as_ref(), "0");
+        assert_eq!(Str::format_indian(1000).as_ref(), "1,000");
+        assert_eq!(Str::format_indian(100000).as_ref(), "1,00,000");
+        assert_eq!(Str::format_indian(-12345678).as_ref(), "-1,23,45,678");
+    }
+
+    #[test]
+    fn test_format_in_base() {
+        let format =
+            |n, base| Str::format_in_base(n, base).unwrap().to_string();
+        assert_eq!(format(255, 16), "FF");
+        assert_eq!(format(255, 2), "11111111");
+        assert_eq!(format(255, 36), "73");
+        assert_eq!(format(255, 10), "255");
+        assert_eq!(format(0, 7), "0");
+        assert_eq!(format(35, 36), "Z");
+        assert_eq!(format(u64::MAX, 2), "1".repeat(64));
+        assert_eq!(format(u64::MAX, 16), "FFFFFFFFFFFFFFFF");
+        assert_eq!(Str::format_in_base(1, 1), Err(BaseError::InvalidBase(1)));
+        assert_eq!(Str::format_in_base(1, 37), Err(BaseError::InvalidBase(37)));
+    }
+
+    #[test]
+    fn test_parse_in_base() {
+        let parse = |s, base| Str::new(s).parse_in_base(base);
+        assert_eq!(parse("FF", 16), Ok(255));
+        assert_eq!(parse("ff", 16), Ok(255));
+        assert_eq!(parse("11111111", 2), Ok(255));
+        assert_eq!(parse("73", 36), Ok(255));
+        assert_eq!(parse("0007", 8), Ok(7));
+        assert_eq!(parse("FFFFFFFFFFFFFFFF", 16), Ok(u64::MAX));
+        assert_eq!(parse("10000000000000000", 16), Err(BaseError::Overflow));
+        assert_eq!(parse("", 10), Err(BaseError::Empty));
+        assert_eq!(parse("19a", 10), Err(BaseError::InvalidDigit(2)));
+        assert_eq!(parse("-1", 10), Err(BaseError::InvalidDigit(0)));
+        assert_eq!(parse("é1", 10), Err(BaseError::InvalidDigit(0)));
+        assert_eq!(parse("1", 0), Err(BaseError::InvalidBase(0)));
+    }
+
+    #[test]
+    fn test_format_in_base_round_trip() {
+        for base in 2..=36 {
+            for n in [0, 1, 35, 36, 255, 1_000_000, u64::MAX / 3, u64::MAX] {
+                let formatted = Str::format_in_base(n, base).unwrap();
+                assert_eq!(formatted.parse_in_base(base), Ok(n));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        let parse = |s: &str| Str::new(s).parse_hex_color();
+        assert_eq!(parse("#FFF"), Ok((255, 255, 255)));
+        assert_eq!(parse("#fa0"), Ok((255, 170, 0)));
+        assert_eq!(parse("#000"), Ok((0, 0, 0)));
+        assert_eq!(parse("#1a2b3c"), Ok((26, 43, 60)));
+        assert_eq!(parse("#1A2B3C"), Ok((26, 43, 60)));
+        assert_eq!(parse("#1a2B3c80"), Ok((26, 43, 60)));
+        assert_eq!(parse("#ffffff00"), Ok((255, 255, 255)));
+    }
+
+    #[test]
+    fn test_parse_hex_color_errors() {
+        let parse = |s: &str| Str::new(s).parse_hex_color();
+        assert_eq!(parse("#GGG"), Err(HexColorError::InvalidDigit(1)));
+        assert_eq!(parse("#12345z"), Err(HexColorError::InvalidDigit(6)));
+        assert_eq!(parse("#12 456"), Err(HexColorError::InvalidDigit(3)));
+        assert_eq!(parse("#+12"), Err(HexColorError::InvalidDigit(1)));
+        assert_eq!(parse("#éfe"), Err(HexColorError::InvalidDigit(1)));
+        assert_eq!(parse("#"), Err(HexColorError::InvalidLength(0)));
+        assert_eq!(parse("#ff"), Err(HexColorError::InvalidLength(2)));
+        assert_eq!(parse("#ffff"), Err(HexColorError::InvalidLength(4)));
+        assert_eq!(parse("#fffff"), Err(HexColorError::InvalidLength(5)));
+        assert_eq!(parse("#fffffff"), Err(HexColorError::InvalidLength(7)));
+        assert_eq!(parse("#fffffffff"), Err(HexColorError::InvalidLength(9)));
+        assert_eq!(parse("fff"), Err(HexColorError::MissingHash));
+        assert_eq!(parse(" #fff"), Err(HexColorError::MissingHash));
+        assert_eq!(parse(""), Err(HexColorError::MissingHash));
+    }
+
+    #[test]
+    fn test_format_hex_color() {
+        assert_eq!(Str::format_hex_color(26, 43, 60).as_ref(), "#1a2b3c");
+        assert_eq!(Str::format_hex_color(0, 0, 0).as_ref(), "#000000");
+        assert_eq!(Str::format_hex_color(255, 255, 255).as_ref(), "#ffffff");
+
+        assert_eq!(Str::format_hex_color_short(255, 255, 255).as_ref(), "#fff");
+        assert_eq!(Str::format_hex_color_short(0, 17, 34).as_ref(), "#012");
+        assert_eq!(Str::format_hex_color_short(26, 43, 60).as_ref(), "#1a2b3c");
+        assert_eq!(
+            Str::format_hex_color_short(255, 255, 254).as_ref(),
+            "#fffffe"
+        );
+
+        for (r, g, b) in [(26, 43, 60), (0, 17, 34), (255, 128, 7)] {
+            let long = Str::format_hex_color(r, g, b);
+            let short = Str::format_hex_color_short(r, g, b);
+            assert_eq!(long.parse_hex_color(), Ok((r, g, b)));
+            assert_eq!(short.parse_hex_color(), Ok((r, g, b)));
+        }
+    }
+}
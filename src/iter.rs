@@ -0,0 +1,315 @@
+use crate::Str;
+
+impl Str {
+    /// Returns an iterator over chunks of `n` characters, starting at the end
+    /// of the string.
+    ///
+    /// Chunks are yielded from right to left, so the last chunk yielded (the
+    /// leftmost one) may be shorter than `n`. Use `.rev()` to get the chunks
+    /// in reading order.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("1234567");
+    /// let groups: Vec<&str> = s.rchunks_by_char_count(3).rev().collect();
+    /// assert_eq!(groups, ["1", "234", "567"]);
+    /// ```
+    pub fn rchunks_by_char_count(
+        &self,
+        n: usize,
+    ) -> impl DoubleEndedIterator<Item = &str> + '_ {
+        assert!(n != 0, "chunk size must be non-zero");
+        let s: &str = self.as_ref();
+        RChunksByCharCount {
+            s,
+            n,
+            chars: s.chars().count(),
+        }
+    }
+
+    /// Returns an iterator over every substring of exactly `size`
+    /// characters, sliding one character at a time.
+    ///
+    /// Yields nothing if `size` is greater than [`Str::char_count`].
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("abcd");
+    /// let bigrams: Vec<&str> = s.char_windows(2).collect();
+    /// assert_eq!(bigrams, ["ab", "bc", "cd"]);
+    /// ```
+    pub fn char_windows(&self, size: usize) -> impl Iterator<Item = &str> + '_ {
+        assert!(size != 0, "window size must be non-zero");
+        let s: &str = self.as_ref();
+        let boundaries = s.char_indices().map(|(at, _)| at);
+        let ends = boundaries.clone().chain(Some(s.len())).skip(size);
+        boundaries.zip(ends).map(move |(start, end)| &s[start..end])
+    }
+
+    /// Returns an iterator over the UTF-8 bytes of the string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("é!");
+    /// assert_eq!(s.iter_bytes().copied().collect::<Vec<u8>>(), [0xC3, 0xA9, b'!']);
+    /// ```
+    #[inline]
+    pub fn iter_bytes(&self) -> std::slice::Iter<'_, u8> {
+        self.as_ref().as_bytes().iter()
+    }
+
+    /// Returns an iterator over the characters of the string and the byte
+    /// offsets they start at, like [`str::char_indices`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("aé b");
+    /// let pairs: Vec<(usize, char)> = s.iter_chars_with_byte_offsets().collect();
+    /// assert_eq!(pairs, [(0, 'a'), (1, 'é'), (3, ' '), (4, 'b')]);
+    /// ```
+    #[inline]
+    pub fn iter_chars_with_byte_offsets(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (usize, char)> + '_ {
+        self.as_ref().char_indices()
+    }
+
+    /// Returns an iterator over the lines of the string together with their
+    /// line numbers, starting at 1.
+    ///
+    /// Lines are split like [`str::lines`], so a trailing line ending does
+    /// not produce an extra empty line and `\r\n` is stripped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("fn main() {\n}\n");
+    /// let lines: Vec<(usize, &str)> = s.lines_numbered().collect();
+    /// assert_eq!(lines, [(1, "fn main() {"), (2, "}")]);
+    /// ```
+    pub fn lines_numbered(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+        self.as_ref()
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+    }
+
+    /// Returns the line with the given 1-based number, or `None` if there is
+    /// no such line.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("first\nsecond");
+    /// assert_eq!(s.line_at(2), Some("second"));
+    /// assert_eq!(s.line_at(3), None);
+    /// ```
+    pub fn line_at(&self, line_number: usize) -> Option<&str> {
+        self.as_ref().lines().nth(line_number.checked_sub(1)?)
+    }
+
+    /// Returns an iterator over every `size` byte window of the string.
+    ///
+    /// Windows may start or end inside a multi-byte character, so they are
+    /// yielded as byte slices.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[inline]
+    pub fn byte_windows(&self, size: usize) -> std::slice::Windows<'_, u8> {
+        self.as_ref().as_bytes().windows(size)
+    }
+}
+
+struct RChunksByCharCount<'a> {
+    s: &'a str,
+    n: usize,
+    chars: usize,
+}
+
+impl<'a> Iterator for RChunksByCharCount<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.chars == 0 {
+            return None;
+        }
+
+        let take = self.n.min(self.chars);
+        let at = match self.s.char_indices().rev().nth(take - 1) {
+            Some((at, _)) => at,
+            None => 0,
+        };
+        let (rest, chunk) = self.s.split_at(at);
+        self.s = rest;
+        self.chars -= take;
+        Some(chunk)
+    }
+}
+
+impl DoubleEndedIterator for RChunksByCharCount<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.chars == 0 {
+            return None;
+        }
+
+        let take = match self.chars % self.n {
+            0 => self.n,
+            rem => rem,
+        };
+        let at = match self.s.char_indices().nth(take) {
+            Some((at, _)) => at,
+            None => self.s.len(),
+        };
+        let (chunk, rest) = self.s.split_at(at);
+        self.s = rest;
+        self.chars -= take;
+        Some(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rchunks_by_char_count_groups_from_right() {
+        let s = Str::new("1234567");
+        let chunks: Vec<&str> = s.rchunks_by_char_count(3).collect();
+        assert_eq!(chunks, ["567", "234", "1"]);
+
+        let chunks: Vec<&str> = s.rchunks_by_char_count(3).rev().collect();
+        assert_eq!(chunks, ["1", "234", "567"]);
+    }
+
+    #[test]
+    fn test_rchunks_by_char_count_divisible() {
+        let s = Str::new("abcdef");
+        let chunks: Vec<&str> = s.rchunks_by_char_count(2).rev().collect();
+        assert_eq!(chunks, ["ab", "cd", "ef"]);
+    }
+
+    #[test]
+    fn test_rchunks_by_char_count_unicode() {
+        let s = Str::new("世界abc");
+        let chunks: Vec<&str> = s.rchunks_by_char_count(2).rev().collect();
+        assert_eq!(chunks, ["世", "界a", "bc"]);
+    }
+
+    #[test]
+    fn test_rchunks_by_char_count_mixed_ends() {
+        let s = Str::new("1234567");
+        let mut it = s.rchunks_by_char_count(3);
+        assert_eq!(it.next_back(), Some("1"));
+        assert_eq!(it.next(), Some("567"));
+        assert_eq!(it.next_back(), Some("234"));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_rchunks_by_char_count_empty() {
+        let s = Str::new("");
+        assert_eq!(s.rchunks_by_char_count(3).count(), 0);
+    }
+
+    #[test]
+    fn test_char_windows_ngrams() {
+        let s = Str::new("hello");
+        let bigrams: Vec<&str> = s.char_windows(2).collect();
+        assert_eq!(bigrams, ["he", "el", "ll", "lo"]);
+
+        let trigrams: Vec<&str> = s.char_windows(3).collect();
+        assert_eq!(trigrams, ["hel", "ell", "llo"]);
+
+        let whole: Vec<&str> = s.char_windows(5).collect();
+        assert_eq!(whole, ["hello"]);
+    }
+
+    #[test]
+    fn test_char_windows_single_chars() {
+        let s = Str::new("a世b");
+        let chars: Vec<&str> = s.char_windows(1).collect();
+        assert_eq!(chars, ["a", "世", "b"]);
+
+        let pairs: Vec<&str> = s.char_windows(2).collect();
+        assert_eq!(pairs, ["a世", "世b"]);
+    }
+
+    #[test]
+    fn test_char_windows_too_large() {
+        let s = Str::new("hello");
+        assert_eq!(s.char_windows(6).count(), 0);
+        assert_eq!(Str::new("").char_windows(1).count(), 0);
+    }
+
+    #[test]
+    fn test_iter_bytes() {
+        for input in ["", "ascii", "Hello, 世界! 🎉"] {
+            let s = Str::new(input);
+            let bytes: Vec<u8> = s.iter_bytes().copied().collect();
+            assert_eq!(bytes, input.as_bytes());
+            assert_eq!(s.iter_bytes().len(), s.len());
+        }
+    }
+
+    #[test]
+    fn test_iter_chars_with_byte_offsets() {
+        for input in ["", "ascii", "Hello, 世界! 🎉"] {
+            let s = Str::new(input);
+            assert!(s.iter_chars_with_byte_offsets().eq(input.char_indices()));
+            assert!(s
+                .iter_chars_with_byte_offsets()
+                .rev()
+                .eq(input.char_indices().rev()));
+        }
+    }
+
+    #[test]
+    fn test_byte_windows() {
+        let s = Str::new("abcd");
+        let windows: Vec<&[u8]> = s.byte_windows(3).collect();
+        assert_eq!(windows, [b"abc", b"bcd"]);
+        assert_eq!(Str::new("é").byte_windows(1).count(), 2);
+        assert_eq!(s.byte_windows(5).count(), 0);
+    }
+
+    #[test]
+    fn test_lines_numbered() {
+        let s = Str::new("one\ntwo\r\n\nfour\nfive\n");
+        let lines: Vec<(usize, &str)> = s.lines_numbered().collect();
+        assert_eq!(
+            lines,
+            [(1, "one"), (2, "two"), (3, ""), (4, "four"), (5, "five")]
+        );
+        assert_eq!(Str::new("").lines_numbered().count(), 0);
+    }
+
+    #[test]
+    fn test_line_at() {
+        let s = Str::new("one\ntwo\r\n\nfour\nfive");
+        assert_eq!(s.line_at(0), None);
+        assert_eq!(s.line_at(1), Some("one"));
+        assert_eq!(s.line_at(2), Some("two"));
+        assert_eq!(s.line_at(3), Some(""));
+        assert_eq!(s.line_at(5), Some("five"));
+        assert_eq!(s.line_at(6), None);
+    }
+
+    #[test]
+    fn test_line_at_single_line() {
+        let s = Str::new("only 世界");
+        assert_eq!(s.line_at(1), Some("only 世界"));
+        assert_eq!(s.line_at(2), None);
+        assert_eq!(Str::new("").line_at(1), None);
+    }
+}
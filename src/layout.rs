@@ -0,0 +1,1124 @@
+use crate::Str;
+#[cfg(feature = "unicode-width")]
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Truncates or pads `value` with spaces to exactly `width` characters,
+/// either left-aligned or centered.
+fn push_cell(out: &mut String, value: &str, width: usize, center: bool) {
+    let len = value.chars().count();
+    if len >= width {
+        out.extend(value.chars().take(width));
+        return;
+    }
+
+    let pad = width - len;
+    let left = if center { pad / 2 } else { 0 };
+    out.extend(std::iter::repeat_n(' ', left));
+    out.push_str(value);
+    out.extend(std::iter::repeat_n(' ', pad - left));
+}
+
+/// Horizontal alignment of text within a fixed width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    Left,
+    Right,
+    /// Centered, with the odd column of padding on the right.
+    Center,
+}
+
+/// Returns the number of terminal columns `c` occupies: 2 for East Asian
+/// wide characters and emoji, 0 for combining marks and zero-width
+/// characters, and 1 otherwise.
+#[cfg(not(feature = "unicode-width"))]
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F900..=0x1F9FF
+        | 0x1FA70..=0x1FAFF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(not(feature = "unicode-width"))]
+fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+#[cfg(feature = "unicode-width")]
+fn display_width(s: &str) -> usize {
+    s.width()
+}
+
+fn table_row(
+    widths: &[usize],
+    values: &[&str],
+    sep: &str,
+    center: bool,
+) -> Str {
+    let mut out = String::new();
+    for (i, &width) in widths.iter().enumerate() {
+        if i > 0 {
+            out.push_str(sep);
+        }
+        push_cell(
+            &mut out,
+            values.get(i).copied().unwrap_or(""),
+            width,
+            center,
+        );
+    }
+    Str::new(&out)
+}
+
+/// Appends a list item to `out`, starting its first line with `marker` and
+/// aligning the following lines under the item's text.
+fn push_list_item(out: &mut String, indent: &str, marker: &str, item: &str) {
+    let hanging = " ".repeat(display_width(marker));
+    for (i, line) in item.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+            if line.is_empty() {
+                continue;
+            }
+        }
+        out.push_str(indent);
+        out.push_str(if i == 0 { marker } else { &hanging });
+        out.push_str(line);
+    }
+}
+
+impl Str {
+    /// Surrounds the string with a border made of `border` characters.
+    ///
+    /// Multi-line content is left-aligned and padded with spaces to the
+    /// longest line. Widths are measured in characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("hello");
+    /// assert_eq!(s.to_ascii_art_box('*').as_ref(), "*******\n*hello*\n*******");
+    /// ```
+    pub fn to_ascii_art_box(&self, border: char) -> Str {
+        self.boxed([border; 6])
+    }
+
+    /// Surrounds the string with a double-line box drawn with Unicode
+    /// box-drawing characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("hi");
+    /// assert_eq!(s.to_double_border_box().as_ref(), "╔══╗\n║hi║\n╚══╝");
+    /// ```
+    pub fn to_double_border_box(&self) -> Str {
+        self.boxed(['╔', '╗', '╚', '╝', '═', '║'])
+    }
+
+    /// Formats one table row with each value left-aligned in a column of the
+    /// corresponding width, joined with `sep`.
+    ///
+    /// Widths are measured in characters. Longer values are truncated, and
+    /// missing values are rendered as empty cells; values past the last
+    /// width are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let row = Str::format_table_row(&[5, 3], &["id", "value"], " | ");
+    /// assert_eq!(row.as_ref(), "id    | val");
+    /// ```
+    #[inline]
+    pub fn format_table_row(
+        widths: &[usize],
+        values: &[&str],
+        sep: &str,
+    ) -> Str {
+        table_row(widths, values, sep, false)
+    }
+
+    /// Formats a table header row like [`Str::format_table_row`], but with
+    /// the headers centered in their columns.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let row = Str::format_table_header(&[6, 3], &["name", "n"], "|");
+    /// assert_eq!(row.as_ref(), " name | n ");
+    /// ```
+    #[inline]
+    pub fn format_table_header(
+        widths: &[usize],
+        headers: &[&str],
+        sep: &str,
+    ) -> Str {
+        table_row(widths, headers, sep, true)
+    }
+
+    /// Formats a divider row made of `fill` characters for each column,
+    /// joined with `sep`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let row = Str::format_table_separator(&[4, 2], '-', "-+-");
+    /// assert_eq!(row.as_ref(), "-----+---");
+    /// ```
+    pub fn format_table_separator(
+        widths: &[usize],
+        fill: char,
+        sep: &str,
+    ) -> Str {
+        let mut out = String::new();
+        for (i, &width) in widths.iter().enumerate() {
+            if i > 0 {
+                out.push_str(sep);
+            }
+            out.extend(std::iter::repeat_n(fill, width));
+        }
+        Str::new(&out)
+    }
+
+    /// Renders a complete table with a header row, a separator line and one
+    /// line per row, with every column as wide as its widest cell.
+    ///
+    /// Cells are left-aligned and padded according to their display width,
+    /// so wide characters such as CJK ideographs take two columns. Rows
+    /// shorter than the header are padded with empty cells, and longer rows
+    /// add columns with empty headers.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let table = Str::render_table(&["id", "name"], &[vec!["1", "alice"]]);
+    /// assert_eq!(
+    ///     table.as_ref(),
+    ///     "| id | name  |\n|----|-------|\n| 1  | alice |"
+    /// );
+    /// ```
+    pub fn render_table(headers: &[&str], rows: &[Vec<&str>]) -> Str {
+        let columns = rows.iter().map(Vec::len).fold(headers.len(), usize::max);
+        let mut widths = vec![0; columns];
+        for line in
+            std::iter::once(headers).chain(rows.iter().map(Vec::as_slice))
+        {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(display_width(cell));
+            }
+        }
+
+        let line = |cells: &[&str]| {
+            let mut out = String::from("|");
+            for (i, &width) in widths.iter().enumerate() {
+                let cell = cells.get(i).copied().unwrap_or("");
+                out.push(' ');
+                out.push_str(cell);
+                let pad = width - display_width(cell) + 1;
+                out.extend(std::iter::repeat_n(' ', pad));
+                out.push('|');
+            }
+            out
+        };
+
+        let mut lines = vec![line(headers)];
+        let mut separator = String::from("|");
+        for &width in &widths {
+            separator.extend(std::iter::repeat_n('-', width + 2));
+            separator.push('|');
+        }
+        lines.push(separator);
+        lines.extend(rows.iter().map(|row| line(row)));
+        Str::new(&lines.join("\n"))
+    }
+
+    /// Formats `items` as a list with one item per line, each starting with
+    /// `indent` followed by `bullet`.
+    ///
+    /// The lines of a multi-line item after the first are indented to line
+    /// up with its text, except for empty lines which are left empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let list = Str::format_bullet_list(&["alpha", "beta"], "- ", "  ");
+    /// assert_eq!(list.as_ref(), "  - alpha\n  - beta");
+    /// ```
+    pub fn format_bullet_list(
+        items: &[&str],
+        bullet: &str,
+        indent: &str,
+    ) -> Str {
+        let mut out = String::new();
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            push_list_item(&mut out, indent, bullet, item);
+        }
+        Str::new(&out)
+    }
+
+    /// Formats `items` as a list numbered from `1. `, with one item per line
+    /// starting with `indent`.
+    ///
+    /// The numbers are right-aligned so that the text of all items lines up,
+    /// and multi-line items are indented like in
+    /// [`Str::format_bullet_list`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let list = Str::format_numbered_list(&["alpha", "beta"], "");
+    /// assert_eq!(list.as_ref(), "1. alpha\n2. beta");
+    /// ```
+    pub fn format_numbered_list(items: &[&str], indent: &str) -> Str {
+        let width = items.len().to_string().len();
+        let mut out = String::new();
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            let number = format!("{:>width$}. ", i + 1, width = width);
+            push_list_item(&mut out, indent, &number, item);
+        }
+        Str::new(&out)
+    }
+
+    /// Fully justifies the text into lines of exactly `width` characters.
+    ///
+    /// Words are packed greedily and the remaining space on each line is
+    /// distributed between words, with the extra spaces going to the
+    /// leftmost gaps. Lines holding a single word and the last line are
+    /// left-justified and padded with trailing spaces. A word longer than
+    /// `width` is placed on its own line as is.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("This is an example of text justification.");
+    /// let lines = s.justify_text(16);
+    /// assert_eq!(lines[0].as_ref(), "This    is    an");
+    /// assert_eq!(lines[1].as_ref(), "example  of text");
+    /// assert_eq!(lines[2].as_ref(), "justification.  ");
+    /// ```
+    pub fn justify_text(&self, width: usize) -> Vec<Str> {
+        let words: Vec<(&str, usize)> = self
+            .as_ref()
+            .split_whitespace()
+            .map(|word| (word, word.chars().count()))
+            .collect();
+
+        let mut lines = Vec::new();
+        let mut start = 0;
+        while start < words.len() {
+            let mut end = start + 1;
+            let mut used = words[start].1;
+            while end < words.len() && used + 1 + words[end].1 <= width {
+                used += 1 + words[end].1;
+                end += 1;
+            }
+
+            let line = &words[start..end];
+            let mut out = String::with_capacity(width);
+            if end == words.len() || line.len() == 1 {
+                for (i, (word, _)) in line.iter().enumerate() {
+                    if i > 0 {
+                        out.push(' ');
+                    }
+                    out.push_str(word);
+                }
+                let pad = width.saturating_sub(used);
+                out.extend(std::iter::repeat_n(' ', pad));
+            } else {
+                let gaps = line.len() - 1;
+                let spaces = width - (used - gaps);
+                for (i, (word, _)) in line.iter().enumerate() {
+                    if i > 0 {
+                        let extra = usize::from(i <= spaces % gaps);
+                        let pad = spaces / gaps + extra;
+                        out.extend(std::iter::repeat_n(' ', pad));
+                    }
+                    out.push_str(word);
+                }
+            }
+            lines.push(Str::new(&out));
+            start = end;
+        }
+        lines
+    }
+
+    /// Wraps the text into lines of at most `width` characters, starting the
+    /// first line with `first_indent` and every other line with `indent`.
+    ///
+    /// The indent counts towards `width`. Lines are broken at whitespace,
+    /// and runs of whitespace between words are collapsed into a single
+    /// space. A word that does not fit on a line of its own is broken
+    /// across lines. The lines are joined with `\n`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("the quick brown fox jumps");
+    /// assert_eq!(
+    ///     s.wrap_with_indent(12, "  ", "> ").as_ref(),
+    ///     "> the quick\n  brown fox\n  jumps",
+    /// );
+    /// ```
+    pub fn wrap_with_indent(
+        &self,
+        width: usize,
+        indent: &str,
+        first_indent: &str,
+    ) -> Str {
+        // Leave room for at least one character, however long the indent.
+        let first_room =
+            width.saturating_sub(first_indent.chars().count()).max(1);
+        let room = width.saturating_sub(indent.chars().count()).max(1);
+
+        let mut lines: Vec<String> = Vec::new();
+        let mut line = String::new();
+        let mut used = 0;
+        for word in self.as_ref().split_whitespace() {
+            let mut chars: Vec<char> = word.chars().collect();
+            if used > 0 {
+                let available =
+                    if lines.is_empty() { first_room } else { room };
+                if used + 1 + chars.len() <= available {
+                    line.push(' ');
+                    line.push_str(word);
+                    used += 1 + chars.len();
+                    continue;
+                }
+                lines.push(std::mem::take(&mut line));
+            }
+
+            loop {
+                let (prefix, available) = if lines.is_empty() {
+                    (first_indent, first_room)
+                } else {
+                    (indent, room)
+                };
+                let take = chars.len().min(available);
+                line.push_str(prefix);
+                line.extend(chars.drain(..take));
+                used = take;
+                if chars.is_empty() {
+                    break;
+                }
+                lines.push(std::mem::take(&mut line));
+            }
+        }
+        if used > 0 {
+            lines.push(line);
+        }
+        Str::new(&lines.join("\n"))
+    }
+
+    /// Pads or truncates the string to exactly `target_chars` characters.
+    ///
+    /// Shorter strings are padded with `fill` according to `align`. Longer
+    /// strings lose characters from the end if `truncate_end` is `true`, and
+    /// from the start otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{Alignment, Str};
+    /// let s = Str::new("report.txt");
+    /// assert_eq!(s.to_fixed_char_len(6, ' ', Alignment::Left, true).as_ref(), "report");
+    /// assert_eq!(s.to_fixed_char_len(6, ' ', Alignment::Left, false).as_ref(), "rt.txt");
+    /// assert_eq!(s.to_fixed_char_len(12, '.', Alignment::Right, true).as_ref(), "..report.txt");
+    /// ```
+    pub fn to_fixed_char_len(
+        &self,
+        target_chars: usize,
+        fill: char,
+        align: Alignment,
+        truncate_end: bool,
+    ) -> Str {
+        let s: &str = self.as_ref();
+        let len = s.chars().count();
+        if len >= target_chars {
+            let out: String = if truncate_end {
+                s.chars().take(target_chars).collect()
+            } else {
+                s.chars().skip(len - target_chars).collect()
+            };
+            return Str::new(&out);
+        }
+
+        let pad = target_chars - len;
+        let left = match align {
+            Alignment::Left => 0,
+            Alignment::Right => pad,
+            Alignment::Center => pad / 2,
+        };
+        let mut out = String::with_capacity(s.len() + pad * fill.len_utf8());
+        out.extend(std::iter::repeat_n(fill, left));
+        out.push_str(s);
+        out.extend(std::iter::repeat_n(fill, pad - left));
+        Str::new(&out)
+    }
+
+    /// Centers the string in `width` characters by padding it with spaces.
+    ///
+    /// See [`Str::center_with`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("title").center(9).as_ref(), "  title  ");
+    /// ```
+    #[inline]
+    pub fn center(&self, width: usize) -> Str {
+        self.center_with(width, ' ')
+    }
+
+    /// Centers the string in `width` characters by padding it with `fill`.
+    ///
+    /// The width is measured in characters. When the padding cannot be
+    /// split evenly, the extra character goes on the right. A string that is
+    /// already at least `width` characters long is returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("menu").center_with(9, '=').as_ref(), "==menu===");
+    /// assert_eq!(Str::new("long title").center_with(4, '=').as_ref(), "long title");
+    /// ```
+    pub fn center_with(&self, width: usize, fill: char) -> Str {
+        if self.char_count() >= width {
+            return self.clone();
+        }
+        self.to_fixed_char_len(width, fill, Alignment::Center, true)
+    }
+
+    /// Returns the number of terminal columns the string occupies, as
+    /// defined by Unicode Standard Annex #11.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("ab世界").display_width(), 6);
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    #[inline]
+    pub fn display_width(&self) -> usize {
+        self.as_ref().width()
+    }
+
+    /// Pads the string with `fill` to `width` terminal columns.
+    ///
+    /// Unlike padding by character count, wide characters such as CJK
+    /// ideographs count as two columns. If `fill` is itself wide and the
+    /// padding does not divide evenly, the remaining column is padded with
+    /// a space; a zero-width `fill` is replaced by a space. Strings already
+    /// wider than `width` are returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{Alignment, Str};
+    /// let s = Str::new("世界");
+    /// assert_eq!(s.pad_to_width(6, '.', Alignment::Left).as_ref(), "世界..");
+    /// assert_eq!(s.pad_to_width(6, '.', Alignment::Right).as_ref(), "..世界");
+    /// assert_eq!(s.pad_to_width(7, '.', Alignment::Center).as_ref(), ".世界..");
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    pub fn pad_to_width(
+        &self,
+        width: usize,
+        fill: char,
+        align: Alignment,
+    ) -> Str {
+        let s: &str = self.as_ref();
+        let pad = width.saturating_sub(s.width());
+        let (left, right) = match align {
+            Alignment::Left => (0, pad),
+            Alignment::Right => (pad, 0),
+            Alignment::Center => (pad / 2, pad - pad / 2),
+        };
+
+        let (fill, fill_width) = match fill.width() {
+            Some(0) | None => (' ', 1),
+            Some(fill_width) => (fill, fill_width),
+        };
+        let push_padding = |out: &mut String, columns: usize| {
+            out.extend(std::iter::repeat_n(fill, columns / fill_width));
+            out.extend(std::iter::repeat_n(' ', columns % fill_width));
+        };
+
+        let mut out = String::with_capacity(s.len() + pad * fill.len_utf8());
+        push_padding(&mut out, left);
+        out.push_str(s);
+        push_padding(&mut out, right);
+        Str::new(&out)
+    }
+
+    /// Wraps the string into lines of at most `width` grapheme clusters.
+    ///
+    /// Lines are broken at whitespace where possible, and runs of whitespace
+    /// between words are collapsed into a single space. A word longer than
+    /// `width` is broken between grapheme clusters, so emoji sequences and
+    /// combining characters are never split.
+    ///
+    /// # Panics
+    /// Panics if `width` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let lines = Str::new("a cafe\u{301} 👩‍👩‍👧").wrap_graphemes(6);
+    /// assert_eq!(lines, [Str::new("a cafe\u{301}"), Str::new("👩‍👩‍👧")]);
+    /// ```
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn wrap_graphemes(&self, width: usize) -> Vec<Str> {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        assert!(width != 0, "width must be non-zero");
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        let mut used = 0;
+        for word in self.as_ref().split_whitespace() {
+            let graphemes: Vec<&str> = word.graphemes(true).collect();
+            if used > 0 && used + 1 + graphemes.len() <= width {
+                line.push(' ');
+                line.push_str(word);
+                used += 1 + graphemes.len();
+                continue;
+            }
+
+            if used > 0 {
+                lines.push(Str::new(&line));
+                line.clear();
+            }
+            let mut chunks = graphemes.chunks(width).peekable();
+            while let Some(chunk) = chunks.next() {
+                line.extend(chunk.iter().copied());
+                used = chunk.len();
+                if chunks.peek().is_some() {
+                    lines.push(Str::new(&line));
+                    line.clear();
+                }
+            }
+        }
+        if used > 0 {
+            lines.push(Str::new(&line));
+        }
+        lines
+    }
+
+    /// Draws a box using `[top_left, top_right, bottom_left, bottom_right,
+    /// horizontal, vertical]` characters.
+    fn boxed(&self, [tl, tr, bl, br, h, v]: [char; 6]) -> Str {
+        let s: &str = self.as_ref();
+        let lines: Vec<&str> = if s.is_empty() {
+            vec![""]
+        } else {
+            s.lines().collect()
+        };
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut out = String::with_capacity((width + 3) * (lines.len() + 2));
+        out.push(tl);
+        out.extend(std::iter::repeat_n(h, width));
+        out.push(tr);
+        for line in lines {
+            out.push('\n');
+            out.push(v);
+            out.push_str(line);
+            out.extend(std::iter::repeat_n(' ', width - line.chars().count()));
+            out.push(v);
+        }
+        out.push('\n');
+        out.push(bl);
+        out.extend(std::iter::repeat_n(h, width));
+        out.push(br);
+        Str::new(&out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_ascii_art_box_single_line() {
+        let s = Str::new("hello");
+        let boxed = s.to_ascii_art_box('#');
+        let lines: Vec<&str> = boxed.as_ref().lines().collect();
+        assert_eq!(lines, ["#######", "#hello#", "#######"]);
+    }
+
+    #[test]
+    fn test_to_ascii_art_box_multi_line() {
+        let s = Str::new("a\nlong line\nmid");
+        assert_eq!(
+            s.to_ascii_art_box('*').as_ref(),
+            "***********\n\
+             *a        *\n\
+             *long line*\n\
+             *mid      *\n\
+             ***********"
+        );
+    }
+
+    #[test]
+    fn test_to_ascii_art_box_empty() {
+        let s = Str::new("");
+        assert_eq!(s.to_ascii_art_box('+').as_ref(), "++\n++\n++");
+    }
+
+    #[test]
+    fn test_to_double_border_box() {
+        let s = Str::new("世界\nab");
+        let boxed = s.to_double_border_box();
+        let lines: Vec<Vec<char>> = boxed
+            .as_ref()
+            .lines()
+            .map(|l| l.chars().collect())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|l| l.len() == 4));
+        assert_eq!(lines[0], ['╔', '═', '═', '╗']);
+        assert_eq!(lines[1], ['║', '世', '界', '║']);
+        assert_eq!(lines[2], ['║', 'a', 'b', '║']);
+        assert_eq!(lines[3], ['╚', '═', '═', '╝']);
+    }
+
+    #[test]
+    fn test_format_table() {
+        let widths = [4, 8, 5];
+        let sep = " | ";
+        let rows = [
+            Str::format_table_header(&widths, &["id", "name", "score"], sep),
+            Str::format_table_separator(&widths, '-', "-+-"),
+            Str::format_table_row(&widths, &["1", "alice", "9.5"], sep),
+            Str::format_table_row(&widths, &["2", "bartholomew", "10"], sep),
+            Str::format_table_row(&widths, &["30", "", "7.25"], sep),
+            Str::format_table_row(&widths, &["4"], sep),
+        ];
+        let lines: Vec<&str> = rows.iter().map(|row| row.as_ref()).collect();
+        assert_eq!(
+            lines,
+            [
+                " id  |   name   | score",
+                "-----+----------+------",
+                "1    | alice    | 9.5  ",
+                "2    | bartholo | 10   ",
+                "30   |          | 7.25 ",
+                "4    |          |      ",
+            ]
+        );
+        assert!(rows.iter().all(|row| row.len() == rows[0].len()));
+    }
+
+    #[test]
+    fn test_format_table_unicode_and_empty() {
+        let row = Str::format_table_row(&[3, 2], &["世界", "éèê"], "|");
+        assert_eq!(row.as_ref(), "世界 |éè");
+        assert_eq!(Str::format_table_row(&[], &["x"], "|").as_ref(), "");
+        assert_eq!(
+            Str::format_table_separator(&[0, 1], '=', "|").as_ref(),
+            "|="
+        );
+    }
+
+    #[test]
+    fn test_render_table() {
+        let rows = [
+            vec!["1", "alice", "9.5"],
+            vec!["2", "bartholomew", "10"],
+            vec!["30", "bob", "7.25"],
+            vec!["400", "carol", "100.125"],
+            vec!["5", "dave", "0"],
+        ];
+        let table = Str::render_table(&["id", "name", "score"], &rows);
+        assert_eq!(
+            table.as_ref(),
+            "| id  | name        | score   |\n\
+             |-----|-------------|---------|\n\
+             | 1   | alice       | 9.5     |\n\
+             | 2   | bartholomew | 10      |\n\
+             | 30  | bob         | 7.25    |\n\
+             | 400 | carol       | 100.125 |\n\
+             | 5   | dave        | 0       |"
+        );
+    }
+
+    #[test]
+    fn test_render_table_single_column() {
+        let table =
+            Str::render_table(&["fruit"], &[vec!["fig"], vec!["banana"]]);
+        assert_eq!(
+            table.as_ref(),
+            "| fruit  |\n|--------|\n| fig    |\n| banana |"
+        );
+    }
+
+    #[test]
+    fn test_render_table_empty_and_wide_cells() {
+        let rows = [vec!["", "世界"], vec!["é", ""], vec!["x"]];
+        let table = Str::render_table(&["a", "b"], &rows);
+        assert_eq!(
+            table.as_ref(),
+            "| a | b    |\n\
+             |---|------|\n\
+             |   | 世界 |\n\
+             | é |      |\n\
+             | x |      |"
+        );
+        assert_eq!(Str::render_table(&[], &[]).as_ref(), "|\n|");
+    }
+
+    fn justified(s: &str, width: usize) -> Vec<String> {
+        Str::new(s)
+            .justify_text(width)
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_justify_text_known_output() {
+        assert_eq!(
+            justified("What must be acknowledgment shall be", 16),
+            ["What   must   be", "acknowledgment  ", "shall be        "]
+        );
+        assert_eq!(
+            justified(
+                "Science is what we understand well enough to explain to a \
+                 computer. Art is everything else we do",
+                20
+            ),
+            [
+                "Science  is  what we",
+                "understand      well",
+                "enough to explain to",
+                "a  computer.  Art is",
+                "everything  else  we",
+                "do                  ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_justify_text_single_word_lines() {
+        assert_eq!(
+            justified("tremendous ok", 10),
+            ["tremendous", "ok        "]
+        );
+        assert_eq!(justified("overlong", 4), ["overlong"]);
+    }
+
+    #[test]
+    fn test_to_fixed_char_len_identity() {
+        let s = Str::new("世界abc");
+        for align in [Alignment::Left, Alignment::Right, Alignment::Center] {
+            for truncate_end in [true, false] {
+                let fixed = s.to_fixed_char_len(5, '-', align, truncate_end);
+                assert_eq!(fixed, s);
+            }
+        }
+        let empty =
+            Str::new("").to_fixed_char_len(0, '-', Alignment::Left, true);
+        assert_eq!(empty.as_ref(), "");
+    }
+
+    #[test]
+    fn test_to_fixed_char_len_padded() {
+        let fixed = |align| {
+            Str::new("ab")
+                .to_fixed_char_len(7, '·', align, true)
+                .to_string()
+        };
+        assert_eq!(fixed(Alignment::Left), "ab·····");
+        assert_eq!(fixed(Alignment::Right), "·····ab");
+        assert_eq!(fixed(Alignment::Center), "··ab···");
+        let s = Str::new("").to_fixed_char_len(3, '0', Alignment::Right, false);
+        assert_eq!(s.as_ref(), "000");
+    }
+
+    #[test]
+    fn test_to_fixed_char_len_truncated() {
+        let s = Str::new("世界hello");
+        let fixed = |n, truncate_end| {
+            s.to_fixed_char_len(n, ' ', Alignment::Center, truncate_end)
+                .to_string()
+        };
+        assert_eq!(fixed(3, true), "世界h");
+        assert_eq!(fixed(3, false), "llo");
+        assert_eq!(fixed(6, false), "界hello");
+        assert_eq!(fixed(0, true), "");
+        assert_eq!(fixed(0, false), "");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-width")]
+    fn test_pad_to_width() {
+        let aligns = [Alignment::Left, Alignment::Right, Alignment::Center];
+        for input in ["世界", "ascii", "", "한국어 text"] {
+            for align in aligns {
+                for width in [0, 5, 12] {
+                    let padded =
+                        Str::new(input).pad_to_width(width, '-', align);
+                    let expected = width.max(input.width());
+                    assert_eq!(padded.display_width(), expected, "{:?}", align);
+                    assert!(padded.as_ref().contains(input));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-width")]
+    fn test_pad_to_width_alignment() {
+        let pad =
+            |s, align| Str::new(s).pad_to_width(8, '*', align).to_string();
+        assert_eq!(pad("世界", Alignment::Left), "世界****");
+        assert_eq!(pad("世界", Alignment::Right), "****世界");
+        assert_eq!(pad("世界", Alignment::Center), "**世界**");
+        assert_eq!(pad("abc", Alignment::Center), "**abc***");
+        assert_eq!(pad("", Alignment::Left), "********");
+        assert_eq!(pad("too long text", Alignment::Right), "too long text");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-width")]
+    fn test_pad_to_width_wide_fill() {
+        let s = Str::new("a");
+        assert_eq!(s.pad_to_width(4, '世', Alignment::Left).as_ref(), "a世 ");
+        assert_eq!(
+            s.pad_to_width(3, '\u{301}', Alignment::Right).as_ref(),
+            "  a"
+        );
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    fn wrapped(s: &str, width: usize) -> Vec<String> {
+        Str::new(s)
+            .wrap_graphemes(width)
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn test_wrap_graphemes_words() {
+        assert_eq!(
+            wrapped("the quick  brown fox", 10),
+            ["the quick", "brown fox"]
+        );
+        assert_eq!(wrapped("ab cd", 5), ["ab cd"]);
+        assert!(wrapped("  ", 3).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn test_wrap_graphemes_emoji() {
+        let family = "👨\u{200d}👩\u{200d}👧";
+        let flag = "🇩🇪";
+        let text = format!("{}{} {}{}{}", family, flag, flag, flag, flag);
+        assert_eq!(
+            wrapped(&text, 2),
+            [
+                format!("{}{}", family, flag),
+                format!("{}{}", flag, flag),
+                flag.into()
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn test_wrap_graphemes_combining() {
+        assert_eq!(
+            wrapped("e\u{301}e\u{301}e\u{301} x", 3),
+            ["e\u{301}e\u{301}e\u{301}", "x"]
+        );
+        assert_eq!(
+            wrapped("a\u{308}bc\u{327}d", 2),
+            ["a\u{308}b", "c\u{327}d"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn test_wrap_graphemes_no_spaces() {
+        assert_eq!(wrapped("abcdefgh", 3), ["abc", "def", "gh"]);
+        assert_eq!(wrapped("ab abcdefgh", 3), ["ab", "abc", "def", "gh"]);
+    }
+
+    #[test]
+    fn test_justify_text_exact_fit() {
+        assert_eq!(justified("ab cd ef", 5), ["ab cd", "ef   "]);
+        assert_eq!(justified("ab cd", 5), ["ab cd"]);
+        assert!(justified("   ", 5).is_empty());
+    }
+
+    #[test]
+    fn test_wrap_with_indent() {
+        let s = Str::new(
+            "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do \
+             eiusmod tempor incididunt ut labore.",
+        );
+        let wrapped = s.wrap_with_indent(24, "  ", "> ");
+        assert_eq!(
+            wrapped.as_ref(),
+            "> Lorem ipsum dolor sit\n  amet, consectetur\n  \
+             adipiscing elit, sed\n  do eiusmod tempor\n  \
+             incididunt ut labore."
+        );
+
+        let lines: Vec<&str> = wrapped.as_ref().lines().collect();
+        assert!(lines[0].starts_with("> "));
+        assert!(lines[0][2..].chars().count() <= 24 - "> ".len());
+        for line in &lines[1..] {
+            assert!(line.starts_with("  ") && !line[2..].starts_with(' '));
+            assert!(line.chars().count() <= 24);
+        }
+    }
+
+    #[test]
+    fn test_wrap_with_indent_long_word() {
+        let s = Str::new("see abcdefghijklmnop ok");
+        assert_eq!(
+            s.wrap_with_indent(8, "  ", "* ").as_ref(),
+            "* see\n  abcdef\n  ghijkl\n  mnop\n  ok"
+        );
+        assert_eq!(
+            Str::new("日本語のテキスト")
+                .wrap_with_indent(5, "", "")
+                .as_ref(),
+            "日本語のテ\nキスト"
+        );
+    }
+
+    #[test]
+    fn test_wrap_with_indent_edge_cases() {
+        assert_eq!(Str::new("").wrap_with_indent(10, "  ", "> ").as_ref(), "");
+        assert_eq!(Str::new(" \n ").wrap_with_indent(10, "", "").as_ref(), "");
+        assert_eq!(
+            Str::new("ab cd").wrap_with_indent(2, "....", "..").as_ref(),
+            "..a\n....b\n....c\n....d"
+        );
+        assert_eq!(
+            Str::new("one   two\nthree")
+                .wrap_with_indent(80, "", "")
+                .as_ref(),
+            "one two three"
+        );
+    }
+
+    #[test]
+    fn test_center() {
+        let center = |s: &str, width| Str::new(s).center(width).to_string();
+        assert_eq!(center("ab", 6), "  ab  ");
+        assert_eq!(center("ab", 5), " ab  ");
+        assert_eq!(center("abc", 4), "abc ");
+        assert_eq!(center("abc", 7), "  abc  ");
+        assert_eq!(center("", 3), "   ");
+        assert_eq!(center("abc", 3), "abc");
+        assert_eq!(center("abcdef", 2), "abcdef");
+    }
+
+    #[test]
+    fn test_center_with_multibyte() {
+        let s = Str::new("世界");
+        assert_eq!(s.center_with(6, '*').as_ref(), "**世界**");
+        assert_eq!(s.center_with(5, '·').as_ref(), "·世界··");
+        assert_eq!(Str::new("été").center_with(4, '─').as_ref(), "été─");
+        assert_eq!(s.center_with(2, '*'), s);
+    }
+
+    #[test]
+    fn test_format_bullet_list() {
+        assert_eq!(Str::format_bullet_list(&[], "- ", "").as_ref(), "");
+        assert_eq!(
+            Str::format_bullet_list(&["one"], "* ", "").as_ref(),
+            "* one"
+        );
+
+        let items = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight",
+            "nine", "ten",
+        ];
+        assert_eq!(
+            Str::format_bullet_list(&items, "• ", "  ").as_ref(),
+            "  • one\n  • two\n  • three\n  • four\n  • five\n  • six\n\
+             \x20 • seven\n  • eight\n  • nine\n  • ten"
+        );
+    }
+
+    #[test]
+    fn test_format_bullet_list_multi_line() {
+        let items = ["first\nsecond line", "alone", "para\n\nnext", ""];
+        assert_eq!(
+            Str::format_bullet_list(&items, "-> ", "\t").as_ref(),
+            "\t-> first\n\t   second line\n\t-> alone\n\t-> para\n\n\t   next\n\t-> "
+        );
+        assert_eq!(
+            Str::format_bullet_list(&["世界\nok"], "世 ", "").as_ref(),
+            "世 世界\n   ok"
+        );
+    }
+
+    #[test]
+    fn test_format_numbered_list() {
+        assert_eq!(Str::format_numbered_list(&[], "  ").as_ref(), "");
+        assert_eq!(Str::format_numbered_list(&["one"], "").as_ref(), "1. one");
+
+        let items = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight",
+            "nine", "ten",
+        ];
+        assert_eq!(
+            Str::format_numbered_list(&items, "").as_ref(),
+            " 1. one\n 2. two\n 3. three\n 4. four\n 5. five\n 6. six\n\
+             \x207. seven\n 8. eight\n 9. nine\n10. ten"
+        );
+        assert_eq!(
+            Str::format_numbered_list(&["a\nb", "c"], "  ").as_ref(),
+            "  1. a\n     b\n  2. c"
+        );
+    }
+
+    #[cfg(not(feature = "unicode-width"))]
+    #[test]
+    fn test_char_width_fallback() {
+        assert_eq!(display_width("a"), 1);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("世界"), 4);
+        assert_eq!(display_width("😀"), 2);
+        assert_eq!(display_width("🚀"), 2);
+        assert_eq!(display_width("🛸"), 2);
+        assert_eq!(display_width("🤖"), 2);
+        assert_eq!(display_width("🫠"), 2);
+        assert_eq!(display_width("🪐"), 2);
+
+        let table = Str::render_table(&["id", "icon"], &[vec!["1", "🚀"]]);
+        assert_eq!(
+            table.as_ref(),
+            "| id | icon |\n|----|------|\n| 1  | 🚀   |"
+        );
+    }
+}
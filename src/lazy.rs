@@ -0,0 +1,83 @@
+use crate::Str;
+
+/// A borrowed [`Str`] that is only copied when an owned value is needed.
+///
+/// Returned by [`Str::lazy_clone`]. Reading through it never allocates;
+/// [`LazyClone::into_str`] performs the actual clone.
+#[derive(Debug, Clone, Copy)]
+pub struct LazyClone<'a> {
+    source: &'a Str,
+}
+
+impl<'a> LazyClone<'a> {
+    /// Returns the string this value would clone.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.source.as_ref()
+    }
+
+    /// Clones the borrowed string into an owned [`Str`].
+    #[inline]
+    pub fn into_str(self) -> Str {
+        self.source.clone()
+    }
+}
+
+impl AsRef<str> for LazyClone<'_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<Str> for LazyClone<'_> {
+    #[inline]
+    fn eq(&self, other: &Str) -> bool {
+        self.as_str() == other.as_ref()
+    }
+}
+
+impl From<LazyClone<'_>> for Str {
+    #[inline]
+    fn from(lazy: LazyClone<'_>) -> Self {
+        lazy.into_str()
+    }
+}
+
+impl Str {
+    /// Returns a handle that clones the string only when
+    /// [`LazyClone::into_str`] is called.
+    ///
+    /// This is useful in pipelines that pass a string along but only
+    /// sometimes need to own it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("config");
+    /// let lazy = s.lazy_clone();
+    /// assert_eq!(lazy.as_str(), "config");
+    /// assert_eq!(lazy.into_str(), s);
+    /// ```
+    #[inline]
+    pub fn lazy_clone(&self) -> LazyClone<'_> {
+        LazyClone { source: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lazy_clone_into_str() {
+        let s = Str::new("Hello, 世界!");
+        let cloned = s.lazy_clone().into_str();
+        assert_eq!(cloned, s);
+        assert_ne!(cloned.as_ref().as_ptr(), s.as_ref().as_ptr());
+
+        let lazy = s.lazy_clone();
+        assert_eq!(lazy, s);
+        assert_eq!(Str::from(lazy), s);
+    }
+}
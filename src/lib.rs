@@ -1,6 +1,45 @@
 use std::borrow::Borrow;
 use std::hash::Hash;
 
+mod alphabet;
+mod analysis;
+mod case;
+mod chars;
+mod compare;
+mod compress;
+mod encoding;
+mod format;
+mod iter;
+mod layout;
+mod lazy;
+mod literal;
+mod markup;
+mod metaphone;
+mod parse;
+mod patch;
+mod phonetic;
+mod search;
+mod semver;
+mod stats;
+mod template;
+mod text;
+mod words;
+
+pub use alphabet::{BrailleError, MorseError, NatoError};
+pub use analysis::{
+    BaseEncoding, EncodingHint, RuneCategory, ScriptHint, UnicodeCategory,
+};
+pub use compress::{DecompressError, RleError};
+pub use encoding::{Base32Error, BcdError, DecodeError, UriDecodeError};
+pub use format::{BaseError, HexColorError};
+pub use layout::Alignment;
+pub use lazy::LazyClone;
+pub use parse::CsvError;
+pub use patch::PatchError;
+pub use search::AhoCorasick;
+pub use semver::{SemVer, SemVerError};
+pub use template::{EnvInterpolationError, TemplateError, TemplateFileError};
+
 /// A compact, owned string type that's optimized for size and performance.
 ///
 /// # Safety
@@ -95,6 +134,60 @@ impl Str {
     pub fn is_empty(&self) -> bool {
         self.size == 0
     }
+
+    /// Returns the number of Unicode scalar values in the string.
+    ///
+    /// Unlike [`Str::len`] this is O(n).
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        self.as_ref().chars().count()
+    }
+
+    /// Returns the length of the longest prefix of at most `max_len` bytes
+    /// that ends on a char boundary.
+    fn truncated_len(&self, max_len: usize) -> usize {
+        let s: &str = self.as_ref();
+        if max_len >= s.len() {
+            return s.len();
+        }
+        let mut len = max_len;
+        while !s.is_char_boundary(len) {
+            len -= 1;
+        }
+        len
+    }
+
+    /// Converts the string into its bytes, keeping at most `max_len` of them.
+    ///
+    /// The cut is moved back to the nearest char boundary, so the bytes are
+    /// always valid UTF-8. If nothing is cut the allocation is reused
+    /// without copying.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("héllo").into_bytes_truncated(2), b"h");
+    /// assert_eq!(Str::new("héllo").into_bytes_truncated(3), "hé".as_bytes());
+    /// ```
+    pub fn into_bytes_truncated(self, max_len: usize) -> Vec<u8> {
+        let len = self.truncated_len(max_len);
+        if len < self.size || self.size == 0 {
+            return self.as_ref().as_bytes()[..len].to_vec();
+        }
+
+        let this = std::mem::ManuallyDrop::new(self);
+        // The buffer was allocated by the global allocator with the layout
+        // of a `[u8; size]`, which is exactly what `Vec<u8>` expects.
+        unsafe {
+            Vec::from_raw_parts(this.data as *mut u8, this.size, this.size)
+        }
+    }
+
+    /// Copies at most `max_len` bytes of the string, moving the cut back to
+    /// the nearest char boundary like [`Str::into_bytes_truncated`].
+    pub fn copy_bytes_truncated(&self, max_len: usize) -> Vec<u8> {
+        self.as_ref().as_bytes()[..self.truncated_len(max_len)].to_vec()
+    }
 }
 
 impl Drop for Str {
@@ -267,6 +360,17 @@ mod tests {
         assert_eq!(s.as_ref(), "Hello, 世界!");
     }
 
+    #[test]
+    fn test_char_count() {
+        assert_eq!(Str::new("").char_count(), 0);
+        assert_eq!(Str::new("hello").char_count(), 5);
+
+        let s = Str::new("Hello, 世界!");
+        assert_eq!(s.len(), 14);
+        assert_eq!(s.char_count(), 10);
+        assert_eq!(Str::new("🎉e\u{301}").char_count(), 3);
+    }
+
     #[test]
     fn test_drop() {
         {
@@ -296,6 +400,40 @@ mod tests {
         assert_ne!(s3.data, s4.data);
     }
 
+    #[test]
+    fn test_into_bytes_truncated_reuses_allocation() {
+        let s = Str::new("hello");
+        let data = s.data;
+        let bytes = s.into_bytes_truncated(10);
+        assert_eq!(bytes, b"hello");
+        assert_eq!(bytes.as_ptr(), data);
+
+        let s = Str::new("hello");
+        let data = s.data;
+        let bytes = s.into_bytes_truncated(5);
+        assert_eq!(bytes.as_ptr(), data);
+        assert!(Str::new("").into_bytes_truncated(3).is_empty());
+    }
+
+    #[test]
+    fn test_bytes_truncated_char_boundary() {
+        let input = "a世🎉";
+        for max_len in 0..=10 {
+            let expected: &[u8] = match max_len {
+                0 => b"",
+                1..=3 => b"a",
+                4..=7 => "a世".as_bytes(),
+                _ => input.as_bytes(),
+            };
+            let s = Str::new(input);
+            let copied = s.copy_bytes_truncated(max_len);
+            assert_eq!(copied, expected, "{}", max_len);
+            assert!(copied.len() <= max_len);
+            assert!(std::str::from_utf8(&copied).is_ok());
+            assert_eq!(s.into_bytes_truncated(max_len), expected);
+        }
+    }
+
     #[test]
     fn test_from_str() {
         let s1: Str = "xxx".into();
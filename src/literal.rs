@@ -0,0 +1,116 @@
+use crate::Str;
+
+impl Str {
+    /// Formats the string as a Rust string literal that evaluates to the
+    /// string.
+    ///
+    /// Quotes, backslashes and the common control characters get their
+    /// short escapes (`\n`, `\t`, `\r`, `\0`). Every other control
+    /// character and non-ASCII character is written as a `\u{..}` escape,
+    /// so the literal itself is plain printable ASCII.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("say \"hé\"\n");
+    /// assert_eq!(s.to_rust_string_literal().as_ref(), r#""say \"h\u{e9}\"\n""#);
+    /// ```
+    pub fn to_rust_string_literal(&self) -> Str {
+        let mut out = String::with_capacity(self.len() + 2);
+        out.push('"');
+        for c in self.as_ref().chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\t' => out.push_str("\\t"),
+                '\r' => out.push_str("\\r"),
+                '\0' => out.push_str("\\0"),
+                ' '..='~' => out.push(c),
+                _ => out.extend(c.escape_unicode()),
+            }
+        }
+        out.push('"');
+        Str::new(&out)
+    }
+
+    /// Formats the string as a Rust raw string literal, such as
+    /// `r#"say "hi""#`, using the fewest `#` needed.
+    ///
+    /// Returns `None` if the string contains a carriage return, which Rust
+    /// does not allow in raw string literals.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new(r"C:\tmp").to_rust_raw_literal().unwrap().as_ref(), r#"r"C:\tmp""#);
+    /// assert_eq!(Str::new("\"#").to_rust_raw_literal().unwrap().as_ref(), r###"r##""#"##"###);
+    /// ```
+    pub fn to_rust_raw_literal(&self) -> Option<Str> {
+        let s: &str = self.as_ref();
+        if s.contains('\r') {
+            return None;
+        }
+
+        // The literal needs one more `#` than the longest run of them that
+        // follows a quote, since that run would otherwise close it early.
+        let hashes = s
+            .match_indices('"')
+            .map(|(at, _)| {
+                s[at + 1..].bytes().take_while(|&b| b == b'#').count() + 1
+            })
+            .max()
+            .unwrap_or(0);
+
+        let fence = "#".repeat(hashes);
+        Some(Str::new(&format!("r{}\"{}\"{}", fence, s, fence)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_rust_string_literal() {
+        let literal = |s| Str::new(s).to_rust_string_literal().to_string();
+        assert_eq!(literal(""), r#""""#);
+        assert_eq!(literal("plain"), r#""plain""#);
+        assert_eq!(literal("a\"b\\c"), r#""a\"b\\c""#);
+        assert_eq!(literal("\n\t\r\0"), r#""\n\t\r\0""#);
+        assert_eq!(literal("bell\x07"), r#""bell\u{7}""#);
+        assert_eq!(literal("{}'#"), r#""{}'#""#);
+    }
+
+    #[test]
+    fn test_to_rust_string_literal_unicode() {
+        for input in ["é", "世界", "🎉", "\u{7f}", "\u{feff}"] {
+            let literal = Str::new(input).to_rust_string_literal();
+            let escaped = input.escape_unicode().to_string();
+            assert_eq!(literal.as_ref(), format!("\"{}\"", escaped));
+            assert!(literal.as_ref().is_ascii());
+        }
+        assert_eq!(
+            Str::new("héllo").to_rust_string_literal().as_ref(),
+            "\"h\\u{e9}llo\""
+        );
+        assert_eq!("h\u{e9}llo", "héllo");
+    }
+
+    #[test]
+    fn test_to_rust_raw_literal() {
+        let raw = |s| Str::new(s).to_rust_raw_literal().unwrap().to_string();
+        assert_eq!(raw(""), r#"r"""#);
+        assert_eq!(raw(r"\d+\n"), r#"r"\d+\n""#);
+        assert_eq!(raw("say \"hi\""), r##"r#"say "hi""#"##);
+        assert_eq!(raw("#\""), r##"r#"#""#"##);
+        assert_eq!(raw("\"##"), r####"r###""##"###"####);
+        assert_eq!(raw("a\"#b\"###c"), r#####"r####"a"#b"###c"####"#####);
+        assert_eq!(raw("multi\nline 世界"), "r\"multi\nline 世界\"");
+    }
+
+    #[test]
+    fn test_to_rust_raw_literal_carriage_return() {
+        assert_eq!(Str::new("a\r\nb").to_rust_raw_literal(), None);
+    }
+}
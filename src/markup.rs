@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+
+use crate::Str;
+
+fn push_xml_escaped(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+/// Longest entity, including its `;`, that [`decode_html_entity`] looks for.
+const MAX_ENTITY_LEN: usize = 32;
+
+/// Decodes the HTML entity at the start of `s`, which follows an `&`,
+/// returning the character and the length of the entity including its `;`.
+fn decode_html_entity(s: &str) -> Option<(char, usize)> {
+    let end = s.bytes().take(MAX_ENTITY_LEN).position(|b| b == b';')?;
+    let name = &s[..end];
+    let c = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        _ => {
+            let code = name.strip_prefix('#')?;
+            let value = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(value)?
+        }
+    };
+    Some((c, end + 1))
+}
+
+/// Returns the anchor GitHub generates for a Markdown heading: the text
+/// lower cased, with spaces turned into `-` and punctuation other than `-`
+/// and `_` removed.
+fn heading_anchor(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            _ if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+impl Str {
+    /// Escapes `&`, `<`, `>`, `"` and `'` as XML entities, so the result can
+    /// be used both as element text and as an attribute value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("a < b & \"c\"");
+    /// assert_eq!(s.escape_xml().as_ref(), "a &lt; b &amp; &quot;c&quot;");
+    /// ```
+    pub fn escape_xml(&self) -> Str {
+        let mut out = String::with_capacity(self.len());
+        push_xml_escaped(&mut out, self.as_ref());
+        Str::new(&out)
+    }
+
+    /// Wraps the string in an SSML `<speak>` document.
+    ///
+    /// The string is XML-escaped. If `rate` or `pitch` is given the content
+    /// is also wrapped in a `<prosody>` element with those attributes. To
+    /// wrap content that is already SSML markup use
+    /// [`Str::wrap_ssml_speak`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("Tom & Jerry");
+    /// assert_eq!(
+    ///     s.to_ssml_speak(Some("slow"), None).as_ref(),
+    ///     "<speak><prosody rate=\"slow\">Tom &amp; Jerry</prosody></speak>",
+    /// );
+    /// ```
+    pub fn to_ssml_speak(
+        &self,
+        rate: Option<&str>,
+        pitch: Option<&str>,
+    ) -> Str {
+        Self::wrap_ssml_speak(self.escape_xml().as_ref(), rate, pitch)
+    }
+
+    /// Wraps SSML `markup` in a `<speak>` document without escaping it.
+    ///
+    /// Attribute values are XML-escaped. This is the building block behind
+    /// [`Str::to_ssml_speak`] and is used to nest elements such as
+    /// [`Str::to_ssml_emphasis`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let inner = Str::new("now").to_ssml_emphasis("strong");
+    /// assert_eq!(
+    ///     Str::wrap_ssml_speak(inner.as_ref(), None, None).as_ref(),
+    ///     "<speak><emphasis level=\"strong\">now</emphasis></speak>",
+    /// );
+    /// ```
+    pub fn wrap_ssml_speak(
+        markup: &str,
+        rate: Option<&str>,
+        pitch: Option<&str>,
+    ) -> Str {
+        let mut out = String::with_capacity(markup.len() + 64);
+        out.push_str("<speak>");
+        let prosody = rate.is_some() || pitch.is_some();
+        if prosody {
+            out.push_str("<prosody");
+            for (name, value) in [("rate", rate), ("pitch", pitch)] {
+                if let Some(value) = value {
+                    out.push(' ');
+                    out.push_str(name);
+                    out.push_str("=\"");
+                    push_xml_escaped(&mut out, value);
+                    out.push('"');
+                }
+            }
+            out.push('>');
+        }
+        out.push_str(markup);
+        if prosody {
+            out.push_str("</prosody>");
+        }
+        out.push_str("</speak>");
+        Str::new(&out)
+    }
+
+    /// Wraps the XML-escaped string in an SSML `<emphasis>` element with the
+    /// given `level`, such as `"strong"`, `"moderate"` or `"reduced"`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("really");
+    /// assert_eq!(
+    ///     s.to_ssml_emphasis("strong").as_ref(),
+    ///     "<emphasis level=\"strong\">really</emphasis>",
+    /// );
+    /// ```
+    pub fn to_ssml_emphasis(&self, level: &str) -> Str {
+        let mut out = String::with_capacity(self.len() + 40);
+        out.push_str("<emphasis level=\"");
+        push_xml_escaped(&mut out, level);
+        out.push_str("\">");
+        push_xml_escaped(&mut out, self.as_ref());
+        out.push_str("</emphasis>");
+        Str::new(&out)
+    }
+
+    /// Renders a Markdown table of contents linking to `headings`, given as
+    /// `(level, text)` pairs with levels from 1 to 6.
+    ///
+    /// Each heading becomes a list item linking to the anchor GitHub and
+    /// most Markdown renderers generate for it, with `-1`, `-2` and so on
+    /// appended to repeated anchors. Items are indented by two spaces per
+    /// level below the highest level present. If `numbered` is `true` the
+    /// items are numbered within their level instead, indented by three
+    /// spaces per level so that they nest in an ordered list. Levels
+    /// outside of 1 to 6 are clamped to that range.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let headings = [(1, "Guide"), (2, "Getting started"), (2, "FAQ")];
+    /// assert_eq!(
+    ///     Str::render_toc(&headings, false).as_ref(),
+    ///     "- [Guide](#guide)\n  - [Getting started](#getting-started)\n  - [FAQ](#faq)",
+    /// );
+    /// ```
+    pub fn render_toc(headings: &[(u8, &str)], numbered: bool) -> Str {
+        let top = headings
+            .iter()
+            .map(|&(level, _)| level.clamp(1, 6))
+            .min()
+            .unwrap_or(1);
+
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let mut numbers = [0usize; 6];
+        let mut lines = Vec::with_capacity(headings.len());
+        for &(level, text) in headings {
+            let level = level.clamp(1, 6) as usize;
+            let depth = level - top as usize;
+
+            let mut anchor = heading_anchor(text);
+            let repeats = seen.entry(anchor.clone()).or_default();
+            if *repeats > 0 {
+                anchor = format!("{}-{}", anchor, repeats);
+            }
+            *repeats += 1;
+
+            let title = text.trim().replace('[', "\\[").replace(']', "\\]");
+            let line = if numbered {
+                numbers[level - 1] += 1;
+                numbers[level..].fill(0);
+                let indent = "   ".repeat(depth);
+                format!(
+                    "{}{}. [{}](#{})",
+                    indent,
+                    numbers[level - 1],
+                    title,
+                    anchor
+                )
+            } else {
+                let indent = "  ".repeat(depth);
+                format!("{}- [{}](#{})", indent, title, anchor)
+            };
+            lines.push(line);
+        }
+        Str::new(&lines.join("\n"))
+    }
+
+    /// Extracts the text of an HTML snippet.
+    ///
+    /// Every `<...>` tag is removed, the entities `&amp;`, `&lt;`, `&gt;`,
+    /// `&quot;`, `&apos;`, `&nbsp;` and numeric ones such as `&#39;` or
+    /// `&#x27;` are decoded, and runs of whitespace are collapsed into a
+    /// single space, trimming the ends. Unknown entities and a `<` without a
+    /// closing `>` are kept as they are. This is not an HTML parser: comments,
+    /// scripts and a `>` inside attribute values are not handled.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("<p>Tom &amp; <b>Jerry</b></p>");
+    /// assert_eq!(s.strip_html_tags().as_ref(), "Tom & Jerry");
+    /// ```
+    pub fn strip_html_tags(&self) -> Str {
+        let mut text = String::with_capacity(self.len());
+        let mut rest: &str = self.as_ref();
+        // Once a `<` has no closing `>`, no later one has either.
+        let mut unclosed = false;
+        while let Some(c) = rest.chars().next() {
+            let markup = match c {
+                '<' if !unclosed => {
+                    let tag = rest.find('>').map(|end| (None, end + 1));
+                    unclosed = tag.is_none();
+                    tag
+                }
+                '&' => decode_html_entity(&rest[1..])
+                    .map(|(decoded, len)| (Some(decoded), len + 1)),
+                _ => None,
+            };
+            let (decoded, len) = markup.unwrap_or((Some(c), c.len_utf8()));
+            text.extend(decoded);
+            rest = &rest[len..];
+        }
+
+        let words: Vec<&str> = text.split_whitespace().collect();
+        Str::new(&words.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_xml() {
+        let s = Str::new("<a href='x'>&amp;</a>");
+        assert_eq!(
+            s.escape_xml().as_ref(),
+            "&lt;a href=&apos;x&apos;&gt;&amp;amp;&lt;/a&gt;"
+        );
+        assert_eq!(Str::new("plain 世界").escape_xml().as_ref(), "plain 世界");
+    }
+
+    #[test]
+    fn test_to_ssml_speak_escapes_content() {
+        let s = Str::new("5 < 6 & \"7\" > 'x'");
+        assert_eq!(
+            s.to_ssml_speak(None, None).as_ref(),
+            "<speak>5 &lt; 6 &amp; &quot;7&quot; &gt; &apos;x&apos;</speak>"
+        );
+    }
+
+    #[test]
+    fn test_to_ssml_speak_prosody() {
+        let s = Str::new("hello");
+        assert_eq!(
+            s.to_ssml_speak(Some("fast"), Some("+2st")).as_ref(),
+            "<speak><prosody rate=\"fast\" pitch=\"+2st\">hello</prosody></speak>"
+        );
+        assert_eq!(
+            s.to_ssml_speak(None, Some("low")).as_ref(),
+            "<speak><prosody pitch=\"low\">hello</prosody></speak>"
+        );
+        assert_eq!(
+            s.to_ssml_speak(Some("\"x\""), None).as_ref(),
+            "<speak><prosody rate=\"&quot;x&quot;\">hello</prosody></speak>"
+        );
+    }
+
+    #[test]
+    fn test_to_ssml_emphasis() {
+        let s = Str::new("R&D");
+        assert_eq!(
+            s.to_ssml_emphasis("moderate").as_ref(),
+            "<emphasis level=\"moderate\">R&amp;D</emphasis>"
+        );
+    }
+
+    #[test]
+    fn test_ssml_nesting() {
+        let inner = Str::new("<now>").to_ssml_emphasis("strong");
+        let doc = Str::wrap_ssml_speak(inner.as_ref(), Some("slow"), None);
+        assert_eq!(
+            doc.as_ref(),
+            "<speak><prosody rate=\"slow\">\
+             <emphasis level=\"strong\">&lt;now&gt;</emphasis>\
+             </prosody></speak>"
+        );
+
+        // Passing markup through `to_ssml_speak` escapes it instead.
+        let escaped = inner.to_ssml_speak(None, None);
+        assert!(escaped.as_ref().starts_with("<speak>&lt;emphasis"));
+    }
+
+    #[test]
+    fn test_strip_html_tags() {
+        let strip = |s: &str| Str::new(s).strip_html_tags().to_string();
+        assert_eq!(strip("<b>bold</b>"), "bold");
+        assert_eq!(strip("see <a href=\"/docs?a=1\">link</a>."), "see link.");
+        assert_eq!(
+            strip("<div><p>a <i><b>nested</b></i></p></div>"),
+            "a nested"
+        );
+        assert_eq!(strip("<p>one</p>\n\n  <p>two</p>"), "one two");
+        assert_eq!(strip("<br/>"), "");
+    }
+
+    #[test]
+    fn test_strip_html_tags_plain_text() {
+        for text in ["plain text", "世界 🎉", "", "a > b"] {
+            assert_eq!(Str::new(text).strip_html_tags().as_ref(), text);
+        }
+        assert_eq!(Str::new("a < b").strip_html_tags().as_ref(), "a < b");
+    }
+
+    #[test]
+    fn test_strip_html_tags_entities() {
+        let strip = |s: &str| Str::new(s).strip_html_tags().to_string();
+        assert_eq!(strip("R&amp;D"), "R&D");
+        assert_eq!(strip("&lt;b&gt;"), "<b>");
+        assert_eq!(strip("&quot;hi&quot; &apos;x&apos;"), "\"hi\" 'x'");
+        assert_eq!(strip("a&nbsp;&nbsp;b"), "a b");
+        assert_eq!(strip("&#72;&#x69;&#X21;"), "Hi!");
+        assert_eq!(strip("&amp;amp;"), "&amp;");
+        assert_eq!(
+            strip("&copy; & &#xZZ; &#1114112;"),
+            "&copy; & &#xZZ; &#1114112;"
+        );
+
+        let padded = format!("&#{:0>30};", 65);
+        assert_eq!(strip(&padded), "A");
+        let too_long = format!("&#{:0>31};", 65);
+        assert_eq!(strip(&too_long), too_long);
+    }
+
+    #[test]
+    fn test_strip_html_tags_unclosed() {
+        let strip = |s: &str| Str::new(s).strip_html_tags().to_string();
+        assert_eq!(strip("a <b>c</b> < d <e"), "a c < d <e");
+        assert_eq!(strip("1 < 2 <i>ok</i>"), "1 ok");
+
+        let unclosed = "<".repeat(50_000);
+        assert_eq!(strip(&unclosed), unclosed);
+        let ampersands = "&".repeat(50_000);
+        assert_eq!(strip(&ampersands), ampersands);
+    }
+
+    #[test]
+    fn test_render_toc() {
+        let headings = [
+            (1, "Introduction"),
+            (2, "Getting Started"),
+            (3, "Install the `ostr` crate"),
+            (3, "First steps!"),
+            (2, "API: Str & friends"),
+            (1, "License"),
+        ];
+        assert_eq!(
+            Str::render_toc(&headings, false).as_ref(),
+            "- [Introduction](#introduction)\n\
+             \x20 - [Getting Started](#getting-started)\n\
+             \x20   - [Install the `ostr` crate](#install-the-ostr-crate)\n\
+             \x20   - [First steps!](#first-steps)\n\
+             \x20 - [API: Str & friends](#api-str--friends)\n\
+             - [License](#license)"
+        );
+    }
+
+    #[test]
+    fn test_render_toc_numbered() {
+        let headings = [
+            (2, "One"),
+            (3, "One A"),
+            (3, "One B"),
+            (2, "Two"),
+            (3, "Two A"),
+        ];
+        assert_eq!(
+            Str::render_toc(&headings, true).as_ref(),
+            "1. [One](#one)\n   1. [One A](#one-a)\n   2. [One B](#one-b)\n\
+             2. [Two](#two)\n   1. [Two A](#two-a)"
+        );
+    }
+
+    #[test]
+    fn test_render_toc_anchors() {
+        let headings = [
+            (1, "Usage"),
+            (2, "Examples"),
+            (1, "Tests"),
+            (2, "Examples"),
+            (2, "snake_case and Ünïcode 世界"),
+            (2, "[draft] notes"),
+        ];
+        let toc = Str::render_toc(&headings, false);
+        let lines: Vec<&str> = toc.as_ref().lines().collect();
+        assert_eq!(lines[1], "  - [Examples](#examples)");
+        assert_eq!(lines[3], "  - [Examples](#examples-1)");
+        assert_eq!(
+            lines[4],
+            "  - [snake_case and Ünïcode 世界](#snake_case-and-ünïcode-世界)"
+        );
+        assert_eq!(lines[5], "  - [\\[draft\\] notes](#draft-notes)");
+        assert_eq!(Str::render_toc(&[], false).as_ref(), "");
+    }
+}
@@ -0,0 +1,776 @@
+use crate::Str;
+
+/// The length Double Metaphone codes are cut to.
+const CODE_LEN: usize = 4;
+
+/// Word prefixes whose first letter is not pronounced.
+const SILENT_STARTS: [&str; 5] = ["GN", "KN", "PN", "WR", "PS"];
+
+/// Letters after an initial `G` that make it sound like `J` or `K`, as in
+/// "gesture" or "gift".
+const G_SOFT_STARTS: [&str; 11] = [
+    "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+];
+
+/// The primary and alternate codes being built, each cut to [`CODE_LEN`].
+#[derive(Default)]
+struct Codes {
+    primary: String,
+    alternate: String,
+}
+
+impl Codes {
+    fn is_complete(&self) -> bool {
+        self.primary.len() >= CODE_LEN && self.alternate.len() >= CODE_LEN
+    }
+
+    fn add(&mut self, primary: &str, alternate: &str) {
+        self.add_primary(primary);
+        self.add_alternate(alternate);
+    }
+
+    #[inline]
+    fn add_both(&mut self, code: &str) {
+        self.add(code, code);
+    }
+
+    fn add_primary(&mut self, code: &str) {
+        let room = CODE_LEN.saturating_sub(self.primary.len());
+        self.primary.extend(code.chars().take(room));
+    }
+
+    fn add_alternate(&mut self, code: &str) {
+        let room = CODE_LEN.saturating_sub(self.alternate.len());
+        self.alternate.extend(code.chars().take(room));
+    }
+}
+
+/// Double Metaphone encoder for one word, following Lawrence Philips'
+/// original implementation.
+///
+/// Positions are signed so that rules can look around the word. Like the
+/// original, the word reads as if it was followed by spaces, so patterns
+/// such as `"VAN "` also match at its end, and positions before its start
+/// match nothing.
+struct Encoder {
+    chars: Vec<char>,
+    last: isize,
+    slavo_germanic: bool,
+    codes: Codes,
+}
+
+impl Encoder {
+    fn new(word: &str) -> Encoder {
+        let chars: Vec<char> =
+            word.chars().flat_map(char::to_uppercase).collect();
+        let mut encoder = Encoder {
+            last: chars.len() as isize - 1,
+            chars,
+            slavo_germanic: false,
+            codes: Codes::default(),
+        };
+        encoder.slavo_germanic = encoder.contains("W")
+            || encoder.contains("K")
+            || encoder.contains("CZ")
+            || encoder.contains("WITZ");
+        encoder
+    }
+
+    /// Returns the character at `at`: a space after the end of the word and
+    /// `'\0'` before its start.
+    fn at(&self, at: isize) -> char {
+        match usize::try_from(at) {
+            Ok(at) => self.chars.get(at).copied().unwrap_or(' '),
+            Err(_) => '\0',
+        }
+    }
+
+    /// Returns `true` if one of `patterns` is found at `start`.
+    fn is_at(&self, start: isize, patterns: &[&str]) -> bool {
+        start >= 0
+            && patterns.iter().any(|pattern| {
+                (start..)
+                    .zip(pattern.chars())
+                    .all(|(at, p)| self.at(at) == p)
+            })
+    }
+
+    /// Returns `true` if `pattern` is found anywhere in the word.
+    fn contains(&self, pattern: &str) -> bool {
+        (0..self.chars.len() as isize).any(|at| self.is_at(at, &[pattern]))
+    }
+
+    fn is_vowel(&self, at: isize) -> bool {
+        matches!(self.at(at), 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    /// Returns `true` if the word looks Germanic: it starts with "VAN ",
+    /// "VON " or "SCH".
+    fn is_germanic(&self) -> bool {
+        self.is_at(0, &["VAN ", "VON ", "SCH"])
+    }
+
+    /// Adds `code` and skips the letter at `at`, and the next one too if it
+    /// is `twin`.
+    fn single(&mut self, at: isize, code: &str, twin: char) -> isize {
+        self.codes.add_both(code);
+        if self.at(at + 1) == twin {
+            at + 2
+        } else {
+            at + 1
+        }
+    }
+
+    fn encode(mut self) -> Codes {
+        let mut at = if self.is_at(0, &SILENT_STARTS) { 1 } else { 0 };
+        while at <= self.last && !self.codes.is_complete() {
+            at = match self.at(at) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if at == 0 {
+                        self.codes.add_both("A");
+                    }
+                    at + 1
+                }
+                'B' => self.single(at, "P", 'B'),
+                'Ç' => {
+                    self.codes.add_both("S");
+                    at + 1
+                }
+                'C' => self.c(at),
+                'D' => self.d(at),
+                'F' => self.single(at, "F", 'F'),
+                'G' => self.g(at),
+                'H' => self.h(at),
+                'J' => self.j(at),
+                'K' => self.single(at, "K", 'K'),
+                'L' => self.l(at),
+                'M' => self.m(at),
+                'N' => self.single(at, "N", 'N'),
+                'Ñ' => {
+                    self.codes.add_both("N");
+                    at + 1
+                }
+                'P' => self.p(at),
+                'Q' => self.single(at, "K", 'Q'),
+                'R' => self.r(at),
+                'S' => self.s(at),
+                'T' => self.t(at),
+                'V' => self.single(at, "F", 'V'),
+                'W' => self.w(at),
+                'X' => self.x(at),
+                'Z' => self.z(at),
+                _ => at + 1,
+            };
+        }
+        self.codes
+    }
+
+    fn c(&mut self, at: isize) -> isize {
+        // Various Germanic spellings, as in "bacher" and "macher".
+        if at > 1
+            && !self.is_vowel(at - 2)
+            && self.is_at(at - 1, &["ACH"])
+            && self.at(at + 2) != 'I'
+            && (self.at(at + 2) != 'E'
+                || self.is_at(at - 2, &["BACHER", "MACHER"]))
+        {
+            self.codes.add_both("K");
+            return at + 2;
+        }
+        if at == 0 && self.is_at(at, &["CAESAR"]) {
+            self.codes.add_both("S");
+            return at + 2;
+        }
+        // Italian "chianti".
+        if self.is_at(at, &["CHIA"]) {
+            self.codes.add_both("K");
+            return at + 2;
+        }
+        if self.is_at(at, &["CH"]) {
+            return self.ch(at);
+        }
+        // "Czerny", but not "Wicz".
+        if self.is_at(at, &["CZ"]) && !self.is_at(at - 2, &["WICZ"]) {
+            self.codes.add("S", "X");
+            return at + 2;
+        }
+        // "focaccia"
+        if self.is_at(at + 1, &["CIA"]) {
+            self.codes.add_both("X");
+            return at + 3;
+        }
+        // A double "C", but not in "McClellan".
+        if self.is_at(at, &["CC"]) && !(at == 1 && self.at(0) == 'M') {
+            // "bellocchio", but not "bacchus".
+            if self.is_at(at + 2, &["I", "E", "H"])
+                && !self.is_at(at + 2, &["HU"])
+            {
+                // "accident", "accede", "succeed"
+                if (at == 1 && self.at(at - 1) == 'A')
+                    || self.is_at(at - 1, &["UCCEE", "UCCES"])
+                {
+                    self.codes.add_both("KS");
+                } else {
+                    // "bacci", "bertucci"
+                    self.codes.add_both("X");
+                }
+                return at + 3;
+            }
+            // Pierce's rule.
+            self.codes.add_both("K");
+            return at + 2;
+        }
+        if self.is_at(at, &["CK", "CG", "CQ"]) {
+            self.codes.add_both("K");
+            return at + 2;
+        }
+        if self.is_at(at, &["CI", "CE", "CY"]) {
+            // Italian versus English.
+            if self.is_at(at, &["CIO", "CIE", "CIA"]) {
+                self.codes.add("S", "X");
+            } else {
+                self.codes.add_both("S");
+            }
+            return at + 2;
+        }
+
+        self.codes.add_both("K");
+        // "Mac Caffrey", "Mac Gregor"
+        if self.is_at(at + 1, &[" C", " Q", " G"]) {
+            at + 3
+        } else if self.is_at(at + 1, &["C", "K", "Q"])
+            && !self.is_at(at + 1, &["CE", "CI"])
+        {
+            at + 2
+        } else {
+            at + 1
+        }
+    }
+
+    fn ch(&mut self, at: isize) -> isize {
+        // "Michael"
+        if at > 0 && self.is_at(at, &["CHAE"]) {
+            self.codes.add("K", "X");
+            return at + 2;
+        }
+        // Greek roots such as "chemistry" and "chorus".
+        if at == 0
+            && (self.is_at(at + 1, &["HARAC", "HARIS"])
+                || self.is_at(at + 1, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.is_at(0, &["CHORE"])
+        {
+            self.codes.add_both("K");
+            return at + 2;
+        }
+        // Germanic, Greek, or otherwise a "kh" sound.
+        if self.is_germanic()
+            || self.is_at(at - 2, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.is_at(at + 2, &["T", "S"])
+            || ((at == 0 || self.is_at(at - 1, &["A", "O", "U", "E"]))
+                && self.is_at(
+                    at + 2,
+                    &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+                ))
+        {
+            self.codes.add_both("K");
+        } else if at > 0 {
+            if self.is_at(0, &["MC"]) {
+                // "McHugh"
+                self.codes.add_both("K");
+            } else {
+                self.codes.add("X", "K");
+            }
+        } else {
+            self.codes.add_both("X");
+        }
+        at + 2
+    }
+
+    fn d(&mut self, at: isize) -> isize {
+        if self.is_at(at, &["DG"]) {
+            // "edge"
+            if self.is_at(at + 2, &["I", "E", "Y"]) {
+                self.codes.add_both("J");
+                return at + 3;
+            }
+            // "Edgar"
+            self.codes.add_both("TK");
+            return at + 2;
+        }
+        if self.is_at(at, &["DT", "DD"]) {
+            self.codes.add_both("T");
+            return at + 2;
+        }
+        self.codes.add_both("T");
+        at + 1
+    }
+
+    fn g(&mut self, at: isize) -> isize {
+        if self.at(at + 1) == 'H' {
+            return self.gh(at);
+        }
+        if self.at(at + 1) == 'N' {
+            if at == 1 && self.is_vowel(0) && !self.slavo_germanic {
+                self.codes.add("KN", "N");
+            } else if !self.is_at(at + 2, &["EY"])
+                && self.at(at + 1) != 'Y'
+                && !self.slavo_germanic
+            {
+                // Not "cagney".
+                self.codes.add("N", "KN");
+            } else {
+                self.codes.add_both("KN");
+            }
+            return at + 2;
+        }
+        // "tagliaro"
+        if self.is_at(at + 1, &["LI"]) && !self.slavo_germanic {
+            self.codes.add("KL", "L");
+            return at + 2;
+        }
+        // "-ges-", "-gep-", "-gel-" and "-gie-" at the start.
+        if at == 0
+            && (self.at(at + 1) == 'Y' || self.is_at(at + 1, &G_SOFT_STARTS))
+        {
+            self.codes.add("K", "J");
+            return at + 2;
+        }
+        // "-ger-" and "-gy-".
+        if (self.is_at(at + 1, &["ER"]) || self.at(at + 1) == 'Y')
+            && !self.is_at(0, &["DANGER", "RANGER", "MANGER"])
+            && !self.is_at(at - 1, &["E", "I"])
+            && !self.is_at(at - 1, &["RGY", "OGY"])
+        {
+            self.codes.add("K", "J");
+            return at + 2;
+        }
+        // Italian "biaggi".
+        if self.is_at(at + 1, &["E", "I", "Y"])
+            || self.is_at(at - 1, &["AGGI", "OGGI"])
+        {
+            if self.is_germanic() || self.is_at(at + 1, &["ET"]) {
+                self.codes.add_both("K");
+            } else if self.is_at(at + 1, &["IER "]) {
+                self.codes.add_both("J");
+            } else {
+                self.codes.add("J", "K");
+            }
+            return at + 2;
+        }
+        self.single(at, "K", 'G')
+    }
+
+    fn gh(&mut self, at: isize) -> isize {
+        if at > 0 && !self.is_vowel(at - 1) {
+            self.codes.add_both("K");
+            return at + 2;
+        }
+        // "ghislane", "ghiradelli"
+        if at == 0 {
+            if self.at(at + 2) == 'I' {
+                self.codes.add_both("J");
+            } else {
+                self.codes.add_both("K");
+            }
+            return at + 2;
+        }
+        // Parker's rule, with some further refinements: "hugh", "bough".
+        if (at > 1 && self.is_at(at - 2, &["B", "H", "D"]))
+            || (at > 2 && self.is_at(at - 3, &["B", "H", "D"]))
+            || (at > 3 && self.is_at(at - 4, &["B", "H"]))
+        {
+            return at + 2;
+        }
+        // "laugh", "McLaughlin", "cough", "gough", "rough", "tough"
+        if at > 2
+            && self.at(at - 1) == 'U'
+            && self.is_at(at - 3, &["C", "G", "L", "R", "T"])
+        {
+            self.codes.add_both("F");
+        } else if at > 0 && self.at(at - 1) != 'I' {
+            self.codes.add_both("K");
+        }
+        at + 2
+    }
+
+    fn h(&mut self, at: isize) -> isize {
+        // Only kept at the start or between vowels, which also takes care
+        // of "HH".
+        if (at == 0 || self.is_vowel(at - 1)) && self.is_vowel(at + 1) {
+            self.codes.add_both("H");
+            return at + 2;
+        }
+        at + 1
+    }
+
+    fn j(&mut self, at: isize) -> isize {
+        // Obvious Spanish, "Jose", "San Jacinto".
+        if self.is_at(at, &["JOSE"]) || self.is_at(0, &["SAN "]) {
+            if (at == 0 && self.at(at + 4) == ' ') || self.is_at(0, &["SAN "]) {
+                self.codes.add_both("H");
+            } else {
+                self.codes.add("J", "H");
+            }
+            return at + 1;
+        }
+
+        if at == 0 {
+            // "Yankelovich", "Jankelowicz"
+            self.codes.add("J", "A");
+        } else if self.is_vowel(at - 1)
+            && !self.slavo_germanic
+            && matches!(self.at(at + 1), 'A' | 'O')
+        {
+            // Spanish pronunciation of "bajador".
+            self.codes.add("J", "H");
+        } else if at == self.last {
+            self.codes.add_primary("J");
+        } else if !self.is_at(at + 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.is_at(at - 1, &["S", "K", "L"])
+        {
+            self.codes.add_both("J");
+        }
+
+        if self.at(at + 1) == 'J' {
+            at + 2
+        } else {
+            at + 1
+        }
+    }
+
+    fn l(&mut self, at: isize) -> isize {
+        if self.at(at + 1) != 'L' {
+            self.codes.add_both("L");
+            return at + 1;
+        }
+        // Spanish "cabrillo", "gallegos".
+        let ends_spanish = self.is_at(self.last - 1, &["AS", "OS"])
+            || self.is_at(self.last, &["A", "O"]);
+        if (at == self.last - 2
+            && self.is_at(at - 1, &["ILLO", "ILLA", "ALLE"]))
+            || (ends_spanish && self.is_at(at - 1, &["ALLE"]))
+        {
+            self.codes.add_primary("L");
+        } else {
+            self.codes.add_both("L");
+        }
+        at + 2
+    }
+
+    fn m(&mut self, at: isize) -> isize {
+        self.codes.add_both("M");
+        // "dumb", "thumb"
+        let silent_b = self.is_at(at - 1, &["UMB"])
+            && (at + 1 == self.last || self.is_at(at + 2, &["ER"]));
+        if silent_b || self.at(at + 1) == 'M' {
+            at + 2
+        } else {
+            at + 1
+        }
+    }
+
+    fn p(&mut self, at: isize) -> isize {
+        if self.at(at + 1) == 'H' {
+            self.codes.add_both("F");
+            return at + 2;
+        }
+        // "campbell", "raspberry"
+        self.codes.add_both("P");
+        if self.is_at(at + 1, &["P", "B"]) {
+            at + 2
+        } else {
+            at + 1
+        }
+    }
+
+    fn r(&mut self, at: isize) -> isize {
+        // French "Rogier", but not "Hochmeier".
+        if at == self.last
+            && !self.slavo_germanic
+            && self.is_at(at - 2, &["IE"])
+            && !self.is_at(at - 4, &["ME", "MA"])
+        {
+            self.codes.add_alternate("R");
+        } else {
+            self.codes.add_both("R");
+        }
+        if self.at(at + 1) == 'R' {
+            at + 2
+        } else {
+            at + 1
+        }
+    }
+
+    fn s(&mut self, at: isize) -> isize {
+        // "island", "isle", "carlisle", "carlysle"
+        if self.is_at(at - 1, &["ISL", "YSL"]) {
+            return at + 1;
+        }
+        // "sugar-"
+        if at == 0 && self.is_at(at, &["SUGAR"]) {
+            self.codes.add("X", "S");
+            return at + 1;
+        }
+        if self.is_at(at, &["SH"]) {
+            // Germanic.
+            if self.is_at(at + 1, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.codes.add_both("S");
+            } else {
+                self.codes.add_both("X");
+            }
+            return at + 2;
+        }
+        // Italian and Armenian.
+        if self.is_at(at, &["SIO", "SIA"]) || self.is_at(at, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.codes.add_both("S");
+            } else {
+                self.codes.add("S", "X");
+            }
+            return at + 3;
+        }
+        // German and anglicisations, so that "smith" matches "schmidt" and
+        // "snider" matches "schneider". Also "-sz-" in Slavic languages,
+        // although in Hungarian it is pronounced "s".
+        if (at == 0 && self.is_at(at + 1, &["M", "N", "L", "W"]))
+            || self.is_at(at + 1, &["Z"])
+        {
+            self.codes.add("S", "X");
+            return if self.is_at(at + 1, &["Z"]) {
+                at + 2
+            } else {
+                at + 1
+            };
+        }
+        if self.is_at(at, &["SC"]) {
+            return self.sc(at);
+        }
+
+        // French "resnais", "artois".
+        if at == self.last && self.is_at(at - 2, &["AI", "OI"]) {
+            self.codes.add_alternate("S");
+        } else {
+            self.codes.add_both("S");
+        }
+        if self.is_at(at + 1, &["S", "Z"]) {
+            at + 2
+        } else {
+            at + 1
+        }
+    }
+
+    fn sc(&mut self, at: isize) -> isize {
+        // Schlesinger's rule.
+        if self.at(at + 2) == 'H' {
+            // Dutch origin, "school", "schooner".
+            if self.is_at(at + 3, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                // "schermerhorn", "schenker"
+                if self.is_at(at + 3, &["ER", "EN"]) {
+                    self.codes.add("X", "SK");
+                } else {
+                    self.codes.add_both("SK");
+                }
+            } else if at == 0 && !self.is_vowel(3) && self.at(3) != 'W' {
+                self.codes.add("X", "S");
+            } else {
+                self.codes.add_both("X");
+            }
+        } else if self.is_at(at + 2, &["I", "E", "Y"]) {
+            self.codes.add_both("S");
+        } else {
+            self.codes.add_both("SK");
+        }
+        at + 3
+    }
+
+    fn t(&mut self, at: isize) -> isize {
+        if self.is_at(at, &["TION"]) || self.is_at(at, &["TIA", "TCH"]) {
+            self.codes.add_both("X");
+            return at + 3;
+        }
+        if self.is_at(at, &["TH"]) || self.is_at(at, &["TTH"]) {
+            // "thomas", "thames" or Germanic.
+            if self.is_at(at + 2, &["OM", "AM"]) || self.is_germanic() {
+                self.codes.add_both("T");
+            } else {
+                self.codes.add("0", "T");
+            }
+            return at + 2;
+        }
+        self.codes.add_both("T");
+        if self.is_at(at + 1, &["T", "D"]) {
+            at + 2
+        } else {
+            at + 1
+        }
+    }
+
+    fn w(&mut self, at: isize) -> isize {
+        // Can also be in the middle of a word.
+        if self.is_at(at, &["WR"]) {
+            self.codes.add_both("R");
+            return at + 2;
+        }
+        if at == 0 && (self.is_vowel(at + 1) || self.is_at(at, &["WH"])) {
+            if self.is_vowel(at + 1) {
+                // "Wasserman" should match "Vasserman".
+                self.codes.add("A", "F");
+            } else {
+                // "Uomo" should match "Womo".
+                self.codes.add_both("A");
+            }
+        }
+        // "Arnow" should match "Arnoff".
+        if (at == self.last && self.is_vowel(at - 1))
+            || self.is_at(at - 1, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.is_at(0, &["SCH"])
+        {
+            self.codes.add_alternate("F");
+            return at + 1;
+        }
+        // Polish "filipowicz".
+        if self.is_at(at, &["WICZ", "WITZ"]) {
+            self.codes.add("TS", "FX");
+            return at + 4;
+        }
+        at + 1
+    }
+
+    fn x(&mut self, at: isize) -> isize {
+        if at == 0 {
+            self.codes.add_both("S");
+            return at + 1;
+        }
+        // French "breaux".
+        let silent = at == self.last
+            && (self.is_at(at - 3, &["IAU", "EAU"])
+                || self.is_at(at - 2, &["AU", "OU"]));
+        if !silent {
+            self.codes.add_both("KS");
+        }
+        if self.is_at(at + 1, &["C", "X"]) {
+            at + 2
+        } else {
+            at + 1
+        }
+    }
+
+    fn z(&mut self, at: isize) -> isize {
+        // Chinese pinyin, "Zhao".
+        if self.at(at + 1) == 'H' {
+            self.codes.add_both("J");
+            return at + 2;
+        }
+        if self.is_at(at + 1, &["ZO", "ZI", "ZA"])
+            || (self.slavo_germanic && at > 0 && self.at(at - 1) != 'T')
+        {
+            self.codes.add("S", "TS");
+        } else {
+            self.codes.add_both("S");
+        }
+        if self.at(at + 1) == 'Z' {
+            at + 2
+        } else {
+            at + 1
+        }
+    }
+}
+
+impl Str {
+    /// Returns the primary and alternate Double Metaphone codes of the
+    /// string.
+    ///
+    /// Double Metaphone maps words that sound alike to the same code of up
+    /// to four characters, with rules for English as well as for Germanic,
+    /// Slavic, Romance and other names as spelled in English. Many words
+    /// have an alternate pronunciation and therefore an alternate code,
+    /// which is `None` when it equals the primary one. The code uses `0`
+    /// for "th", and characters that are not letters are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let (primary, alternate) = Str::new("Schmidt").double_metaphone();
+    /// assert_eq!(primary.as_ref(), "XMT");
+    /// assert_eq!(alternate, Some(Str::new("SMT")));
+    ///
+    /// let (primary, alternate) = Str::new("Thompson").double_metaphone();
+    /// assert_eq!(primary.as_ref(), "TMPS");
+    /// assert_eq!(alternate, None);
+    /// ```
+    pub fn double_metaphone(&self) -> (Str, Option<Str>) {
+        let codes = Encoder::new(self.as_ref()).encode();
+        let alternate = (codes.alternate != codes.primary)
+            .then(|| Str::new(&codes.alternate));
+        (Str::new(&codes.primary), alternate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(words: &[(&str, &str, Option<&str>)]) {
+        for &(word, primary, alternate) in words {
+            assert_eq!(
+                Str::new(word).double_metaphone(),
+                (Str::new(primary), alternate.map(Str::new)),
+                "{}",
+                word
+            );
+        }
+    }
+
+    #[test]
+    fn test_double_metaphone_names() {
+        check(&[
+            ("Schmidt", "XMT", Some("SMT")),
+            ("Schneider", "XNTR", Some("SNTR")),
+            ("Smith", "SM0", Some("XMT")),
+            ("Smythe", "SM0", Some("XMT")),
+            ("Thompson", "TMPS", None),
+        ]);
+    }
+
+    #[test]
+    fn test_double_metaphone_rules() {
+        check(&[
+            ("Michael", "MKL", Some("MXL")),
+            ("Caesar", "SSR", None),
+            ("Czerny", "SRN", Some("XRN")),
+            ("Focaccia", "FKX", None),
+            ("Accident", "AKST", None),
+            ("Edge", "AJ", None),
+            ("Edgar", "ATKR", None),
+            ("Laugh", "LF", None),
+            ("Knight", "NT", None),
+            ("Tagliaro", "TKLR", Some("TLR")),
+            ("Jose", "HS", None),
+            ("Bajador", "PJTR", Some("PHTR")),
+            ("Raj", "RJ", Some("R")),
+            ("Cabrillo", "KPRL", Some("KPR")),
+            ("Sugar", "XKR", Some("SKR")),
+            ("Schermerhorn", "XRMR", Some("SKRM")),
+            ("Wasserman", "ASRM", Some("FSRM")),
+            ("Arnow", "ARN", Some("ARNF")),
+            ("Filipowicz", "FLPT", Some("FLPF")),
+            ("Xavier", "SF", Some("SFR")),
+            ("Zhao", "J", None),
+        ]);
+    }
+
+    #[test]
+    fn test_double_metaphone_case_and_padding() {
+        assert_eq!(
+            Str::new("SCHMIDT").double_metaphone(),
+            Str::new("schmidt").double_metaphone()
+        );
+        check(&[
+            ("", "", None),
+            ("123 -", "", None),
+            ("a", "A", None),
+            ("Ach", "AK", None),
+            ("Van Gogh", "FNKK", None),
+        ]);
+    }
+}
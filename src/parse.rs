@@ -0,0 +1,497 @@
+use crate::Str;
+
+/// Splits `s` on `sep`, ignoring separators inside double quotes.
+fn split_unquoted(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (at, c) in s.char_indices() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if c == sep && !quoted {
+            parts.push(&s[start..at]);
+            start = at + c.len_utf8();
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Returns the numbers in `s`, each with whether it has no fractional part,
+/// as described by [`Str::extract_numbers`].
+fn number_tokens(s: &str) -> Vec<(&str, bool)> {
+    let bytes = s.as_bytes();
+    let digits_from = |at: usize| {
+        at + bytes[at..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+
+    let mut tokens = Vec::new();
+    let mut at = 0;
+    while at < bytes.len() {
+        if !bytes[at].is_ascii_digit() {
+            at += 1;
+            continue;
+        }
+
+        let signed = at > 0
+            && bytes[at - 1] == b'-'
+            && !s[..at - 1]
+                .chars()
+                .next_back()
+                .is_some_and(char::is_alphanumeric);
+        let start = if signed { at - 1 } else { at };
+        at = digits_from(at);
+        let fraction = bytes.get(at) == Some(&b'.')
+            && bytes.get(at + 1).is_some_and(u8::is_ascii_digit);
+        if fraction {
+            at = digits_from(at + 1);
+        }
+        tokens.push((&s[start..at], !fraction));
+    }
+    tokens
+}
+
+/// Error returned by [`Str::parse_csv_row`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvError {
+    /// A quoted field is not closed before the end of the row.
+    UnterminatedQuote,
+    /// A `"` appears inside an unquoted field at the given byte offset.
+    UnexpectedQuote(usize),
+    /// A character other than the delimiter follows a closing quote at the
+    /// given byte offset.
+    UnexpectedChar(usize),
+}
+
+impl std::fmt::Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnterminatedQuote => f.write_str("unterminated quoted field"),
+            Self::UnexpectedQuote(at) => {
+                write!(f, "unexpected quote in unquoted field at byte {}", at)
+            }
+            Self::UnexpectedChar(at) => {
+                write!(
+                    f,
+                    "unexpected character after closing quote at byte {}",
+                    at
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl Str {
+    /// Splits a comma separated HTTP header value into its trimmed elements.
+    ///
+    /// Empty elements are skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("gzip, deflate ,br");
+    /// assert_eq!(s.split_http_header_value(), ["gzip", "deflate", "br"]);
+    /// ```
+    pub fn split_http_header_value(&self) -> Vec<&str> {
+        self.as_ref()
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .collect()
+    }
+
+    /// Formats the string as an RFC 4180 CSV field.
+    ///
+    /// The field is wrapped in double quotes, with inner quotes doubled, if
+    /// it contains `delimiter`, `"`, `\n` or `\r`. Otherwise it is returned
+    /// unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("plain").to_csv_field(',').as_ref(), "plain");
+    /// assert_eq!(Str::new("a,\"b\"").to_csv_field(',').as_ref(), "\"a,\"\"b\"\"\"");
+    /// ```
+    pub fn to_csv_field(&self, delimiter: char) -> Str {
+        let s: &str = self.as_ref();
+        let quote = s
+            .chars()
+            .any(|c| c == delimiter || matches!(c, '"' | '\n' | '\r'));
+        if !quote {
+            return self.clone();
+        }
+
+        let mut out = String::with_capacity(s.len() + 2);
+        out.push('"');
+        out.push_str(&s.replace('"', "\"\""));
+        out.push('"');
+        Str::new(&out)
+    }
+
+    /// Parses the string as a single RFC 4180 CSV row.
+    ///
+    /// Quoted fields may contain the delimiter, line breaks and doubled
+    /// quotes. One trailing `\n` or `\r\n` is ignored. An empty string is a
+    /// row with a single empty field.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let row = Str::new("1,\"Smith, J\",\"say \"\"hi\"\"\"\r\n");
+    /// assert_eq!(
+    ///     row.parse_csv_row(',').unwrap(),
+    ///     [Str::new("1"), Str::new("Smith, J"), Str::new("say \"hi\"")],
+    /// );
+    /// ```
+    pub fn parse_csv_row(&self, delimiter: char) -> Result<Vec<Str>, CsvError> {
+        let s: &str = self.as_ref();
+        let s = s
+            .strip_suffix("\r\n")
+            .or_else(|| s.strip_suffix('\n'))
+            .unwrap_or(s);
+
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut chars = s.char_indices().peekable();
+        let mut quoted = false;
+        let mut closed = false;
+        while let Some((at, c)) = chars.next() {
+            if quoted {
+                if c != '"' {
+                    field.push(c);
+                } else if chars.next_if(|&(_, next)| next == '"').is_some() {
+                    field.push('"');
+                } else {
+                    quoted = false;
+                    closed = true;
+                }
+            } else if c == delimiter {
+                fields.push(Str::new(&field));
+                field.clear();
+                closed = false;
+            } else if closed {
+                return Err(CsvError::UnexpectedChar(at));
+            } else if c == '"' {
+                if !field.is_empty() {
+                    return Err(CsvError::UnexpectedQuote(at));
+                }
+                quoted = true;
+            } else {
+                field.push(c);
+            }
+        }
+        if quoted {
+            return Err(CsvError::UnterminatedQuote);
+        }
+        fields.push(Str::new(&field));
+        Ok(fields)
+    }
+
+    /// Removes insignificant whitespace from a JSON document.
+    ///
+    /// This is a single pass over the characters that drops JSON whitespace
+    /// (space, `\t`, `\n` and `\r`) outside of string literals, honoring
+    /// `\"` escapes. It does not validate the document: if a string literal
+    /// is left unterminated the input is returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("{ \"key\" : [1, 2] }");
+    /// assert_eq!(s.compact_json().as_ref(), "{\"key\":[1,2]}");
+    /// ```
+    pub fn compact_json(&self) -> Str {
+        let s: &str = self.as_ref();
+        let mut out = String::with_capacity(s.len());
+        let mut in_string = false;
+        let mut escaped = false;
+        for c in s.chars() {
+            if in_string {
+                out.push(c);
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    in_string = false;
+                }
+            } else if c == '"' {
+                out.push(c);
+                in_string = true;
+            } else if !matches!(c, ' ' | '\t' | '\n' | '\r') {
+                out.push(c);
+            }
+        }
+        if in_string {
+            return self.clone();
+        }
+        Str::new(&out)
+    }
+
+    /// Parses a `Content-Type` style header value into the media type and
+    /// its `name=value` parameters.
+    ///
+    /// Whitespace around names and values is trimmed and a quoted value has
+    /// its surrounding quotes removed; separators inside quotes are kept as
+    /// part of the value. Parameters without `=` or with an
+    /// empty name are skipped. Returns `None` if the media type is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("text/html; charset=utf-8");
+    /// let (media_type, params) = s.parse_content_type().unwrap();
+    /// assert_eq!(media_type, "text/html");
+    /// assert_eq!(params, [("charset", "utf-8")]);
+    /// ```
+    pub fn parse_content_type(&self) -> Option<(&str, Vec<(&str, &str)>)> {
+        let mut parts = split_unquoted(self.as_ref(), ';').into_iter();
+        let media_type = parts.next().map(str::trim).unwrap_or("");
+        if media_type.is_empty() {
+            return None;
+        }
+
+        let params = parts
+            .filter_map(|param| {
+                let (name, value) = param.split_once('=')?;
+                let name = name.trim();
+                if name.is_empty() {
+                    return None;
+                }
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(value);
+                Some((name, value))
+            })
+            .collect();
+        Some((media_type, params))
+    }
+
+    /// Returns the numbers written in the string, in order.
+    ///
+    /// A number is a run of ASCII digits, optionally followed by a `.` and
+    /// more digits. A `-` right before it makes it negative, unless the `-`
+    /// itself follows a letter or digit, as in `3-4`. Anything else, such as
+    /// surrounding punctuation, separates numbers, so `"1,000"` holds `1`
+    /// and `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("I have 3 cats and 1.5 kg of food");
+    /// assert_eq!(s.extract_numbers(), [3.0, 1.5]);
+    /// ```
+    pub fn extract_numbers(&self) -> Vec<f64> {
+        number_tokens(self.as_ref())
+            .into_iter()
+            .filter_map(|(token, _)| token.parse().ok())
+            .collect()
+    }
+
+    /// Returns the integers written in the string, in order.
+    ///
+    /// Numbers are found as described by [`Str::extract_numbers`]. Those
+    /// with a fractional part, and those that do not fit in an `i64`, are
+    /// skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("Move -3 steps, then 1.5 more, 10 times.");
+    /// assert_eq!(s.extract_integers(), [-3, 10]);
+    /// ```
+    pub fn extract_integers(&self) -> Vec<i64> {
+        number_tokens(self.as_ref())
+            .into_iter()
+            .filter(|&(_, integer)| integer)
+            .filter_map(|(token, _)| token.parse().ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_http_header_value() {
+        let s = Str::new("gzip, deflate");
+        assert_eq!(s.split_http_header_value(), ["gzip", "deflate"]);
+
+        let s = Str::new(" en-US , en;q=0.9,, *;q=0.5 ");
+        assert_eq!(
+            s.split_http_header_value(),
+            ["en-US", "en;q=0.9", "*;q=0.5"]
+        );
+
+        assert!(Str::new(" , ").split_http_header_value().is_empty());
+    }
+
+    fn row(s: &str, delimiter: char) -> Vec<String> {
+        let fields = Str::new(s).parse_csv_row(delimiter).unwrap();
+        fields.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_to_csv_field() {
+        let field = |s: &str, d| Str::new(s).to_csv_field(d).to_string();
+        assert_eq!(field("clean", ','), "clean");
+        assert_eq!(field("", ','), "");
+        assert_eq!(field("a,b", ','), "\"a,b\"");
+        assert_eq!(field("a,b", ';'), "a,b");
+        assert_eq!(field("a;b", ';'), "\"a;b\"");
+        assert_eq!(field("say \"hi\"", ','), "\"say \"\"hi\"\"\"");
+        assert_eq!(field("two\nlines", ','), "\"two\nlines\"");
+        assert_eq!(field("cr\r", ','), "\"cr\r\"");
+    }
+
+    #[test]
+    fn test_parse_csv_row() {
+        assert_eq!(row("a,b,c", ','), ["a", "b", "c"]);
+        assert_eq!(row("a,\"b,c\",d\n", ','), ["a", "b,c", "d"]);
+        assert_eq!(row("\"a \"\"q\"\"\";x", ';'), ["a \"q\"", "x"]);
+        assert_eq!(row("\"multi\nline\",2\r\n", ','), ["multi\nline", "2"]);
+        assert_eq!(row(",,", ','), ["", "", ""]);
+        assert_eq!(row("\"\",", ','), ["", ""]);
+        assert_eq!(row("", ','), [""]);
+        assert_eq!(row("世界\t🎉", '\t'), ["世界", "🎉"]);
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let values =
+            ["plain", "with,comma", "with \"quotes\"", "new\nline", ""];
+        let line: Vec<String> = values
+            .iter()
+            .map(|v| Str::new(v).to_csv_field(',').to_string())
+            .collect();
+        assert_eq!(row(&line.join(","), ','), values);
+    }
+
+    #[test]
+    fn test_parse_csv_row_malformed() {
+        let err = |s: &str| Str::new(s).parse_csv_row(',').unwrap_err();
+        assert_eq!(err("\"open,field"), CsvError::UnterminatedQuote);
+        assert_eq!(err("a,\"b\"c"), CsvError::UnexpectedChar(5));
+        assert_eq!(err("ab\"c\""), CsvError::UnexpectedQuote(2));
+    }
+
+    #[test]
+    fn test_compact_json() {
+        let compact = |s: &str| Str::new(s).compact_json().to_string();
+        assert_eq!(compact("{ \"key\" : \"value\" }"), "{\"key\":\"value\"}");
+        assert_eq!(
+            compact("{\n  \"a\": [1, 2,\t3],\r\n  \"b\": null\n}\n"),
+            "{\"a\":[1,2,3],\"b\":null}"
+        );
+        assert_eq!(compact("[]"), "[]");
+        assert_eq!(compact(""), "");
+    }
+
+    #[test]
+    fn test_compact_json_strings() {
+        let compact = |s: &str| Str::new(s).compact_json().to_string();
+        assert_eq!(
+            compact("{ \"a b\" : \" spaced  value \" }"),
+            "{\"a b\":\" spaced  value \"}"
+        );
+        assert_eq!(
+            compact("[ \"say \\\"hi there\\\"\" , 1 ]"),
+            "[\"say \\\"hi there\\\"\",1]"
+        );
+        assert_eq!(
+            compact("[ \"back\\\\\" , \" x\" ]"),
+            "[\"back\\\\\",\" x\"]"
+        );
+        assert_eq!(compact("{ \"世界\" : \"🎉 \" }"), "{\"世界\":\"🎉 \"}");
+    }
+
+    #[test]
+    fn test_compact_json_unterminated_string() {
+        let s = Str::new("{ \"key\" : \"value }");
+        assert_eq!(s.compact_json(), s);
+        let s = Str::new("[ \"escaped end\\\" ]");
+        assert_eq!(s.compact_json(), s);
+    }
+
+    #[test]
+    fn test_parse_content_type() {
+        let s = Str::new("text/html; charset=utf-8; boundary=something");
+        assert_eq!(
+            s.parse_content_type(),
+            Some((
+                "text/html",
+                vec![("charset", "utf-8"), ("boundary", "something")]
+            ))
+        );
+
+        let s = Str::new("application/json");
+        assert_eq!(s.parse_content_type(), Some(("application/json", vec![])));
+    }
+
+    #[test]
+    fn test_parse_content_type_quoted_value() {
+        let s = Str::new("multipart/form-data; boundary=\"a b;c\"");
+        assert_eq!(
+            s.parse_content_type(),
+            Some(("multipart/form-data", vec![("boundary", "a b;c")]))
+        );
+
+        let s = Str::new("text/plain; charset = \"utf-8\" ");
+        assert_eq!(
+            s.parse_content_type(),
+            Some(("text/plain", vec![("charset", "utf-8")]))
+        );
+    }
+
+    #[test]
+    fn test_parse_content_type_malformed() {
+        let s = Str::new("text/html; charset; =utf-8; q=1");
+        assert_eq!(
+            s.parse_content_type(),
+            Some(("text/html", vec![("q", "1")]))
+        );
+
+        assert_eq!(Str::new("").parse_content_type(), None);
+        assert_eq!(Str::new(" ; charset=utf-8").parse_content_type(), None);
+    }
+
+    #[test]
+    fn test_extract_numbers() {
+        let numbers = |s: &str| Str::new(s).extract_numbers();
+        assert_eq!(numbers("I have 3 cats and 1.5 kg of food"), [3.0, 1.5]);
+        assert_eq!(
+            numbers("It went from -4.5 to -10, then 0."),
+            [-4.5, -10.0, 0.0]
+        );
+        assert_eq!(numbers("no numbers here"), Vec::<f64>::new());
+        assert_eq!(numbers(""), Vec::<f64>::new());
+        assert_eq!(numbers("(42), $3.50; x=7! [8]"), [42.0, 3.5, 7.0, 8.0]);
+        assert_eq!(numbers("pages 3-4 and room-12"), [3.0, 4.0, 12.0]);
+        assert_eq!(numbers("v1.2.3 at 192.168.0.1"), [1.2, 3.0, 192.168, 0.1]);
+        assert_eq!(numbers("end 5. .5 - 6 --7"), [5.0, 5.0, 6.0, -7.0]);
+        assert_eq!(numbers("été 12世界"), [12.0]);
+    }
+
+    #[test]
+    fn test_extract_integers() {
+        let integers = |s: &str| Str::new(s).extract_integers();
+        assert_eq!(integers("I have 3 cats and 1.5 kg of food"), [3]);
+        assert_eq!(integers("from -4 to -10, via 0"), [-4, -10, 0]);
+        assert_eq!(integers("x:1,y:2;z:-3."), [1, 2, -3]);
+        assert_eq!(integers("nothing"), Vec::<i64>::new());
+        assert_eq!(
+            integers("max 9223372036854775807, min -9223372036854775808"),
+            [i64::MAX, i64::MIN]
+        );
+        assert_eq!(integers("too big 9223372036854775808 ok 1"), [1]);
+        assert_eq!(integers("007 agents"), [7]);
+    }
+}
@@ -0,0 +1,500 @@
+use crate::Str;
+
+/// Number of unchanged lines kept around every change in a patch.
+const CONTEXT: usize = 3;
+
+/// Error returned by [`Str::apply_patch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// The line of the patch with the given 1-based number is not valid, or
+    /// is missing because the patch ends early.
+    Malformed(usize),
+    /// The original differs from the patch at the line with the given
+    /// 1-based number, so the patch was not made for it.
+    Mismatch(usize),
+}
+
+impl std::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed(line) => {
+                write!(f, "malformed patch at line {}", line)
+            }
+            Self::Mismatch(line) => {
+                write!(f, "patch does not apply at line {}", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Keep,
+    Delete,
+    Insert,
+}
+
+impl Edit {
+    fn prefix(self) -> char {
+        match self {
+            Self::Keep => ' ',
+            Self::Delete => '-',
+            Self::Insert => '+',
+        }
+    }
+}
+
+/// Returns the lengths of the longest common subsequences of `a` and every
+/// prefix `b[..j]`, keeping a single row of the table.
+fn lcs_row(a: &[&str], b: &[&str]) -> Vec<usize> {
+    let mut row = vec![0usize; b.len() + 1];
+    for x in a {
+        // The previous line's value of row[j].
+        let mut diagonal = 0;
+        for (j, y) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if x == y {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row
+}
+
+/// Appends the edits turning `a` into `b` to `edits`, splitting the problem
+/// in half around a line of an optimal path (Hirschberg's algorithm), so
+/// that memory stays linear in the numbers of lines.
+fn push_edits(a: &[&str], b: &[&str], edits: &mut Vec<Edit>) {
+    if b.is_empty() {
+        edits.resize(edits.len() + a.len(), Edit::Delete);
+        return;
+    }
+    if a.len() <= 1 {
+        match a.first().and_then(|x| b.iter().position(|y| x == y)) {
+            Some(at) => {
+                edits.resize(edits.len() + at, Edit::Insert);
+                edits.push(Edit::Keep);
+                edits.resize(edits.len() + b.len() - at - 1, Edit::Insert);
+            }
+            None => {
+                edits.resize(edits.len() + a.len(), Edit::Delete);
+                edits.resize(edits.len() + b.len(), Edit::Insert);
+            }
+        }
+        return;
+    }
+
+    let mid = a.len() / 2;
+    let split = {
+        let head = lcs_row(&a[..mid], b);
+        let a_tail: Vec<&str> = a[mid..].iter().rev().copied().collect();
+        let b_tail: Vec<&str> = b.iter().rev().copied().collect();
+        let tail = lcs_row(&a_tail, &b_tail);
+        // Take the leftmost split, so that deletions come first.
+        (0..=b.len())
+            .rev()
+            .max_by_key(|&j| head[j] + tail[b.len() - j])
+            .unwrap_or(0)
+    };
+    push_edits(&a[..mid], &b[..split], edits);
+    push_edits(&a[mid..], &b[split..], edits);
+}
+
+/// Returns the edits turning the lines `a` into the lines `b`, based on
+/// their longest common subsequence. Deletions come before insertions
+/// where both are possible.
+fn line_edits(a: &[&str], b: &[&str]) -> Vec<Edit> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let mut edits = vec![Edit::Keep; prefix];
+    push_edits(
+        &a[prefix..a.len() - suffix],
+        &b[prefix..b.len() - suffix],
+        &mut edits,
+    );
+    edits.resize(edits.len() + suffix, Edit::Keep);
+    edits
+}
+
+/// Formats the start and length of a hunk's range in the unified diff
+/// convention, where an empty range starts at the line before it.
+fn hunk_range(start: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", start + 1, len)
+    }
+}
+
+/// Parses a `start,len` or `start` hunk range, returning the 0-based index
+/// of the first line after which the hunk applies and its length.
+fn parse_hunk_range(range: &str) -> Option<(usize, usize)> {
+    let (start, len) = match range.split_once(',') {
+        Some((start, len)) => (start.parse().ok()?, len.parse().ok()?),
+        None => (range.parse().ok()?, 1),
+    };
+    match len {
+        0 => Some((start, 0)),
+        _ => Some((usize::checked_sub(start, 1)?, len)),
+    }
+}
+
+/// Parses a `@@ -start,len +start,len @@` hunk header.
+fn parse_hunk_header(line: &str) -> Option<((usize, usize), (usize, usize))> {
+    let ranges = line.strip_prefix("@@ -")?.strip_suffix(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    Some((parse_hunk_range(old)?, parse_hunk_range(new)?))
+}
+
+impl Str {
+    /// Computes a patch turning `original` into `modified`, which
+    /// [`Str::apply_patch`] can apply.
+    ///
+    /// The patch is a line-based unified diff: a `--- original` and a
+    /// `+++ modified` header, then one hunk per group of changes. A hunk
+    /// starts with a `@@ -start,len +start,len @@` line giving the 1-based
+    /// line ranges it covers in both texts, followed by its lines, each
+    /// prefixed with `' '` if unchanged, `'-'` if removed, or `'+'` if
+    /// added. Up to three unchanged lines of context are kept around every
+    /// change. Lines are separated by `\n` only, so a trailing newline
+    /// counts as an empty last line. The patch is empty if the texts are
+    /// equal.
+    ///
+    /// Computing the patch takes time proportional to the product of the
+    /// numbers of lines between the first and the last change, and memory
+    /// proportional to their sum.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let patch = Str::compute_patch("a\nb\nc", "a\nB\nc");
+    /// assert_eq!(
+    ///     patch.as_ref(),
+    ///     "--- original\n+++ modified\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n",
+    /// );
+    /// ```
+    pub fn compute_patch(original: &str, modified: &str) -> Str {
+        let a: Vec<&str> = original.split('\n').collect();
+        let b: Vec<&str> = modified.split('\n').collect();
+        let edits = line_edits(&a, &b);
+        let changes: Vec<usize> = (0..edits.len())
+            .filter(|&at| edits[at] != Edit::Keep)
+            .collect();
+        if changes.is_empty() {
+            return Str::new("");
+        }
+
+        // Index into `a` and `b` of the line each edit applies to.
+        let mut lines = Vec::with_capacity(edits.len());
+        let (mut i, mut j) = (0, 0);
+        for &edit in &edits {
+            lines.push((i, j));
+            i += (edit != Edit::Insert) as usize;
+            j += (edit != Edit::Delete) as usize;
+        }
+
+        let mut out = String::from("--- original\n+++ modified\n");
+        let mut next = 0;
+        while next < changes.len() {
+            let first = changes[next];
+            let mut last = first;
+            next += 1;
+            while next < changes.len()
+                && changes[next] - last <= 2 * CONTEXT + 1
+            {
+                last = changes[next];
+                next += 1;
+            }
+
+            let hunk = first.saturating_sub(CONTEXT)
+                ..(last + 1 + CONTEXT).min(edits.len());
+            let (old_start, new_start) = lines[hunk.start];
+            let old_len = edits[hunk.clone()]
+                .iter()
+                .filter(|&&edit| edit != Edit::Insert)
+                .count();
+            let new_len = edits[hunk.clone()]
+                .iter()
+                .filter(|&&edit| edit != Edit::Delete)
+                .count();
+            out.push_str(&format!(
+                "@@ -{} +{} @@\n",
+                hunk_range(old_start, old_len),
+                hunk_range(new_start, new_len)
+            ));
+
+            for at in hunk {
+                let (i, j) = lines[at];
+                let line = match edits[at] {
+                    Edit::Insert => b[j],
+                    _ => a[i],
+                };
+                out.push(edits[at].prefix());
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+        Str::new(&out)
+    }
+
+    /// Applies a patch made by [`Str::compute_patch`] to `original`.
+    ///
+    /// Every unchanged and removed line in the patch must match `original`,
+    /// so a patch applies only to the text it was computed from, give or
+    /// take lines outside of its hunks. An empty patch returns `original`
+    /// unchanged.
+    ///
+    /// # Errors
+    /// Returns [`PatchError::Malformed`] if the patch is not in the format
+    /// [`Str::compute_patch`] produces, and [`PatchError::Mismatch`] if
+    /// `original` does not match it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{PatchError, Str};
+    /// let patch = Str::compute_patch("a\nb\nc", "a\nB\nc");
+    /// assert_eq!(
+    ///     Str::apply_patch("a\nb\nc", patch.as_ref()).unwrap().as_ref(),
+    ///     "a\nB\nc",
+    /// );
+    /// assert_eq!(
+    ///     Str::apply_patch("a\nx\nc", patch.as_ref()),
+    ///     Err(PatchError::Mismatch(2)),
+    /// );
+    /// ```
+    pub fn apply_patch(original: &str, patch: &str) -> Result<Str, PatchError> {
+        if patch.is_empty() {
+            return Ok(Str::new(original));
+        }
+
+        let body = patch
+            .strip_suffix('\n')
+            .ok_or(PatchError::Malformed(patch.split('\n').count()))?;
+        let missing = PatchError::Malformed(body.split('\n').count() + 1);
+        let mut patch_lines = body.split('\n').zip(1..);
+        for (header, number) in ["--- ", "+++ "].into_iter().zip(1..) {
+            match patch_lines.next() {
+                Some((line, _)) if line.starts_with(header) => {}
+                _ => return Err(PatchError::Malformed(number)),
+            }
+        }
+
+        let a: Vec<&str> = original.split('\n').collect();
+        let mut out: Vec<&str> = Vec::with_capacity(a.len());
+        let mut i = 0;
+        while let Some((header, number)) = patch_lines.next() {
+            let ((old_start, old_len), (_, new_len)) =
+                parse_hunk_header(header)
+                    .ok_or(PatchError::Malformed(number))?;
+            if old_start < i {
+                return Err(PatchError::Malformed(number));
+            }
+            if old_start > a.len() {
+                return Err(PatchError::Mismatch(a.len() + 1));
+            }
+            out.extend_from_slice(&a[i..old_start]);
+            i = old_start;
+
+            let (mut old_seen, mut new_seen) = (0, 0);
+            while old_seen < old_len || new_seen < new_len {
+                let (line, number) =
+                    patch_lines.next().ok_or(missing.clone())?;
+                let mut chars = line.chars();
+                let (in_old, in_new) = match chars.next() {
+                    Some(' ') => (true, true),
+                    Some('-') => (true, false),
+                    Some('+') => (false, true),
+                    _ => return Err(PatchError::Malformed(number)),
+                };
+                let text = chars.as_str();
+                if in_old {
+                    if a.get(i) != Some(&text) {
+                        return Err(PatchError::Mismatch(i + 1));
+                    }
+                    i += 1;
+                    old_seen += 1;
+                }
+                if in_new {
+                    out.push(text);
+                    new_seen += 1;
+                }
+                if old_seen > old_len || new_seen > new_len {
+                    return Err(PatchError::Malformed(number));
+                }
+            }
+        }
+        out.extend_from_slice(&a[i..]);
+        Ok(Str::new(&out.join("\n")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(original: &str, modified: &str) {
+        let patch = Str::compute_patch(original, modified);
+        let patched = Str::apply_patch(original, patch.as_ref());
+        assert_eq!(patched.unwrap().as_ref(), modified, "patch:\n{}", patch);
+    }
+
+    #[test]
+    fn test_compute_patch_one_line() {
+        let original = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\n";
+        let modified = "one\ntwo\nthree\nfour\n5\nsix\nseven\neight\n";
+        let patch = Str::compute_patch(original, modified);
+        assert_eq!(
+            patch.as_ref(),
+            "--- original\n\
+             +++ modified\n\
+             @@ -2,7 +2,7 @@\n \
+             two\n \
+             three\n \
+             four\n\
+             -five\n\
+             +5\n \
+             six\n \
+             seven\n \
+             eight\n"
+        );
+        assert_eq!(
+            Str::apply_patch(original, patch.as_ref()).unwrap().as_ref(),
+            modified
+        );
+    }
+
+    #[test]
+    fn test_compute_patch_hunks() {
+        let original: Vec<String> = (1..=20).map(|n| n.to_string()).collect();
+        let mut modified = original.clone();
+        modified[1] = "two".to_string();
+        modified.insert(15, "inserted".to_string());
+        modified.remove(18);
+        let original = original.join("\n");
+        let modified = modified.join("\n");
+
+        let patch = Str::compute_patch(&original, &modified);
+        let headers: Vec<&str> = patch
+            .as_ref()
+            .lines()
+            .filter(|line| line.starts_with("@@"))
+            .collect();
+        assert_eq!(headers, ["@@ -1,5 +1,5 @@", "@@ -13,8 +13,8 @@"]);
+        round_trip(&original, &modified);
+    }
+
+    #[test]
+    fn test_compute_patch_edge_cases() {
+        assert_eq!(Str::compute_patch("same\n", "same\n").as_ref(), "");
+        assert_eq!(
+            Str::compute_patch("", "a").as_ref(),
+            "--- original\n+++ modified\n@@ -1,1 +1,1 @@\n-\n+a\n"
+        );
+        assert_eq!(
+            Str::compute_patch("a", "b\na").as_ref(),
+            "--- original\n+++ modified\n@@ -1,1 +1,2 @@\n+b\n a\n"
+        );
+        round_trip("", "");
+        round_trip("a\nb", "a\nb\n");
+        round_trip("a\nb\n", "a\nb");
+        round_trip("crlf\r\nlines\r\n", "crlf\r\nmore\r\nlines\r\n");
+        round_trip("x\ny\nz", "");
+        round_trip("été\n世界", "世界\nété");
+        round_trip("a\nb\nc\nd", "d\nc\nb\na");
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_compute_patch_long() {
+        let lines: Vec<String> =
+            (0..3000).map(|n| format!("line {}", n)).collect();
+        let original = lines.join("\n");
+        let mut changed = lines.clone();
+        changed[0] = "first".to_string();
+        changed[2999] = "last".to_string();
+        changed.retain(|line| !line.ends_with('7'));
+        let modified = changed.join("\n");
+
+        let patch = Str::compute_patch(&original, &modified);
+        let hunk_lines = patch.as_ref().lines().skip(2);
+        let removed = hunk_lines.filter(|line| line.starts_with('-'));
+        assert_eq!(removed.count(), 2 + 300);
+        round_trip(&original, &modified);
+    }
+
+    #[test]
+    fn test_apply_patch_wrong_base() {
+        let patch = Str::compute_patch("a\nb\nc\n", "a\nB\nc\n");
+        let patch = patch.as_ref();
+        assert_eq!(
+            Str::apply_patch("a\nc\nc\n", patch),
+            Err(PatchError::Mismatch(2))
+        );
+        assert_eq!(
+            Str::apply_patch("x\nb\nc\n", patch),
+            Err(PatchError::Mismatch(1))
+        );
+        assert_eq!(
+            Str::apply_patch("a\nb", patch),
+            Err(PatchError::Mismatch(3))
+        );
+        assert_eq!(Str::apply_patch("", patch), Err(PatchError::Mismatch(1)));
+
+        let patch = Str::compute_patch(
+            "1\n2\n3\n4\n5\n6\n7\n8\n",
+            "1\n2\n3\n4\n5\n6\n7\n9\n",
+        );
+        assert_eq!(
+            Str::apply_patch("1\n2\n", patch.as_ref()),
+            Err(PatchError::Mismatch(4))
+        );
+        assert_eq!(
+            Str::apply_patch("anything", "").unwrap().as_ref(),
+            "anything"
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_malformed() {
+        let apply = |patch: &str| Str::apply_patch("a\nb", patch);
+        assert_eq!(apply("a"), Err(PatchError::Malformed(1)));
+        assert_eq!(apply("--- a\n"), Err(PatchError::Malformed(2)));
+        assert_eq!(
+            apply("--- a\n+++ b\n@@ -1 @@\n"),
+            Err(PatchError::Malformed(3))
+        );
+        assert_eq!(
+            apply("--- a\n+++ b\n@@ -1,2 +1,2 @@\n a\n"),
+            Err(PatchError::Malformed(5))
+        );
+        assert_eq!(
+            apply("--- a\n+++ b\n@@ -1,1 +1,1 @@\n a\n+x\n"),
+            Err(PatchError::Malformed(5))
+        );
+        assert_eq!(
+            apply("--- a\n+++ b\n@@ -2,1 +2,1 @@\n b\n@@ -1,1 +1,1 @@\n a\n"),
+            Err(PatchError::Malformed(5))
+        );
+        assert_eq!(
+            apply("--- a\n+++ b\n@@ -1,1 +1,1 @@\n*a\n"),
+            Err(PatchError::Malformed(4))
+        );
+        assert_eq!(
+            apply("--- a\n+++ b\n@@ -1,1 +1,1 @@\n-a\n+A\n")
+                .unwrap()
+                .as_ref(),
+            "A\nb"
+        );
+    }
+}
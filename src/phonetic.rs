@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use crate::Str;
+
+/// Letter groups tried before single letters by [`Str::to_ipa_via_rules`],
+/// longest first.
+const IPA_GROUPS: [(&str, &str); 20] = [
+    ("tion", "ʃən"),
+    ("igh", "aɪ"),
+    ("sh", "ʃ"),
+    ("ch", "tʃ"),
+    ("th", "θ"),
+    ("ph", "f"),
+    ("ng", "ŋ"),
+    ("ck", "k"),
+    ("qu", "kw"),
+    ("wh", "w"),
+    ("ee", "iː"),
+    ("ea", "iː"),
+    ("oo", "uː"),
+    ("ai", "eɪ"),
+    ("ay", "eɪ"),
+    ("oa", "oʊ"),
+    ("ou", "aʊ"),
+    ("ow", "aʊ"),
+    ("oi", "ɔɪ"),
+    ("oy", "ɔɪ"),
+];
+
+fn ipa_letter(c: char, word_start: bool) -> &'static str {
+    match c {
+        'a' => "æ",
+        'b' => "b",
+        'c' | 'k' | 'q' => "k",
+        'd' => "d",
+        'e' => "ɛ",
+        'f' => "f",
+        'g' => "ɡ",
+        'h' => "h",
+        'i' => "ɪ",
+        'j' => "dʒ",
+        'l' => "l",
+        'm' => "m",
+        'n' => "n",
+        'o' => "ɒ",
+        'p' => "p",
+        'r' => "ɹ",
+        's' => "s",
+        't' => "t",
+        'u' => "ʌ",
+        'v' => "v",
+        'w' => "w",
+        'x' => "ks",
+        'y' if word_start => "j",
+        'y' => "i",
+        _ => "z",
+    }
+}
+
+fn push_ipa_word(out: &mut String, word: &str) {
+    let is_vowel = |c| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let chars: Vec<char> = word.chars().collect();
+    // A final `e` after a consonant is silent, as in "stone".
+    let silent_e = chars.len() > 2
+        && chars[chars.len() - 1] == 'e'
+        && !is_vowel(chars[chars.len() - 2]);
+    let word = if silent_e {
+        &word[..word.len() - 1]
+    } else {
+        word
+    };
+
+    let mut rest = word;
+    let mut prev = None;
+    while let Some(c) = rest.chars().next() {
+        if let Some((group, ipa)) =
+            IPA_GROUPS.iter().find(|(group, _)| rest.starts_with(group))
+        {
+            out.push_str(ipa);
+            rest = &rest[group.len()..];
+            prev = None;
+            continue;
+        }
+
+        // Doubled consonants are pronounced once.
+        if prev != Some(c) || is_vowel(c) {
+            out.push_str(ipa_letter(c, rest.len() == word.len()));
+        }
+        prev = Some(c);
+        rest = &rest[1..];
+    }
+}
+
+impl Str {
+    /// Looks the word up in a CMU Pronouncing Dictionary style map from
+    /// words to ARPABET phoneme strings.
+    ///
+    /// The word is tried as is and then upper cased, which is how the CMU
+    /// dictionary spells its entries. Returns `None` if neither is present.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// # use std::collections::HashMap;
+    /// let mut dict = HashMap::new();
+    /// dict.insert(Str::new("PYTHON"), Str::new("P AY1 TH AA0 N"));
+    ///
+    /// let arpabet = Str::new("python").to_cmu_arpabet(&dict);
+    /// assert_eq!(arpabet, Some(Str::new("P AY1 TH AA0 N")));
+    /// ```
+    pub fn to_cmu_arpabet(&self, dict: &HashMap<Str, Str>) -> Option<Str> {
+        let word: &str = self.as_ref();
+        dict.get(word)
+            .or_else(|| dict.get(word.to_uppercase().as_str()))
+            .cloned()
+    }
+
+    /// Approximates the IPA pronunciation of English text with simple
+    /// spelling rules.
+    ///
+    /// This is a fallback for words missing from a pronouncing dictionary,
+    /// not a linguistically accurate transcription: common letter groups
+    /// such as `sh`, `th` or `ee` are mapped first, then single letters,
+    /// doubled consonants are collapsed and a final silent `e` is dropped.
+    /// The text is lower cased and characters other than ASCII letters are
+    /// copied unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("fish and chips").to_ipa_via_rules().as_ref(), "fɪʃ ænd tʃɪps");
+    /// ```
+    pub fn to_ipa_via_rules(&self) -> Str {
+        let lower = self.as_ref().to_lowercase();
+        let mut out = String::with_capacity(lower.len() * 2);
+        let mut rest = lower.as_str();
+        while !rest.is_empty() {
+            let end = rest
+                .find(|c: char| !c.is_ascii_lowercase())
+                .unwrap_or(rest.len());
+            let (word, tail) = rest.split_at(end);
+            push_ipa_word(&mut out, word);
+
+            let mut tail_chars = tail.chars();
+            if let Some(c) = tail_chars.next() {
+                out.push(c);
+            }
+            rest = tail_chars.as_str();
+        }
+        Str::new(&out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dict() -> HashMap<Str, Str> {
+        [
+            ("PYTHON", "P AY1 TH AA0 N"),
+            ("HELLO", "HH AH0 L OW1"),
+            ("read", "R EH1 D"),
+        ]
+        .into_iter()
+        .map(|(word, arpabet)| (Str::new(word), Str::new(arpabet)))
+        .collect()
+    }
+
+    #[test]
+    fn test_to_cmu_arpabet_hits() {
+        let dict = dict();
+        let lookup = |s: &str| Str::new(s).to_cmu_arpabet(&dict);
+        assert_eq!(lookup("PYTHON"), Some(Str::new("P AY1 TH AA0 N")));
+        assert_eq!(lookup("Hello"), Some(Str::new("HH AH0 L OW1")));
+        assert_eq!(lookup("read"), Some(Str::new("R EH1 D")));
+    }
+
+    #[test]
+    fn test_to_cmu_arpabet_misses() {
+        let dict = dict();
+        assert_eq!(Str::new("rust").to_cmu_arpabet(&dict), None);
+        assert_eq!(Str::new("READ").to_cmu_arpabet(&dict), None);
+        assert_eq!(Str::new("").to_cmu_arpabet(&dict), None);
+    }
+
+    #[test]
+    fn test_to_ipa_via_rules() {
+        let ipa = |s: &str| Str::new(s).to_ipa_via_rules().to_string();
+        assert_eq!(ipa("cat"), "kæt");
+        assert_eq!(ipa("ship"), "ʃɪp");
+        assert_eq!(ipa("thing"), "θɪŋ");
+        assert_eq!(ipa("queen"), "kwiːn");
+        assert_eq!(ipa("night"), "naɪt");
+        assert_eq!(ipa("action"), "ækʃən");
+        assert_eq!(ipa("bell"), "bɛl");
+        assert_eq!(ipa("stone"), "stɒn");
+        assert_eq!(ipa("yes"), "jɛs");
+        assert_eq!(ipa("boat"), "boʊt");
+        assert_eq!(ipa("Hello, World!"), "hɛlɒ, wɒɹld!");
+        assert_eq!(ipa(""), "");
+    }
+}
@@ -0,0 +1,674 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::Str;
+
+/// A multi-pattern matcher built with the Aho-Corasick algorithm.
+///
+/// Building takes time linear in the total length of the patterns, and
+/// [`AhoCorasick::search`] finds every occurrence of every pattern in a
+/// single pass over the text. Patterns are matched by bytes; empty patterns
+/// never match.
+///
+/// # Examples
+/// ```
+/// # use ostr::{AhoCorasick, Str};
+/// let ac = AhoCorasick::build(&["he", "she", "his", "hers"]);
+/// let text = Str::new("ushers");
+/// let found: Vec<(usize, usize, usize)> = ac.search(&text).collect();
+/// assert_eq!(found, [(1, 4, 1), (2, 4, 0), (2, 6, 3)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    /// Trie edges of each node.
+    goto: Vec<HashMap<u8, usize>>,
+    /// The node of the longest proper suffix of each node that is also in
+    /// the trie.
+    fail: Vec<usize>,
+    /// The patterns that end at each node, including those reachable
+    /// through failure links.
+    output: Vec<Vec<usize>>,
+    lengths: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// Builds the automaton for `patterns`. Match results refer to patterns
+    /// by their index in this slice.
+    pub fn build(patterns: &[&str]) -> AhoCorasick {
+        let mut goto = vec![HashMap::new()];
+        let mut output = vec![Vec::new()];
+        for (index, pattern) in patterns.iter().enumerate() {
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut node = 0;
+            for &b in pattern.as_bytes() {
+                node = match goto[node].get(&b) {
+                    Some(&next) => next,
+                    None => {
+                        goto.push(HashMap::new());
+                        output.push(Vec::new());
+                        let next = goto.len() - 1;
+                        goto[node].insert(b, next);
+                        next
+                    }
+                };
+            }
+            output[node].push(index);
+        }
+
+        // Breadth-first, so the failure target of every node is finished
+        // before the node itself.
+        let mut fail = vec![0; goto.len()];
+        let mut queue: VecDeque<usize> = goto[0].values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            let edges: Vec<(u8, usize)> =
+                goto[node].iter().map(|(&b, &next)| (b, next)).collect();
+            for (b, next) in edges {
+                let mut state = fail[node];
+                while state != 0 && !goto[state].contains_key(&b) {
+                    state = fail[state];
+                }
+                fail[next] = goto[state].get(&b).copied().unwrap_or(0);
+                let inherited = output[fail[next]].clone();
+                output[next].extend(inherited);
+                queue.push_back(next);
+            }
+        }
+
+        AhoCorasick {
+            goto,
+            fail,
+            output,
+            lengths: patterns.iter().map(|pattern| pattern.len()).collect(),
+        }
+    }
+
+    fn step(&self, mut state: usize, b: u8) -> usize {
+        loop {
+            if let Some(&next) = self.goto[state].get(&b) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.fail[state];
+        }
+    }
+
+    /// Returns an iterator over all matches in `text`, including overlapping
+    /// ones, as `(start, end, pattern_index)` byte ranges.
+    ///
+    /// Matches are yielded in order of their end offset; matches ending at
+    /// the same offset are yielded longest first.
+    pub fn search<'a>(
+        &'a self,
+        text: &'a Str,
+    ) -> impl Iterator<Item = (usize, usize, usize)> + 'a {
+        let mut state = 0;
+        text.as_ref().bytes().enumerate().flat_map(move |(at, b)| {
+            state = self.step(state, b);
+            let end = at + 1;
+            self.output[state]
+                .iter()
+                .map(move |&index| (end - self.lengths[index], end, index))
+        })
+    }
+}
+
+#[inline]
+fn add_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 + b as u128) % modulus as u128) as u64
+}
+
+#[inline]
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+struct RollingHash<'a> {
+    bytes: &'a [u8],
+    window: usize,
+    base: u64,
+    modulus: u64,
+    /// `base` raised to `window - 1`, the weight of the byte leaving the
+    /// window.
+    high: u64,
+    /// Start of the next window to yield.
+    pos: usize,
+    hash: u64,
+}
+
+impl Iterator for RollingHash<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.pos + self.window > self.bytes.len() {
+            return None;
+        }
+        if self.pos > 0 {
+            let m = self.modulus;
+            let old = mul_mod(self.bytes[self.pos - 1] as u64, self.high, m);
+            let new = self.bytes[self.pos + self.window - 1] as u64 % m;
+            let hash = add_mod(self.hash, m - old, m);
+            self.hash = add_mod(mul_mod(hash, self.base, m), new, m);
+        }
+        self.pos += 1;
+        Some(self.hash)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.bytes.len() + 1).saturating_sub(self.pos + self.window);
+        (n, Some(n))
+    }
+}
+
+/// Stable counting sort of `items` by `key`, whose values are below
+/// `classes`.
+fn counting_sort(
+    items: &[usize],
+    classes: usize,
+    key: impl Fn(usize) -> usize,
+) -> Vec<usize> {
+    let mut starts = vec![0; classes + 1];
+    for &item in items {
+        starts[key(item) + 1] += 1;
+    }
+    for class in 0..classes {
+        starts[class + 1] += starts[class];
+    }
+    let mut sorted = vec![0; items.len()];
+    for &item in items {
+        let slot = &mut starts[key(item)];
+        sorted[*slot] = item;
+        *slot += 1;
+    }
+    sorted
+}
+
+/// The number of characters in each shingle hashed by [`Str::simhash`].
+const SHINGLE_LEN: usize = 3;
+
+/// Hashes `bytes` with 64-bit FNV-1a, followed by the MurmurHash3 finalizer
+/// so that every input bit affects every output bit.
+fn shingle_hash(bytes: &[u8]) -> u64 {
+    let mut hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+impl Str {
+    /// Returns an iterator over the Rabin-Karp hash of every `window` byte
+    /// substring, from left to right.
+    ///
+    /// The hash of bytes `b[0] .. b[w - 1]` is the polynomial
+    /// `b[0] * base^(w - 1) + ... + b[w - 1]` modulo `modulus`. The first
+    /// hash takes O(`window`) to compute and each of the following ones
+    /// O(1). Yields nothing if `window` is greater than [`Str::len`].
+    ///
+    /// # Panics
+    /// Panics if `window` or `modulus` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("abab");
+    /// let hashes: Vec<u64> = s.rolling_hash(2, 256, 1_000_007).collect();
+    /// assert_eq!(hashes.len(), 3);
+    /// assert_eq!(hashes[0], hashes[2]);
+    /// assert_eq!(hashes[0], (b'a' as u64 * 256 + b'b' as u64) % 1_000_007);
+    /// ```
+    pub fn rolling_hash(
+        &self,
+        window: usize,
+        base: u64,
+        modulus: u64,
+    ) -> impl Iterator<Item = u64> + '_ {
+        assert!(window != 0, "window size must be non-zero");
+        assert!(modulus != 0, "modulus must be non-zero");
+        let bytes = self.as_ref().as_bytes();
+        let base = base % modulus;
+
+        let mut high = 1 % modulus;
+        let mut hash = 0;
+        if window <= bytes.len() {
+            for &b in &bytes[..window] {
+                let b = b as u64 % modulus;
+                hash = add_mod(mul_mod(hash, base, modulus), b, modulus);
+            }
+            for _ in 1..window {
+                high = mul_mod(high, base, modulus);
+            }
+        }
+
+        RollingHash {
+            bytes,
+            window,
+            base,
+            modulus,
+            high,
+            pos: 0,
+            hash,
+        }
+    }
+
+    /// Builds the suffix array of the string: the byte offsets of all its
+    /// suffixes, sorted lexicographically by bytes.
+    ///
+    /// Uses prefix doubling with counting sorts, which takes O(n log n)
+    /// time for a string of n bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("banana").build_suffix_array(), [5, 3, 1, 0, 4, 2]);
+    /// ```
+    pub fn build_suffix_array(&self) -> Vec<usize> {
+        let bytes = self.as_ref().as_bytes();
+        let n = bytes.len();
+        let all: Vec<usize> = (0..n).collect();
+        let mut sa = counting_sort(&all, 256, |i| bytes[i] as usize);
+
+        // `rank[i]` is the class of the suffix at `i` among suffixes sorted
+        // by their first `k` bytes.
+        let mut rank = vec![0; n];
+        for j in 1..n {
+            let same = bytes[sa[j]] == bytes[sa[j - 1]];
+            rank[sa[j]] = rank[sa[j - 1]] + usize::from(!same);
+        }
+
+        let mut k = 1;
+        while k < n && rank[sa[n - 1]] < n - 1 {
+            // Order by the second half first: suffixes too short to have
+            // one come first, then the others in the current order.
+            let mut by_second: Vec<usize> = (n - k..n).collect();
+            by_second.extend(sa.iter().filter(|&&p| p >= k).map(|&p| p - k));
+            sa = counting_sort(&by_second, n, |i| rank[i]);
+
+            let key = |i: usize| (rank[i], rank.get(i + k).map(|&r| r + 1));
+            let mut next = vec![0; n];
+            for j in 1..n {
+                let same = key(sa[j]) == key(sa[j - 1]);
+                next[sa[j]] = next[sa[j - 1]] + usize::from(!same);
+            }
+            rank = next;
+            k *= 2;
+        }
+        sa
+    }
+
+    /// Finds an occurrence of `pattern` using the suffix array `sa` built by
+    /// [`Str::build_suffix_array`], in O(m log n) time.
+    ///
+    /// Returns the byte offset of the occurrence whose suffix sorts first,
+    /// which is not necessarily the leftmost one. An empty pattern is found
+    /// at offset 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("banana");
+    /// let sa = s.build_suffix_array();
+    /// assert_eq!(s.search_with_suffix_array(&sa, "nan"), Some(2));
+    /// assert_eq!(s.search_with_suffix_array(&sa, "nab"), None);
+    /// ```
+    pub fn search_with_suffix_array(
+        &self,
+        sa: &[usize],
+        pattern: &str,
+    ) -> Option<usize> {
+        if pattern.is_empty() {
+            return Some(0);
+        }
+        let bytes = self.as_ref().as_bytes();
+        let pattern = pattern.as_bytes();
+        let first = sa.partition_point(|&i| &bytes[i..] < pattern);
+        let &at = sa.get(first)?;
+        bytes[at..].starts_with(pattern).then_some(at)
+    }
+
+    /// Returns the SimHash fingerprint of the string, which changes in few
+    /// bits when the string changes a little.
+    ///
+    /// Every run of three consecutive characters is hashed, and each bit of
+    /// the fingerprint is set if it is set in most of these hashes. Compare
+    /// fingerprints with [`Str::simhash_similarity`]: near-duplicates differ
+    /// in few bits, while unrelated strings differ in about half of them.
+    /// A string shorter than three characters is hashed as a whole, and an
+    /// empty string has the fingerprint 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let a = Str::new("The quick brown fox jumps over the lazy dog").simhash();
+    /// let b = Str::new("The quick brown fox jumped over the lazy dog").simhash();
+    /// assert!(Str::simhash_similarity(a, b) > 0.8);
+    /// ```
+    pub fn simhash(&self) -> u64 {
+        let s: &str = self.as_ref();
+        if s.is_empty() {
+            return 0;
+        }
+
+        let starts: Vec<usize> = s
+            .char_indices()
+            .map(|(at, _)| at)
+            .chain(std::iter::once(s.len()))
+            .collect();
+        let mut weights = [0i64; 64];
+        let shingles = starts.len().saturating_sub(SHINGLE_LEN).max(1);
+        for i in 0..shingles {
+            let end = starts[(i + SHINGLE_LEN).min(starts.len() - 1)];
+            let hash = shingle_hash(&s.as_bytes()[starts[i]..end]);
+            for (bit, weight) in weights.iter_mut().enumerate() {
+                if hash >> bit & 1 == 1 {
+                    *weight += 1;
+                } else {
+                    *weight -= 1;
+                }
+            }
+        }
+
+        weights
+            .iter()
+            .enumerate()
+            .filter(|&(_, &weight)| weight > 0)
+            .fold(0, |fingerprint, (bit, _)| fingerprint | 1 << bit)
+    }
+
+    /// Returns the similarity of two [`Str::simhash`] fingerprints: the
+    /// fraction of their bits that are equal, from 0.0 to 1.0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::simhash_similarity(0b1010, 0b1010), 1.0);
+    /// assert_eq!(Str::simhash_similarity(0, u64::MAX), 0.0);
+    /// assert_eq!(Str::simhash_similarity(0, 0xffff_ffff), 0.5);
+    /// ```
+    #[inline]
+    pub fn simhash_similarity(a: u64, b: u64) -> f64 {
+        1.0 - (a ^ b).count_ones() as f64 / 64.0
+    }
+
+    /// Returns the byte offset of every occurrence of `c`, in increasing
+    /// order.
+    ///
+    /// With the `memchr` feature, ASCII characters are searched for with
+    /// [`memchr`](https://docs.rs/memchr).
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("a,b,,c");
+    /// assert_eq!(s.find_all_char(','), [1, 3, 4]);
+    /// ```
+    pub fn find_all_char(&self, c: char) -> Vec<usize> {
+        let s: &str = self.as_ref();
+        #[cfg(feature = "memchr")]
+        if c.is_ascii() {
+            return memchr::memchr_iter(c as u8, s.as_bytes()).collect();
+        }
+        s.match_indices(c).map(|(at, _)| at).collect()
+    }
+
+    /// Returns the number of occurrences of `c`.
+    ///
+    /// Unlike counting with [`str::chars`], this does not decode the string.
+    /// With the `memchr` feature, ASCII characters are counted with
+    /// [`memchr`](https://docs.rs/memchr).
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("one\ntwo\nthree").count_char('\n'), 2);
+    /// ```
+    pub fn count_char(&self, c: char) -> usize {
+        let s: &str = self.as_ref();
+        #[cfg(feature = "memchr")]
+        if c.is_ascii() {
+            return memchr::memchr_iter(c as u8, s.as_bytes()).count();
+        }
+        s.matches(c).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: u64 = 257;
+    const MODULUS: u64 = 1_000_000_007;
+
+    fn naive_hash(bytes: &[u8]) -> u64 {
+        bytes
+            .iter()
+            .fold(0, |hash, &b| (hash * BASE + b as u64) % MODULUS)
+    }
+
+    #[test]
+    fn test_rolling_hash_matches_naive() {
+        let s = Str::new("the quick brown fox, 世界");
+        for window in [1, 3, 8, s.len()] {
+            let hashes: Vec<u64> =
+                s.rolling_hash(window, BASE, MODULUS).collect();
+            let naive: Vec<u64> = s
+                .as_ref()
+                .as_bytes()
+                .windows(window)
+                .map(naive_hash)
+                .collect();
+            assert_eq!(hashes, naive, "{}", window);
+        }
+    }
+
+    #[test]
+    fn test_rolling_hash_slides() {
+        let s = Str::new("abcabd");
+        let hashes: Vec<u64> = s.rolling_hash(2, BASE, MODULUS).collect();
+        assert_eq!(hashes.len(), 5);
+        assert_eq!(hashes[0], naive_hash(b"ab"));
+        assert_ne!(hashes[0], hashes[1]);
+        assert_eq!(hashes[0], hashes[3]);
+        assert_ne!(hashes[2], hashes[4]);
+    }
+
+    #[test]
+    fn test_rolling_hash_window_too_large() {
+        let s = Str::new("abc");
+        assert_eq!(s.rolling_hash(4, BASE, MODULUS).count(), 0);
+        assert_eq!(s.rolling_hash(3, BASE, MODULUS).count(), 1);
+        assert_eq!(Str::new("").rolling_hash(1, BASE, MODULUS).count(), 0);
+    }
+
+    #[test]
+    fn test_rolling_hash_large_modulus() {
+        let s = Str::new(&"xyz".repeat(20));
+        let hashes: Vec<u64> =
+            s.rolling_hash(6, u64::MAX - 1, u64::MAX).collect();
+        assert!(hashes.chunks(3).all(|chunk| chunk[0] == hashes[0]));
+    }
+
+    fn naive_suffix_array(s: &str) -> Vec<usize> {
+        let mut sa: Vec<usize> = (0..s.len()).collect();
+        sa.sort_by_key(|&i| &s.as_bytes()[i..]);
+        sa
+    }
+
+    #[test]
+    fn test_build_suffix_array() {
+        assert_eq!(Str::new("banana").build_suffix_array(), [5, 3, 1, 0, 4, 2]);
+        assert!(Str::new("").build_suffix_array().is_empty());
+        assert_eq!(Str::new("a").build_suffix_array(), [0]);
+        assert_eq!(Str::new("aaaa").build_suffix_array(), [3, 2, 1, 0]);
+        for input in
+            ["mississippi", "abracadabra", "世界世界 界", "abcabcabcab"]
+        {
+            let sa = Str::new(input).build_suffix_array();
+            assert_eq!(sa, naive_suffix_array(input), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_search_with_suffix_array() {
+        let s = Str::new("banana");
+        let sa = s.build_suffix_array();
+        assert_eq!(s.search_with_suffix_array(&sa, "ana"), Some(3));
+        assert_eq!(s.search_with_suffix_array(&sa, "ban"), Some(0));
+        assert_eq!(s.search_with_suffix_array(&sa, "a"), Some(5));
+        assert_eq!(s.search_with_suffix_array(&sa, "banana"), Some(0));
+        assert_eq!(s.search_with_suffix_array(&sa, "xyz"), None);
+        assert_eq!(s.search_with_suffix_array(&sa, "bananas"), None);
+        assert_eq!(s.search_with_suffix_array(&sa, ""), Some(0));
+    }
+
+    #[test]
+    fn test_search_with_suffix_array_unicode() {
+        let s = Str::new("日本語のテキスト、日本");
+        let sa = s.build_suffix_array();
+        for pattern in ["本", "テキスト", "、日本", "日本語の"] {
+            let at = s.search_with_suffix_array(&sa, pattern).unwrap();
+            assert!(s.as_ref()[at..].starts_with(pattern), "{}", pattern);
+        }
+        assert_eq!(s.search_with_suffix_array(&sa, "本語テ"), None);
+        assert_eq!(Str::new("").search_with_suffix_array(&[], "a"), None);
+    }
+
+    fn matches(patterns: &[&str], text: &str) -> Vec<(usize, usize, usize)> {
+        let text = Str::new(text);
+        AhoCorasick::build(patterns).search(&text).collect()
+    }
+
+    #[test]
+    fn test_aho_corasick_classic() {
+        let found = matches(&["he", "she", "his", "hers"], "ushers");
+        assert_eq!(found, [(1, 4, 1), (2, 4, 0), (2, 6, 3)]);
+
+        let found = matches(&["he", "she", "his", "hers"], "ahishers");
+        assert_eq!(found, [(1, 4, 2), (3, 6, 1), (4, 6, 0), (4, 8, 3)]);
+    }
+
+    #[test]
+    fn test_aho_corasick_overlapping() {
+        assert_eq!(matches(&["aa"], "aaaa"), [(0, 2, 0), (1, 3, 0), (2, 4, 0)]);
+        assert_eq!(
+            matches(&["a", "ab", "bc", "abc"], "abc"),
+            [(0, 1, 0), (0, 2, 1), (0, 3, 3), (1, 3, 2)]
+        );
+    }
+
+    #[test]
+    fn test_aho_corasick_no_matches() {
+        assert!(matches(&["xyz"], "ushers").is_empty());
+        assert!(matches(&[], "ushers").is_empty());
+        assert!(matches(&["", "a"], "bbb").is_empty());
+        assert!(matches(&["he"], "").is_empty());
+    }
+
+    #[test]
+    fn test_aho_corasick_agrees_with_naive() {
+        let patterns = ["世界", "界", "hello", "lo 世", "o"];
+        let text = "hello 世界, hello 界";
+        let mut naive = Vec::new();
+        for (index, pattern) in patterns.iter().enumerate() {
+            for (start, _) in text.match_indices(pattern) {
+                naive.push((start, start + pattern.len(), index));
+            }
+        }
+        let mut found = matches(&patterns, text);
+        found.sort();
+        naive.sort();
+        assert_eq!(found, naive);
+    }
+
+    #[test]
+    fn test_find_all_char() {
+        let line = Str::new("id,name,\"city, state\",zip");
+        assert_eq!(line.find_all_char(','), [2, 7, 13, 21]);
+        assert_eq!(line.find_all_char('"'), [8, 20]);
+        assert!(line.find_all_char(';').is_empty());
+        assert!(Str::new("").find_all_char(',').is_empty());
+
+        let s = Str::new("世a界a🎉");
+        assert_eq!(s.find_all_char('a'), [3, 7]);
+        assert_eq!(s.find_all_char('界'), [4]);
+        assert_eq!(s.find_all_char('🎉'), [8]);
+    }
+
+    #[test]
+    fn test_count_char() {
+        let text = Str::new("line one\nline two\n\nline four\n");
+        assert_eq!(text.count_char('\n'), 4);
+        assert_eq!(text.count_char('l'), 3);
+        assert_eq!(text.count_char('x'), 0);
+        assert_eq!(Str::new("").count_char('\n'), 0);
+
+        let s = Str::new("été à l'été");
+        assert_eq!(s.count_char('é'), 4);
+        assert_eq!(
+            s.count_char('é'),
+            s.as_ref().chars().filter(|&c| c == 'é').count()
+        );
+    }
+
+    fn simhash_distance(a: &str, b: &str) -> u32 {
+        (Str::new(a).simhash() ^ Str::new(b).simhash()).count_ones()
+    }
+
+    #[test]
+    fn test_simhash_near_duplicates() {
+        let fox = "The quick brown fox jumps over the lazy dog";
+        assert_eq!(simhash_distance(fox, fox), 0);
+        assert!(simhash_distance(fox, &format!("{}!", fox)) <= 4);
+        assert!(
+            simhash_distance(
+                fox,
+                "The quick brown fox jumped over the lazy dog"
+            ) <= 8
+        );
+        let lorem = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+        assert!(simhash_distance(lorem, &format!("{}, sed do", lorem)) <= 8);
+    }
+
+    #[test]
+    fn test_simhash_unrelated() {
+        let pairs = [
+            (
+                "The quick brown fox jumps over the lazy dog",
+                "Pack my box with five dozen liquor jugs",
+            ),
+            ("hello world", "completely unrelated text 12345"),
+            (
+                "Lorem ipsum dolor sit amet, consectetur adipiscing elit",
+                "Sphinx of black quartz, judge my vow and leave now",
+            ),
+        ];
+        for (a, b) in pairs {
+            let distance = simhash_distance(a, b);
+            assert!((20..=44).contains(&distance), "{}", distance);
+        }
+    }
+
+    #[test]
+    fn test_simhash_short_strings() {
+        assert_eq!(Str::new("").simhash(), 0);
+        assert_ne!(Str::new("a").simhash(), 0);
+        assert_ne!(Str::new("a").simhash(), Str::new("b").simhash());
+        assert_ne!(Str::new("ab").simhash(), Str::new("abc").simhash());
+        assert_eq!(Str::new("世界").simhash(), Str::new("世界").simhash());
+    }
+
+    #[test]
+    fn test_simhash_similarity() {
+        assert_eq!(Str::simhash_similarity(42, 42), 1.0);
+        assert_eq!(Str::simhash_similarity(u64::MAX, 0), 0.0);
+        assert_eq!(Str::simhash_similarity(0, 0b1111), 1.0 - 4.0 / 64.0);
+        let a = Str::new("near duplicate text here").simhash();
+        let b = Str::new("near duplicate text here.").simhash();
+        assert!(Str::simhash_similarity(a, b) > 0.9);
+    }
+}
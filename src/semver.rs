@@ -0,0 +1,287 @@
+use std::cmp::Ordering;
+
+use crate::Str;
+
+/// A version number parsed by [`Str::parse_semver`].
+///
+/// Versions are ordered by SemVer 2.0.0 precedence. Build metadata does not
+/// affect precedence, it is only compared as a last resort to keep [`Ord`]
+/// consistent with [`Eq`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SemVer {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub pre: Option<Str>,
+    pub build: Option<Str>,
+}
+
+/// Error returned by [`Str::parse_semver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SemVerError {
+    /// The `major.minor.patch` part is missing, malformed or has leading
+    /// zeros.
+    InvalidCore,
+    /// A `major`, `minor` or `patch` number does not fit in `u32`.
+    NumberOverflow,
+    /// The pre-release part has an empty or invalid identifier.
+    InvalidPreRelease,
+    /// The build metadata part has an empty or invalid identifier.
+    InvalidBuild,
+}
+
+impl std::fmt::Display for SemVerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::InvalidCore => "invalid major.minor.patch version",
+            Self::NumberOverflow => "version number is too large",
+            Self::InvalidPreRelease => "invalid pre-release identifier",
+            Self::InvalidBuild => "invalid build metadata identifier",
+        })
+    }
+}
+
+impl std::error::Error for SemVerError {}
+
+impl std::fmt::Display for SemVer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialOrd for SemVer {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let core = (self.major, self.minor, self.patch);
+        core.cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => cmp_pre_release(a.as_ref(), b.as_ref()),
+            })
+            .then_with(|| {
+                let a = self.build.as_ref().map(AsRef::as_ref);
+                a.cmp(&other.build.as_ref().map(AsRef::as_ref))
+            })
+    }
+}
+
+fn cmp_pre_release(a: &str, b: &str) -> Ordering {
+    let is_numeric = |id: &str| id.bytes().all(|b| b.is_ascii_digit());
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        let ord = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match (is_numeric(x), is_numeric(y)) {
+                (true, true) => x.len().cmp(&y.len()).then_with(|| x.cmp(y)),
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                (false, false) => x.cmp(y),
+            },
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+}
+
+fn parse_number(s: &str) -> Result<u32, SemVerError> {
+    let valid = !s.is_empty()
+        && s.bytes().all(|b| b.is_ascii_digit())
+        && (s == "0" || !s.starts_with('0'));
+    if !valid {
+        return Err(SemVerError::InvalidCore);
+    }
+    s.parse().map_err(|_| SemVerError::NumberOverflow)
+}
+
+fn valid_identifiers(s: &str, allow_leading_zeros: bool) -> bool {
+    s.split('.').all(|id| {
+        let bytes = id.as_bytes();
+        let charset = !bytes.is_empty()
+            && bytes
+                .iter()
+                .all(|b| b.is_ascii_alphanumeric() || *b == b'-');
+        let numeric = bytes.iter().all(u8::is_ascii_digit);
+        charset
+            && (allow_leading_zeros
+                || !numeric
+                || id == "0"
+                || bytes[0] != b'0')
+    })
+}
+
+impl Str {
+    /// Returns `true` if the string is a valid SemVer 2.0.0 version.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert!(Str::new("1.0.0-alpha.1+build.5").is_valid_semver());
+    /// assert!(!Str::new("1.0").is_valid_semver());
+    /// ```
+    #[inline]
+    pub fn is_valid_semver(&self) -> bool {
+        self.parse_semver().is_ok()
+    }
+
+    /// Parses the string as a
+    /// `major.minor.patch[-pre-release][+build]` SemVer 2.0.0 version.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let v = Str::new("1.2.3-rc.1").parse_semver().unwrap();
+    /// assert_eq!((v.major, v.minor, v.patch), (1, 2, 3));
+    /// assert_eq!(v.pre, Some(Str::new("rc.1")));
+    /// assert!(v < Str::new("1.2.3").parse_semver().unwrap());
+    /// ```
+    pub fn parse_semver(&self) -> Result<SemVer, SemVerError> {
+        let s: &str = self.as_ref();
+        let (s, build) = match s.split_once('+') {
+            Some((s, build)) => (s, Some(build)),
+            None => (s, None),
+        };
+        let (core, pre) = match s.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (s, None),
+        };
+
+        let mut numbers = core.split('.');
+        let (major, minor, patch) = match (
+            numbers.next(),
+            numbers.next(),
+            numbers.next(),
+            numbers.next(),
+        ) {
+            (Some(major), Some(minor), Some(patch), None) => (
+                parse_number(major)?,
+                parse_number(minor)?,
+                parse_number(patch)?,
+            ),
+            _ => return Err(SemVerError::InvalidCore),
+        };
+
+        if let Some(pre) = pre {
+            if !valid_identifiers(pre, false) {
+                return Err(SemVerError::InvalidPreRelease);
+            }
+        }
+        if let Some(build) = build {
+            if !valid_identifiers(build, true) {
+                return Err(SemVerError::InvalidBuild);
+            }
+        }
+
+        Ok(SemVer {
+            major,
+            minor,
+            patch,
+            pre: pre.map(Str::new),
+            build: build.map(Str::new),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn semver(s: &str) -> SemVer {
+        Str::new(s).parse_semver().unwrap()
+    }
+
+    #[test]
+    fn test_parse_semver_valid() {
+        let v = semver("1.2.3");
+        assert_eq!((v.major, v.minor, v.patch), (1, 2, 3));
+        assert_eq!((v.pre, v.build), (None, None));
+
+        let v = semver("0.0.0-alpha-1.0.x+exp.sha.5114f85");
+        assert_eq!((v.major, v.minor, v.patch), (0, 0, 0));
+        assert_eq!(v.pre, Some(Str::new("alpha-1.0.x")));
+        assert_eq!(v.build, Some(Str::new("exp.sha.5114f85")));
+
+        let v = semver("10.20.30+001");
+        assert_eq!(v.pre, None);
+        assert_eq!(v.build, Some(Str::new("001")));
+
+        assert_eq!(semver("4294967295.0.0").major, u32::MAX);
+        assert_eq!(semver("1.0.0-rc.1+b").to_string(), "1.0.0-rc.1+b");
+    }
+
+    #[test]
+    fn test_parse_semver_invalid() {
+        let err = |s: &str| Str::new(s).parse_semver().unwrap_err();
+        assert_eq!(err(""), SemVerError::InvalidCore);
+        assert_eq!(err("1.2"), SemVerError::InvalidCore);
+        assert_eq!(err("1.2.3.4"), SemVerError::InvalidCore);
+        assert_eq!(err("01.2.3"), SemVerError::InvalidCore);
+        assert_eq!(err("1.2.x"), SemVerError::InvalidCore);
+        assert_eq!(err("v1.2.3"), SemVerError::InvalidCore);
+        assert_eq!(err("+1.2.3"), SemVerError::InvalidCore);
+        assert_eq!(err("4294967296.0.0"), SemVerError::NumberOverflow);
+        assert_eq!(err("1.2.3-"), SemVerError::InvalidPreRelease);
+        assert_eq!(err("1.2.3-01"), SemVerError::InvalidPreRelease);
+        assert_eq!(err("1.2.3-a..b"), SemVerError::InvalidPreRelease);
+        assert_eq!(err("1.2.3-a_b"), SemVerError::InvalidPreRelease);
+        assert_eq!(err("1.2.3+"), SemVerError::InvalidBuild);
+        assert_eq!(err("1.2.3+a+b"), SemVerError::InvalidBuild);
+
+        assert!(!Str::new("1.2.3-01").is_valid_semver());
+        assert!(Str::new("1.2.3-0a").is_valid_semver());
+    }
+
+    #[test]
+    fn test_semver_ordering() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0",
+            "2.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(semver(pair[0]) < semver(pair[1]), "{:?}", pair);
+        }
+
+        let mut shuffled: Vec<SemVer> =
+            ordered.iter().rev().map(|s| semver(s)).collect();
+        shuffled.sort();
+        let sorted: Vec<String> =
+            shuffled.iter().map(ToString::to_string).collect();
+        assert_eq!(sorted, ordered);
+    }
+
+    #[test]
+    fn test_semver_build_does_not_affect_precedence() {
+        let a = semver("1.0.0+a");
+        let b = semver("1.0.0+b");
+        assert_ne!(a, b);
+        assert!(a < b);
+        assert!(semver("1.0.0-rc.1+z") < semver("1.0.0+a"));
+    }
+}
@@ -0,0 +1,638 @@
+use std::collections::HashMap;
+
+use crate::Str;
+
+/// Splits `s` into words made of letters and apostrophes, so that
+/// punctuation and hyphens separate words.
+fn letter_words(s: &str) -> impl Iterator<Item = &str> {
+    s.split(|c: char| !c.is_alphabetic() && c != '\'')
+        .filter(|word| word.chars().any(char::is_alphabetic))
+}
+
+/// Returns `true` if `c` is a vowel for the purpose of counting syllables.
+#[inline]
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u' | b'y')
+}
+
+/// Estimates the number of syllables of a single English word.
+fn word_syllables(word: &str) -> usize {
+    let letters: Vec<u8> = word
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_lowercase())
+        .collect();
+    if letters.len() <= 3 {
+        return 1;
+    }
+
+    // A final "e", "es" or "ed" is usually silent, as in "make", "makes"
+    // and "named", except in "-le" ("table"), "-ted" and "-ded" ("wanted").
+    let mut end = letters.len();
+    if letters.ends_with(b"es") || letters.ends_with(b"ed") {
+        let voiced = letters.ends_with(b"ted") || letters.ends_with(b"ded");
+        if !is_vowel(letters[end - 3]) && !voiced {
+            end -= 2;
+        }
+    } else if letters.ends_with(b"e") {
+        let before = letters[end - 2];
+        if !is_vowel(before) && before != b'l' {
+            end -= 1;
+        }
+    }
+    // A leading "y" is a consonant, as in "yellow".
+    let start = usize::from(letters[0] == b'y');
+
+    let groups = letters[start..end]
+        .split(|&c| !is_vowel(c))
+        .filter(|group| !group.is_empty())
+        .count();
+    groups.max(1)
+}
+
+/// Abbreviations whose period does not end a sentence, lower cased and
+/// without their final period.
+const ABBREVIATIONS: [&str; 14] = [
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "mt", "vs", "etc",
+    "e.g", "i.e", "inc",
+];
+
+/// Returns `true` if the period after `word` marks an abbreviation or an
+/// initial rather than the end of a sentence.
+fn is_abbreviation(word: &str) -> bool {
+    let mut chars = word.chars();
+    let first = chars.next();
+    let initial =
+        chars.next().is_none() && first.is_some_and(char::is_uppercase);
+    initial
+        || ABBREVIATIONS
+            .iter()
+            .any(|abbreviation| word.eq_ignore_ascii_case(abbreviation))
+}
+
+/// Returns the byte offset of the end of every sentence in `s`, as
+/// described by [`Str::sentence_count`]. Text after the last sentence end
+/// ends at the last non-whitespace character.
+fn sentence_ends(s: &str) -> Vec<usize> {
+    let mut ends = Vec::new();
+    let mut pending = false;
+    let mut last = 0;
+    while let Some(found) = s[last..].find(['.', '!', '?']) {
+        let at = last + found;
+        let end = s[at..]
+            .find(|c| !matches!(c, '.' | '!' | '?'))
+            .map_or(s.len(), |len| at + len);
+        pending |= s[last..at].chars().any(char::is_alphanumeric);
+        last = end;
+
+        let word = s[..at]
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or("")
+            .trim_start_matches(|c: char| !c.is_alphanumeric());
+        let abbreviation = &s[at..end] == "." && is_abbreviation(word);
+        let ends_here = s[end..].chars().next().is_none_or(char::is_whitespace);
+        if ends_here && pending && !abbreviation {
+            ends.push(end);
+            pending = false;
+        }
+    }
+    if s[last..].chars().any(char::is_alphanumeric) {
+        ends.push(s.trim_end().len());
+    }
+    ends
+}
+
+impl Str {
+    /// Estimates the number of syllables in the string, read as English
+    /// text.
+    ///
+    /// Each word is counted separately: groups of adjacent vowels (including
+    /// `y`) are counted, ignoring a silent final `e`, `es` or `ed`, and every
+    /// word has at least one syllable. This is a heuristic that is right for
+    /// most common words but not all of them, and it is meaningless for
+    /// other languages.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("beautiful").estimate_syllables(), 3);
+    /// assert_eq!(Str::new("The table is named.").estimate_syllables(), 5);
+    /// ```
+    pub fn estimate_syllables(&self) -> usize {
+        letter_words(self.as_ref()).map(word_syllables).sum()
+    }
+
+    /// Returns the Flesch-Kincaid grade level of the string, read as
+    /// English text: roughly the number of years of schooling needed to
+    /// understand it.
+    ///
+    /// The score is `0.39 * words / sentences + 11.8 * syllables / words -
+    /// 15.59`, where sentences are split on `.`, `?` and `!` and syllables
+    /// are counted with [`Str::estimate_syllables`]. Very simple text can
+    /// score below zero. Returns 0.0 if the string has no words.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let simple = Str::new("The cat sat on the mat. It was fat.");
+    /// let complex = Str::new("Readability formulas estimate comprehension difficulty.");
+    /// assert!(simple.flesch_kincaid_grade_level() < 1.0);
+    /// assert!(complex.flesch_kincaid_grade_level() > 12.0);
+    /// ```
+    pub fn flesch_kincaid_grade_level(&self) -> f64 {
+        let s: &str = self.as_ref();
+        let words = letter_words(s).count();
+        if words == 0 {
+            return 0.0;
+        }
+        let sentences = s
+            .split(['.', '?', '!'])
+            .filter(|sentence| letter_words(sentence).next().is_some())
+            .count();
+        let syllables = self.estimate_syllables();
+
+        0.39 * words as f64 / sentences as f64
+            + 11.8 * syllables as f64 / words as f64
+            - 15.59
+    }
+
+    /// Returns the number of words in the string, where words are separated
+    /// by whitespace.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("  one two\tthree\n").word_count(), 3);
+    /// ```
+    #[inline]
+    pub fn word_count(&self) -> usize {
+        self.as_ref().split_whitespace().count()
+    }
+
+    /// Estimates how long the string takes to read at `words_per_minute`.
+    ///
+    /// Words are counted with [`Str::word_count`]. Any text takes at least
+    /// one second, while a string without words takes [`Duration::ZERO`].
+    ///
+    /// # Panics
+    /// Panics if `words_per_minute` is not positive and finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// # use std::time::Duration;
+    /// let post = Str::new(&"word ".repeat(450));
+    /// assert_eq!(post.reading_time_estimate(300.0), Duration::from_secs(90));
+    /// ```
+    ///
+    /// [`Duration::ZERO`]: std::time::Duration::ZERO
+    pub fn reading_time_estimate(
+        &self,
+        words_per_minute: f64,
+    ) -> std::time::Duration {
+        assert!(
+            words_per_minute > 0.0 && words_per_minute.is_finite(),
+            "words per minute must be positive"
+        );
+        let words = self.word_count();
+        if words == 0 {
+            return std::time::Duration::ZERO;
+        }
+        let secs = words as f64 * 60.0 / words_per_minute;
+        std::time::Duration::from_secs_f64(secs.max(1.0))
+    }
+
+    /// Returns how many times each character occurs in the string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let counts = Str::new("hello").frequency_map();
+    /// assert_eq!(counts[&'l'], 2);
+    /// assert_eq!(counts.len(), 4);
+    /// ```
+    pub fn frequency_map(&self) -> HashMap<char, usize> {
+        let mut counts = HashMap::new();
+        for c in self.as_ref().chars() {
+            *counts.entry(c).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Returns the Shannon entropy of the characters of the string, in bits
+    /// per character.
+    ///
+    /// The entropy is `-sum(p * log2(p))` over the frequency `p` of each
+    /// distinct character, as counted by [`Str::frequency_map`]. It is 0.0
+    /// when all characters are the same, and for an empty string, and
+    /// `log2(n)` when `n` distinct characters occur equally often: at most
+    /// about 6.57 for the 95 printable ASCII characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("aaaa").shannon_entropy(), 0.0);
+    /// assert_eq!(Str::new("abab").shannon_entropy(), 1.0);
+    /// ```
+    pub fn shannon_entropy(&self) -> f64 {
+        let counts = self.frequency_map();
+        let total: usize = counts.values().sum();
+        let entropy: f64 = counts
+            .values()
+            .map(|&count| {
+                let p = count as f64 / total as f64;
+                -p * p.log2()
+            })
+            .sum();
+        // A single character gives -0.0.
+        entropy.abs()
+    }
+
+    /// Counts the words of the string, ignoring case and punctuation.
+    ///
+    /// The string is lower cased, punctuation and symbols are removed, so
+    /// `"don't"` counts as `"dont"`, and the rest is split on whitespace.
+    /// Every word is counted, including stop words such as `"the"`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let counts = Str::new("The cat saw the dog.").word_frequencies();
+    /// assert_eq!(counts["the"], 2);
+    /// assert_eq!(counts["dog"], 1);
+    /// ```
+    pub fn word_frequencies(&self) -> HashMap<Str, usize> {
+        let text: String = self
+            .as_ref()
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+            .collect();
+
+        let mut counts = HashMap::new();
+        for word in text.split_whitespace() {
+            match counts.get_mut(word) {
+                Some(count) => *count += 1,
+                None => {
+                    counts.insert(Str::new(word), 1);
+                }
+            }
+        }
+        counts
+    }
+
+    /// Returns the `n` most frequent words as counted by
+    /// [`Str::word_frequencies`], most frequent first, with ties in
+    /// alphabetical order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let top = Str::new("b a b c a b").top_n_words(2);
+    /// assert_eq!(top, [(Str::new("b"), 3), (Str::new("a"), 2)]);
+    /// ```
+    pub fn top_n_words(&self, n: usize) -> Vec<(Str, usize)> {
+        let mut words: Vec<(Str, usize)> =
+            self.word_frequencies().into_iter().collect();
+        words.sort_by(|(a, a_count), (b, b_count)| {
+            b_count
+                .cmp(a_count)
+                .then_with(|| a.as_ref().cmp(b.as_ref()))
+        });
+        words.truncate(n);
+        words
+    }
+
+    /// Estimates the number of sentences in the string.
+    ///
+    /// A run of `.`, `!` and `?` followed by whitespace or the end of the
+    /// string ends a sentence, unless it is a single period after a common
+    /// abbreviation such as `Mr` or `e.g`, or after an initial such as the
+    /// `J` in `J. Smith`. Text after the last sentence end counts as one
+    /// more sentence.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("Mr. Smith arrived. Did he stay? No!");
+    /// assert_eq!(s.sentence_count(), 3);
+    /// ```
+    pub fn sentence_count(&self) -> usize {
+        sentence_ends(self.as_ref()).len()
+    }
+
+    /// Returns the first `n` sentences of the string, found as described by
+    /// [`Str::sentence_count`], without leading or trailing whitespace.
+    ///
+    /// The whole string is returned, trimmed, if it has no more than `n`
+    /// sentences. Use [`Str::truncate_to_sentences_str`] to keep the
+    /// leading whitespace.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("  Mr. Smith arrived. Did he stay? No!");
+    /// assert_eq!(s.first_n_sentences(1), "Mr. Smith arrived.");
+    /// assert_eq!(s.first_n_sentences(2), "Mr. Smith arrived. Did he stay?");
+    /// ```
+    pub fn first_n_sentences(&self, n: usize) -> &str {
+        self.truncate_to_sentences_str(n).trim_start()
+    }
+
+    /// Cuts the string after its first `n` sentences, found as described by
+    /// [`Str::sentence_count`], returning a prefix of the string.
+    ///
+    /// The prefix ends right after the punctuation ending the last sentence
+    /// it keeps, or at the last non-whitespace character if the string has
+    /// no more than `n` sentences.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("  One. Two. Three.");
+    /// assert_eq!(s.truncate_to_sentences_str(2), "  One. Two.");
+    /// assert_eq!(s.truncate_to_sentences_str(0), "");
+    /// ```
+    pub fn truncate_to_sentences_str(&self, n: usize) -> &str {
+        let s: &str = self.as_ref();
+        let ends = sentence_ends(s);
+        let end = match n {
+            0 => 0,
+            _ => ends.get(n - 1).or(ends.last()).copied().unwrap_or(0),
+        };
+        &s[..end]
+    }
+
+    /// Returns the number of paragraphs in the string: blocks of non-blank
+    /// lines separated by one or more blank lines.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("First paragraph.\n\nSecond one,\nwrapped.\n");
+    /// assert_eq!(s.paragraph_count(), 2);
+    /// ```
+    pub fn paragraph_count(&self) -> usize {
+        let mut count = 0;
+        let mut in_paragraph = false;
+        for line in self.as_ref().lines() {
+            let blank = line.trim().is_empty();
+            if !blank && !in_paragraph {
+                count += 1;
+            }
+            in_paragraph = !blank;
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn syllables(s: &str) -> usize {
+        Str::new(s).estimate_syllables()
+    }
+
+    #[test]
+    fn test_estimate_syllables_words() {
+        let words = [
+            ("cat", 1),
+            ("the", 1),
+            ("strength", 1),
+            ("make", 1),
+            ("makes", 1),
+            ("named", 1),
+            ("queue", 1),
+            ("table", 2),
+            ("hello", 2),
+            ("yellow", 2),
+            ("wanted", 2),
+            ("python", 2),
+            ("beautiful", 3),
+            ("syllable", 3),
+            ("computer", 3),
+            ("education", 4),
+            ("readability", 5),
+            ("university", 5),
+        ];
+        for (word, expected) in words {
+            assert_eq!(syllables(word), expected, "{}", word);
+        }
+        assert_eq!(syllables("Beautiful"), 3);
+    }
+
+    #[test]
+    fn test_estimate_syllables_text() {
+        assert_eq!(syllables(""), 0);
+        assert_eq!(syllables("  42 ... -- "), 0);
+        assert_eq!(syllables("Hello, beautiful world!"), 6);
+        assert_eq!(syllables("well-known"), 2);
+        assert_eq!(syllables("don't"), 1);
+    }
+
+    #[test]
+    fn test_flesch_kincaid_grade_level() {
+        let grade = |s: &str| Str::new(s).flesch_kincaid_grade_level();
+        let expected = 0.39 * 6.0 + 11.8 * 1.0 - 15.59;
+        assert!((grade("The cat sat on the mat.") - expected).abs() < 1e-9);
+        let unterminated = grade("The cat sat on the mat");
+        assert!((unterminated - expected).abs() < 1e-9);
+
+        let two = grade("The cat sat. The dog ran? A bird flew!");
+        let one = grade("The cat sat and the dog ran and a bird flew.");
+        assert!(two < one);
+        assert_eq!(grade(""), 0.0);
+        assert_eq!(grade("... ?!"), 0.0);
+    }
+
+    #[test]
+    fn test_word_count() {
+        assert_eq!(Str::new("").word_count(), 0);
+        assert_eq!(Str::new(" \n\t ").word_count(), 0);
+        assert_eq!(Str::new("one").word_count(), 1);
+        assert_eq!(Str::new("Hello, world! It's 9am.").word_count(), 4);
+        assert_eq!(Str::new("世界 été\u{3000}done").word_count(), 3);
+    }
+
+    #[test]
+    fn test_reading_time_estimate() {
+        let text = Str::new(&"word ".repeat(300));
+        assert_eq!(text.reading_time_estimate(300.0), Duration::from_secs(60));
+        assert_eq!(text.reading_time_estimate(200.0), Duration::from_secs(90));
+        assert_eq!(
+            Str::new("one").reading_time_estimate(300.0),
+            Duration::from_secs(1)
+        );
+        assert_eq!(Str::new("").reading_time_estimate(300.0), Duration::ZERO);
+        assert_eq!(Str::new("  ").reading_time_estimate(1.0), Duration::ZERO);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reading_time_estimate_zero_speed() {
+        Str::new("text").reading_time_estimate(0.0);
+    }
+
+    #[test]
+    fn test_frequency_map() {
+        let counts = Str::new("a世a界a世").frequency_map();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&'a'], 3);
+        assert_eq!(counts[&'世'], 2);
+        assert_eq!(counts[&'界'], 1);
+        assert!(Str::new("").frequency_map().is_empty());
+    }
+
+    #[test]
+    fn test_shannon_entropy() {
+        let entropy = |s: &str| Str::new(s).shannon_entropy();
+        assert_eq!(entropy(""), 0.0);
+        assert_eq!(entropy("z"), 0.0);
+        assert_eq!(entropy("zzzzzz"), 0.0);
+        assert_eq!(entropy("abababab"), 1.0);
+        assert_eq!(entropy("abcd"), 2.0);
+        assert_eq!(entropy("dcbadcba"), 2.0);
+        assert!((entropy("aab") - 0.9182958340544896).abs() < 1e-12);
+        assert!(entropy("password1") < entropy("x7#Qm!2vL"));
+    }
+
+    #[test]
+    fn test_shannon_entropy_uniform() {
+        let printable: String = (' '..='~').collect();
+        assert_eq!(printable.len(), 95);
+        let expected = 95f64.log2();
+        assert!(
+            (Str::new(&printable).shannon_entropy() - expected).abs() < 1e-12
+        );
+
+        for n in [2u32, 3, 10, 64] {
+            let text: String = (0..n)
+                .map(|i| char::from_u32(0x4e00 + i).unwrap())
+                .collect();
+            let entropy = Str::new(&text.repeat(3)).shannon_entropy();
+            assert!((entropy - (n as f64).log2()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_word_frequencies() {
+        let s = Str::new("The quick fox. THE lazy dog, and the fox!");
+        let counts = s.word_frequencies();
+        assert_eq!(counts.len(), 6);
+        assert_eq!(counts["the"], 3);
+        assert_eq!(counts["fox"], 2);
+        assert_eq!(counts["and"], 1);
+        assert_eq!(counts["dog"], 1);
+        assert!(!counts.contains_key("dog,"));
+
+        let counts = Str::new("Don't stop; don't — 'stop'").word_frequencies();
+        assert_eq!(counts["dont"], 2);
+        assert_eq!(counts["stop"], 2);
+        assert_eq!(counts.len(), 2);
+        assert!(Str::new(" ... !? ").word_frequencies().is_empty());
+    }
+
+    #[test]
+    fn test_top_n_words() {
+        let s = Str::new("a b c a b a d d d c e");
+        let top = s.top_n_words(3);
+        assert_eq!(
+            top,
+            [(Str::new("a"), 3), (Str::new("d"), 3), (Str::new("b"), 2)]
+        );
+        assert_eq!(s.top_n_words(0), []);
+        assert_eq!(s.top_n_words(100).len(), 5);
+        assert_eq!(s.top_n_words(100)[4], (Str::new("e"), 1));
+        assert!(Str::new("").top_n_words(3).is_empty());
+    }
+
+    #[test]
+    fn test_sentence_count() {
+        let count = |s: &str| Str::new(s).sentence_count();
+        let passage = "It was a bright cold day in April. The clocks were \
+                       striking thirteen! Was it late? Winston hurried home.";
+        assert_eq!(count(passage), 4);
+        assert_eq!(count("One. Two. Three"), 3);
+        assert_eq!(count("Wait... what?! Really?"), 3);
+        assert_eq!(count("No terminator"), 1);
+        assert_eq!(count("The value is 3.14 today."), 1);
+        assert_eq!(count("See example.com for more."), 1);
+        assert_eq!(count(""), 0);
+        assert_eq!(count("  ...  "), 0);
+    }
+
+    #[test]
+    fn test_sentence_count_abbreviations() {
+        let count = |s: &str| Str::new(s).sentence_count();
+        assert_eq!(count("Mr. Smith met Dr. Jones. They talked."), 2);
+        assert_eq!(count("Use a tool, e.g. a hammer. Done."), 2);
+        assert_eq!(count("J. R. R. Tolkien wrote it."), 1);
+        assert_eq!(count("Ask Mr. Smith!"), 1);
+        assert_eq!(count("It costs less, etc. and so on. Then more."), 2);
+    }
+
+    #[test]
+    fn test_paragraph_count() {
+        let count = |s: &str| Str::new(s).paragraph_count();
+        assert_eq!(count("A single\nparagraph over\nthree lines."), 1);
+        assert_eq!(count("One.\n\nTwo.\n\n\n  \nThree."), 3);
+        assert_eq!(count("\n\nLeading and trailing.\n\n"), 1);
+        assert_eq!(count("Windows\r\n\r\nline endings"), 2);
+        assert_eq!(count(""), 0);
+        assert_eq!(count(" \n\t\n"), 0);
+    }
+
+    #[test]
+    fn test_first_n_sentences() {
+        let s = Str::new(
+            "It was a bright cold day in April. The clocks were striking \
+             thirteen! Was it late?\nWinston hurried home ",
+        );
+        assert_eq!(s.first_n_sentences(0), "");
+        assert_eq!(
+            s.first_n_sentences(1),
+            "It was a bright cold day in April."
+        );
+        assert_eq!(
+            s.first_n_sentences(2),
+            "It was a bright cold day in April. The clocks were striking \
+             thirteen!"
+        );
+        assert_eq!(s.first_n_sentences(4), s.as_ref().trim_end());
+        assert_eq!(s.first_n_sentences(10), s.as_ref().trim_end());
+
+        let s = Str::new("\n  Dr. Jones left, e.g. early. Wait... what?! ");
+        assert_eq!(s.first_n_sentences(1), "Dr. Jones left, e.g. early.");
+        assert_eq!(
+            s.first_n_sentences(2),
+            "Dr. Jones left, e.g. early. Wait..."
+        );
+        assert_eq!(
+            s.first_n_sentences(5),
+            "Dr. Jones left, e.g. early. Wait... what?!"
+        );
+        assert_eq!(Str::new("").first_n_sentences(1), "");
+        assert_eq!(Str::new("  ...  ").first_n_sentences(1), "");
+    }
+
+    #[test]
+    fn test_truncate_to_sentences_str() {
+        let s = Str::new("  One. Two!  Three?  ");
+        assert_eq!(s.truncate_to_sentences_str(0), "");
+        assert_eq!(s.truncate_to_sentences_str(1), "  One.");
+        assert_eq!(s.truncate_to_sentences_str(2), "  One. Two!");
+        assert_eq!(s.truncate_to_sentences_str(3), "  One. Two!  Three?");
+        assert_eq!(s.truncate_to_sentences_str(4), "  One. Two!  Three?");
+
+        let s = Str::new("The value is 3.14 today. Then more");
+        for n in 0..4 {
+            let prefix = s.truncate_to_sentences_str(n);
+            assert!(s.as_ref().starts_with(prefix));
+            assert_eq!(Str::new(prefix).sentence_count(), n.min(2));
+        }
+        assert_eq!(s.truncate_to_sentences_str(1), "The value is 3.14 today.");
+    }
+}
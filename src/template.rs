@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::Str;
+
+/// Error returned by [`Str::parse_template`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// The `${` at the given byte offset has no closing `}`.
+    Unclosed(usize),
+    /// The `${}` at the given byte offset names no variable.
+    EmptyName(usize),
+    /// The variable has no value.
+    MissingVariable(String),
+}
+
+impl std::fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unclosed(at) => write!(f, "unclosed `${{` at byte {}", at),
+            Self::EmptyName(at) => {
+                write!(f, "empty variable name at byte {}", at)
+            }
+            Self::MissingVariable(name) => {
+                write!(f, "template variable {} has no value", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Error returned by [`Str::from_template_file`].
+#[derive(Debug)]
+pub enum TemplateFileError {
+    /// The file could not be read or is not valid UTF-8.
+    Io(std::io::Error),
+    /// The contents of the file are not a valid template for the variables.
+    Template(TemplateError),
+}
+
+impl std::fmt::Display for TemplateFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "cannot read template file: {}", err),
+            Self::Template(err) => write!(f, "invalid template file: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for TemplateFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Template(err) => Some(err),
+        }
+    }
+}
+
+/// Error returned by [`Str::from_env_interpolated`] and
+/// [`Str::from_env_interpolated_with_defaults`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvInterpolationError {
+    /// The `${` at the given byte offset has no closing `}`.
+    Unclosed(usize),
+    /// The `${}` at the given byte offset names no variable.
+    EmptyName(usize),
+    /// The variable is not set and has no default.
+    MissingVariable(String),
+    /// The value of the variable is not valid Unicode.
+    NotUnicode(String),
+}
+
+impl std::fmt::Display for EnvInterpolationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unclosed(at) => write!(f, "unclosed `${{` at byte {}", at),
+            Self::EmptyName(at) => {
+                write!(f, "empty variable name at byte {}", at)
+            }
+            Self::MissingVariable(name) => {
+                write!(f, "environment variable {} is not set", name)
+            }
+            Self::NotUnicode(name) => {
+                write!(f, "environment variable {} is not valid Unicode", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EnvInterpolationError {}
+
+impl From<TemplateError> for EnvInterpolationError {
+    fn from(err: TemplateError) -> Self {
+        match err {
+            TemplateError::Unclosed(at) => Self::Unclosed(at),
+            TemplateError::EmptyName(at) => Self::EmptyName(at),
+            TemplateError::MissingVariable(name) => Self::MissingVariable(name),
+        }
+    }
+}
+
+/// Replaces every `${NAME}` in `template` with the value `lookup` returns
+/// for `NAME`.
+fn interpolate<E: From<TemplateError>>(
+    template: &str,
+    lookup: impl Fn(&str) -> Result<Option<String>, E>,
+) -> Result<Str, E> {
+    let mut out = String::with_capacity(template.len());
+    let mut last = 0;
+    while let Some(found) = template[last..].find("${") {
+        let start = last + found;
+        let name_start = start + 2;
+        let name_len = template[name_start..]
+            .find('}')
+            .ok_or(TemplateError::Unclosed(start))?;
+        let name = &template[name_start..name_start + name_len];
+        if name.is_empty() {
+            return Err(TemplateError::EmptyName(start).into());
+        }
+
+        let value = lookup(name)?
+            .ok_or_else(|| TemplateError::MissingVariable(name.to_string()))?;
+        out.push_str(&template[last..start]);
+        out.push_str(&value);
+        last = name_start + name_len + 1;
+    }
+    out.push_str(&template[last..]);
+    Ok(Str::new(&out))
+}
+
+/// Reads the environment variable `name`, returning `None` if it is not
+/// set.
+fn env_var(name: &str) -> Result<Option<String>, EnvInterpolationError> {
+    match std::env::var(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => {
+            Err(EnvInterpolationError::NotUnicode(name.to_string()))
+        }
+    }
+}
+
+impl Str {
+    /// Expands every `${NAME}` in `template` with the value of the
+    /// environment variable `NAME`.
+    ///
+    /// Values are inserted as they are and not expanded again. A `$` that
+    /// is not followed by `{` is kept literally.
+    ///
+    /// # Errors
+    /// Returns an [`EnvInterpolationError`] if a `${` is not closed, names
+    /// no variable, or names a variable that is not set or not valid
+    /// Unicode.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::{EnvInterpolationError, Str};
+    /// std::env::set_var("OSTR_DOC_HOME", "/home/ostr");
+    /// let path = Str::from_env_interpolated("${OSTR_DOC_HOME}/.config");
+    /// assert_eq!(path.unwrap().as_ref(), "/home/ostr/.config");
+    ///
+    /// let missing = Str::from_env_interpolated("${OSTR_DOC_UNSET}");
+    /// assert_eq!(
+    ///     missing,
+    ///     Err(EnvInterpolationError::MissingVariable("OSTR_DOC_UNSET".to_string())),
+    /// );
+    /// ```
+    pub fn from_env_interpolated(
+        template: &str,
+    ) -> Result<Str, EnvInterpolationError> {
+        interpolate(template, env_var)
+    }
+
+    /// Expands every `${NAME}` in `template` like
+    /// [`Str::from_env_interpolated`], using the value paired with `NAME` in
+    /// `defaults` when the environment variable is not set.
+    ///
+    /// # Errors
+    /// Returns an [`EnvInterpolationError`] if a `${` is not closed or names
+    /// no variable, if a variable is neither set nor in `defaults`, or if
+    /// its value is not valid Unicode.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// std::env::remove_var("OSTR_DOC_PORT");
+    /// let url = Str::from_env_interpolated_with_defaults(
+    ///     "localhost:${OSTR_DOC_PORT}",
+    ///     &[("OSTR_DOC_PORT", "8080")],
+    /// );
+    /// assert_eq!(url.unwrap().as_ref(), "localhost:8080");
+    /// ```
+    pub fn from_env_interpolated_with_defaults(
+        template: &str,
+        defaults: &[(&str, &str)],
+    ) -> Result<Str, EnvInterpolationError> {
+        interpolate(template, |name| {
+            Ok(env_var(name)?.or_else(|| {
+                defaults
+                    .iter()
+                    .find(|&&(default, _)| default == name)
+                    .map(|&(_, value)| value.to_string())
+            }))
+        })
+    }
+
+    /// Expands every `${NAME}` in the string with the value of `NAME` in
+    /// `vars`.
+    ///
+    /// Values are inserted as they are and not expanded again. A `$` that
+    /// is not followed by `{` is kept literally.
+    ///
+    /// # Errors
+    /// Returns a [`TemplateError`] if a `${` is not closed, names no
+    /// variable, or names a variable missing from `vars`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use ostr::{Str, TemplateError};
+    /// let vars = HashMap::from([("name", "ostr")]);
+    /// let s = Str::new("Hello, ${name}!").parse_template(&vars);
+    /// assert_eq!(s.unwrap().as_ref(), "Hello, ostr!");
+    ///
+    /// let s = Str::new("${greeting}").parse_template(&vars);
+    /// assert_eq!(s, Err(TemplateError::MissingVariable("greeting".to_string())));
+    /// ```
+    pub fn parse_template(
+        &self,
+        vars: &HashMap<&str, &str>,
+    ) -> Result<Str, TemplateError> {
+        interpolate(self.as_ref(), |name| {
+            Ok(vars.get(name).map(|value| value.to_string()))
+        })
+    }
+
+    /// Reads the UTF-8 file at `path` and expands it as a template with
+    /// [`Str::parse_template`].
+    ///
+    /// # Errors
+    /// Returns [`TemplateFileError::Io`] if the file cannot be read or is
+    /// not valid UTF-8, and [`TemplateFileError::Template`] if its contents
+    /// cannot be expanded with `vars`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use ostr::Str;
+    /// let name = format!("ostr_doc_template_{}.txt", std::process::id());
+    /// let path = std::env::temp_dir().join(name);
+    /// std::fs::write(&path, "port = ${port}\n").unwrap();
+    ///
+    /// let vars = HashMap::from([("port", "8080")]);
+    /// let config = Str::from_template_file(&path, &vars).unwrap();
+    /// assert_eq!(config.as_ref(), "port = 8080\n");
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn from_template_file(
+        path: impl AsRef<Path>,
+        vars: &HashMap<&str, &str>,
+    ) -> Result<Str, TemplateFileError> {
+        let template =
+            std::fs::read_to_string(path).map_err(TemplateFileError::Io)?;
+        Str::new(&template)
+            .parse_template(vars)
+            .map_err(TemplateFileError::Template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup_in<'a>(
+        vars: &'a [(&str, &str)],
+    ) -> impl Fn(&str) -> Result<Option<String>, EnvInterpolationError> + 'a
+    {
+        |name| {
+            Ok(vars
+                .iter()
+                .find(|&&(var, _)| var == name)
+                .map(|&(_, value)| value.to_string()))
+        }
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let vars = [("USER", "ostr"), ("EMPTY", ""), ("NESTED", "${USER}")];
+        let expand = |s: &str| interpolate(s, lookup_in(&vars)).unwrap();
+        assert_eq!(expand("hi ${USER}!").as_ref(), "hi ostr!");
+        assert_eq!(expand("${USER}${USER}").as_ref(), "ostrostr");
+        assert_eq!(expand("[${EMPTY}]").as_ref(), "[]");
+        assert_eq!(expand("${NESTED}").as_ref(), "${USER}");
+        assert_eq!(expand("cost: $5, {x}").as_ref(), "cost: $5, {x}");
+        assert_eq!(expand("").as_ref(), "");
+    }
+
+    #[test]
+    fn test_interpolate_errors() {
+        let vars = [("USER", "ostr")];
+        let expand = |s: &str| interpolate(s, lookup_in(&vars));
+        assert_eq!(
+            expand("a ${MISSING} b"),
+            Err(EnvInterpolationError::MissingVariable(
+                "MISSING".to_string()
+            ))
+        );
+        assert_eq!(
+            expand("ok ${USER"),
+            Err(EnvInterpolationError::Unclosed(3))
+        );
+        assert_eq!(expand("世${}"), Err(EnvInterpolationError::EmptyName(3)));
+
+        let not_unicode = |name: &str| {
+            Err(EnvInterpolationError::NotUnicode(name.to_string()))
+        };
+        assert_eq!(
+            interpolate("x${BINARY}", not_unicode),
+            Err(EnvInterpolationError::NotUnicode("BINARY".to_string()))
+        );
+    }
+
+    /// The only test that changes the environment, as tests run in parallel
+    /// threads and changing it while another thread reads it is a data race.
+    #[test]
+    fn test_from_env_interpolated() {
+        std::env::set_var("OSTR_TEST_USER", "ostr");
+        std::env::set_var("OSTR_TEST_HOST", "example.com");
+        std::env::set_var("OSTR_TEST_REMOVED", "set");
+        std::env::remove_var("OSTR_TEST_REMOVED");
+        std::env::remove_var("OSTR_TEST_PORT");
+
+        assert_eq!(
+            Str::from_env_interpolated("hi ${OSTR_TEST_USER}!")
+                .unwrap()
+                .as_ref(),
+            "hi ostr!"
+        );
+        assert_eq!(
+            Str::from_env_interpolated("a ${OSTR_TEST_REMOVED} b"),
+            Err(EnvInterpolationError::MissingVariable(
+                "OSTR_TEST_REMOVED".to_string()
+            ))
+        );
+
+        let defaults =
+            [("OSTR_TEST_HOST", "localhost"), ("OSTR_TEST_PORT", "8080")];
+        let url = Str::from_env_interpolated_with_defaults(
+            "${OSTR_TEST_HOST}:${OSTR_TEST_PORT}",
+            &defaults,
+        );
+        assert_eq!(url.unwrap().as_ref(), "example.com:8080");
+        assert_eq!(
+            Str::from_env_interpolated_with_defaults(
+                "${OSTR_TEST_REMOVED}://",
+                &defaults
+            ),
+            Err(EnvInterpolationError::MissingVariable(
+                "OSTR_TEST_REMOVED".to_string()
+            ))
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+
+            let value = std::ffi::OsStr::from_bytes(b"\xff");
+            std::env::set_var("OSTR_TEST_BINARY", value);
+            assert_eq!(
+                Str::from_env_interpolated_with_defaults(
+                    "${OSTR_TEST_BINARY}",
+                    &[("OSTR_TEST_BINARY", "default")]
+                ),
+                Err(EnvInterpolationError::NotUnicode(
+                    "OSTR_TEST_BINARY".to_string()
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_template() {
+        let vars = HashMap::from([
+            ("user", "ostr"),
+            ("empty", ""),
+            ("nested", "${user}"),
+        ]);
+        let expand = |s: &str| Str::new(s).parse_template(&vars).unwrap();
+        assert_eq!(expand("hi ${user}!").as_ref(), "hi ostr!");
+        assert_eq!(expand("${user}${user}").as_ref(), "ostrostr");
+        assert_eq!(expand("[${empty}]").as_ref(), "[]");
+        assert_eq!(expand("${nested}").as_ref(), "${user}");
+        assert_eq!(expand("cost: $5, {x}").as_ref(), "cost: $5, {x}");
+        assert_eq!(expand("").as_ref(), "");
+    }
+
+    #[test]
+    fn test_parse_template_errors() {
+        let vars = HashMap::from([("user", "ostr")]);
+        let expand = |s: &str| Str::new(s).parse_template(&vars);
+        assert_eq!(
+            expand("${user} ${USER}"),
+            Err(TemplateError::MissingVariable("USER".to_string()))
+        );
+        assert_eq!(expand("ok ${user"), Err(TemplateError::Unclosed(3)));
+        assert_eq!(expand("世${}"), Err(TemplateError::EmptyName(3)));
+    }
+
+    fn temp_file(name: &str, contents: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "ostr_test_{}_{}",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn test_from_template_file() {
+        let path = temp_file(
+            "template.conf",
+            b"host = ${HOST}\nport = ${PORT}\nurl = ${HOST}:${PORT}\n",
+        );
+        let vars = HashMap::from([("HOST", "localhost"), ("PORT", "8080")]);
+        let config = Str::from_template_file(&path, &vars);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            config.unwrap().as_ref(),
+            "host = localhost\nport = 8080\nurl = localhost:8080\n"
+        );
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn test_from_template_file_errors() {
+        let vars = HashMap::from([("HOST", "localhost")]);
+
+        let path = temp_file("missing_var.conf", b"port = ${PORT}");
+        let result = Str::from_template_file(&path, &vars);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            result,
+            Err(TemplateFileError::Template(TemplateError::MissingVariable(
+                ref name
+            ))) if name == "PORT"
+        ));
+
+        let path = temp_file("binary.conf", b"\xff${HOST}");
+        let result = Str::from_template_file(&path, &vars);
+        std::fs::remove_file(&path).unwrap();
+        match result {
+            Err(TemplateFileError::Io(err)) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::InvalidData)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let path = std::env::temp_dir().join("ostr_test_does_not_exist.conf");
+        match Str::from_template_file(&path, &vars) {
+            Err(TemplateFileError::Io(err)) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::NotFound)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,1198 @@
+use crate::Str;
+
+/// Returns the length of the ANSI CSI sequence at the start of `bytes`, if
+/// there is a complete one.
+fn csi_len(bytes: &[u8]) -> Option<usize> {
+    if !bytes.starts_with(b"\x1b[") {
+        return None;
+    }
+    let mut i = 2;
+    while bytes.get(i).is_some_and(|b| (0x30..=0x3f).contains(b)) {
+        i += 1;
+    }
+    while bytes.get(i).is_some_and(|b| (0x20..=0x2f).contains(b)) {
+        i += 1;
+    }
+    match bytes.get(i) {
+        Some(0x40..=0x7e) => Some(i + 1),
+        _ => None,
+    }
+}
+
+/// Returns `true` if `c` is an emoji: a pictograph, a skin tone modifier or
+/// a regional indicator, as of Unicode 15.0.
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x203C
+            | 0x2049
+            | 0x231A..=0x231B
+            | 0x2328
+            | 0x23CF
+            | 0x23E9..=0x23F3
+            | 0x23F8..=0x23FA
+            | 0x24C2
+            | 0x25AA..=0x25AB
+            | 0x25B6
+            | 0x25C0
+            | 0x25FB..=0x25FE
+            | 0x2600..=0x27BF
+            | 0x2934..=0x2935
+            | 0x2B05..=0x2B07
+            | 0x2B1B..=0x2B1C
+            | 0x2B50
+            | 0x2B55
+            | 0x3030
+            | 0x303D
+            | 0x3297
+            | 0x3299
+            | 0x1F004
+            | 0x1F0CF
+            | 0x1F170..=0x1F251
+            | 0x1F300..=0x1F64F
+            | 0x1F680..=0x1F6FF
+            | 0x1F7E0..=0x1F7EB
+            | 0x1F7F0
+            | 0x1F90C..=0x1F9FF
+            | 0x1FA70..=0x1FAFF
+    )
+}
+
+/// Returns `true` if `c` only has a meaning inside an emoji sequence: a zero
+/// width joiner, the emoji variation selector, the combining keycap or a
+/// tag character.
+fn is_emoji_component(c: char) -> bool {
+    matches!(c as u32, 0x200D | 0xFE0F | 0x20E3 | 0xE0020..=0xE007F)
+}
+
+fn push_pig_latin_word(out: &mut String, word: &str) {
+    let is_vowel = |i: usize, c: char| {
+        matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u')
+            || (i > 0 && c.eq_ignore_ascii_case(&'y'))
+    };
+    let split = word
+        .char_indices()
+        .find(|&(i, c)| is_vowel(i, c))
+        .map(|(i, _)| i)
+        .unwrap_or(word.len());
+    let (head, tail) = word.split_at(split);
+
+    let capitalized = word.starts_with(|c: char| c.is_uppercase());
+    let mut moved = tail.chars().chain(head.chars());
+    if let Some(first) = moved.next() {
+        if capitalized && !head.is_empty() {
+            out.extend(first.to_uppercase());
+            out.extend(moved.map(|c| c.to_ascii_lowercase()));
+        } else {
+            out.push(first);
+            out.extend(moved);
+        }
+    }
+    out.push_str("ay");
+}
+
+/// The characters of the US QWERTY layout, unshifted then shifted, and what
+/// the same keys produce on the US Dvorak layout.
+const QWERTY_KEYS: &str = concat!(
+    "-=qwertyuiop[]asdfghjkl;'zxcvbnm,./",
+    "_+QWERTYUIOP{}ASDFGHJKL:\"ZXCVBNM<>?",
+);
+const DVORAK_KEYS: &str = concat!(
+    "[]',.pyfgcrl/=aoeuidhtns-;qjkxbmwvz",
+    "{}\"<>PYFGCRL?+AOEUIDHTNS_:QJKXBMWVZ",
+);
+
+/// Returns the Dvorak layout as `(qwerty, dvorak)` pairs for
+/// [`Str::remap_keyboard`].
+fn dvorak_layout() -> Vec<(char, char)> {
+    QWERTY_KEYS.chars().zip(DVORAK_KEYS.chars()).collect()
+}
+
+impl Str {
+    /// Returns `true` if the string contains no control characters.
+    ///
+    /// Spaces and other non-control whitespace are printable, while `\n`,
+    /// `\t` and `\0` are not. An empty string is printable.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert!(Str::new("hello world").contains_only_printable());
+    /// assert!(!Str::new("hello\nworld").contains_only_printable());
+    /// ```
+    #[inline]
+    pub fn contains_only_printable(&self) -> bool {
+        !self.as_ref().chars().any(char::is_control)
+    }
+
+    /// Returns `true` if the string contains neither control characters nor
+    /// whitespace. An empty string is visible.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert!(Str::new("hello").contains_only_visible());
+    /// assert!(!Str::new("hello world").contains_only_visible());
+    /// ```
+    #[inline]
+    pub fn contains_only_visible(&self) -> bool {
+        self.as_ref()
+            .chars()
+            .all(|c| !c.is_whitespace() && !c.is_control())
+    }
+
+    /// Replaces the characters at positions `[start_char, end_char)` with
+    /// `mask_char`.
+    ///
+    /// Positions count Unicode scalar values and are clamped to the length
+    /// of the string, so the result always has the same number of
+    /// characters as the original. An empty range returns a copy.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let card = Str::new("1234-5678-9012-3456");
+    /// assert_eq!(card.mask(0, 15, '*').as_ref(), "***************3456");
+    /// ```
+    pub fn mask(
+        &self,
+        start_char: usize,
+        end_char: usize,
+        mask_char: char,
+    ) -> Str {
+        if start_char >= end_char {
+            return self.clone();
+        }
+        let out: String = self
+            .as_ref()
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if (start_char..end_char).contains(&i) {
+                    mask_char
+                } else {
+                    c
+                }
+            })
+            .collect();
+        Str::new(&out)
+    }
+
+    /// Replaces every non-overlapping occurrence of `pattern` with
+    /// `replacement`.
+    ///
+    /// This behaves like [`str::replace`], except that an empty `pattern`
+    /// matches nothing. Redaction only helps if the original value is not
+    /// kept around: drop it as soon as the redacted copy is made, and avoid
+    /// formatting it with [`Display`](std::fmt::Display) or
+    /// [`Debug`](std::fmt::Debug), which makes it easy to end up in logs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let line = Str::new("auth key=sk_live_123 ok");
+    /// assert_eq!(line.redact("sk_live_123", "[REDACTED]").as_ref(), "auth key=[REDACTED] ok");
+    /// ```
+    pub fn redact(&self, pattern: &str, replacement: &str) -> Str {
+        if pattern.is_empty() {
+            return self.clone();
+        }
+        Str::new(&self.as_ref().replace(pattern, replacement))
+    }
+
+    /// Applies all `replacements` at once, so replaced text is never
+    /// matched again by another pattern.
+    ///
+    /// The string is scanned from left to right and at each position the
+    /// longest matching pattern is replaced; among patterns of the same
+    /// length the first one listed wins. Empty patterns are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("a < b && c");
+    /// let escaped = s.multi_replace(&[("&", "&amp;"), ("<", "&lt;")]);
+    /// assert_eq!(escaped.as_ref(), "a &lt; b &amp;&amp; c");
+    /// ```
+    pub fn multi_replace(&self, replacements: &[(&str, &str)]) -> Str {
+        let s: &str = self.as_ref();
+        let mut out = String::with_capacity(s.len());
+        let mut rest = s;
+        while let Some(c) = rest.chars().next() {
+            let matched = replacements
+                .iter()
+                .filter(|(pattern, _)| {
+                    !pattern.is_empty() && rest.starts_with(pattern)
+                })
+                // `max_by_key` returns the last maximum, so iterate in
+                // reverse to let the first listed pattern win ties.
+                .rev()
+                .max_by_key(|(pattern, _)| pattern.len());
+            match matched {
+                Some((pattern, replacement)) => {
+                    out.push_str(replacement);
+                    rest = &rest[pattern.len()..];
+                }
+                None => {
+                    out.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+        Str::new(&out)
+    }
+
+    /// Replaces every character found in the first column of `table` with
+    /// the character next to it, leaving other characters unchanged.
+    ///
+    /// The table is sorted once and then binary searched for each
+    /// character. If a character is listed more than once, its first entry
+    /// is used.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let turkish = [('I', 'ı'), ('i', 'İ')];
+    /// assert_eq!(Str::new("Istanbul is").apply_char_map(&turkish).as_ref(), "ıstanbul İs");
+    /// ```
+    pub fn apply_char_map(&self, table: &[(char, char)]) -> Str {
+        let mut sorted = table.to_vec();
+        sorted.sort_by_key(|&(from, _)| from);
+        sorted.dedup_by_key(|&mut (from, _)| from);
+
+        let out: String = self
+            .as_ref()
+            .chars()
+            .map(
+                |c| match sorted.binary_search_by_key(&c, |&(from, _)| from) {
+                    Ok(i) => sorted[i].1,
+                    Err(_) => c,
+                },
+            )
+            .collect();
+        Str::new(&out)
+    }
+
+    /// Removes every occurrence of the characters in `chars_to_remove`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("(555) 123-4567");
+    /// assert_eq!(s.apply_char_removal(&['(', ')', ' ', '-']).as_ref(), "5551234567");
+    /// ```
+    pub fn apply_char_removal(&self, chars_to_remove: &[char]) -> Str {
+        let mut sorted = chars_to_remove.to_vec();
+        sorted.sort_unstable();
+
+        let out: String = self
+            .as_ref()
+            .chars()
+            .filter(|c| sorted.binary_search(c).is_err())
+            .collect();
+        Str::new(&out)
+    }
+
+    /// Wraps every occurrence of `pattern` between `open_tag` and
+    /// `close_tag`, such as `<b>` and `</b>` or ANSI color codes.
+    ///
+    /// Occurrences are found from left to right and do not overlap. An empty
+    /// `pattern` matches every character, so each one is wrapped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("to be or not to be");
+    /// assert_eq!(
+    ///     s.highlight_matches("be", "<b>", "</b>").as_ref(),
+    ///     "to <b>be</b> or not to <b>be</b>",
+    /// );
+    /// ```
+    pub fn highlight_matches(
+        &self,
+        pattern: &str,
+        open_tag: &str,
+        close_tag: &str,
+    ) -> Str {
+        let s: &str = self.as_ref();
+        let mut out = String::with_capacity(s.len());
+        let wrap = |out: &mut String, text: &str| {
+            out.push_str(open_tag);
+            out.push_str(text);
+            out.push_str(close_tag);
+        };
+
+        if pattern.is_empty() {
+            let mut buf = [0; 4];
+            for c in s.chars() {
+                wrap(&mut out, c.encode_utf8(&mut buf));
+            }
+            return Str::new(&out);
+        }
+
+        let mut last = 0;
+        for (at, matched) in s.match_indices(pattern) {
+            out.push_str(&s[last..at]);
+            wrap(&mut out, matched);
+            last = at + matched.len();
+        }
+        out.push_str(&s[last..]);
+        Str::new(&out)
+    }
+
+    /// Converts text typed with one keyboard layout to what the same keys
+    /// produce with another.
+    ///
+    /// A layout is given as `(qwerty, key)` pairs, mapping each character of
+    /// the US QWERTY layout to the character on the same key. The inverse
+    /// of `from_layout` is applied first, then `to_layout`; characters not
+    /// listed in a layout are taken to be the same as on QWERTY. An empty
+    /// slice therefore stands for QWERTY itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let azerty = [('q', 'a'), ('a', 'q'), ('w', 'z'), ('z', 'w')];
+    /// let s = Str::new("zqw");
+    /// assert_eq!(s.remap_keyboard(&azerty, &[]).as_ref(), "waz");
+    /// ```
+    pub fn remap_keyboard(
+        &self,
+        from_layout: &[(char, char)],
+        to_layout: &[(char, char)],
+    ) -> Str {
+        let inverse: Vec<(char, char)> = from_layout
+            .iter()
+            .map(|&(qwerty, key)| (key, qwerty))
+            .collect();
+        self.apply_char_map(&inverse).apply_char_map(to_layout)
+    }
+
+    /// Converts text typed on a US QWERTY keyboard to what the same keys
+    /// produce with the US Dvorak layout.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("Dvorak").qwerty_to_dvorak().as_ref(), "Ekrpat");
+    /// ```
+    pub fn qwerty_to_dvorak(&self) -> Str {
+        self.remap_keyboard(&[], &dvorak_layout())
+    }
+
+    /// Converts text typed on a US Dvorak keyboard to what the same keys
+    /// produce with the US QWERTY layout.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("Ekrpat").dvorak_to_qwerty().as_ref(), "Dvorak");
+    /// ```
+    pub fn dvorak_to_qwerty(&self) -> Str {
+        self.remap_keyboard(&dvorak_layout(), &[])
+    }
+
+    /// Replaces every match of `re` with `replacement`.
+    ///
+    /// `replacement` is inserted literally, `$` references to capture groups
+    /// are not expanded. See [`Str::redact`] for security considerations.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let re = regex::Regex::new(r"\d{3}-\d{2}-\d{4}").unwrap();
+    /// let line = Str::new("ssn=123-45-6789");
+    /// assert_eq!(line.redact_regex(&re, "***").as_ref(), "ssn=***");
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn redact_regex(&self, re: &regex::Regex, replacement: &str) -> Str {
+        let out = re.replace_all(self.as_ref(), regex::NoExpand(replacement));
+        Str::new(&out)
+    }
+
+    /// Removes ANSI Control Sequence Introducer sequences, such as colors
+    /// (`\x1b[31m`) or cursor movements (`\x1b[2A`), leaving the visible
+    /// text.
+    ///
+    /// An escape character that does not start a complete sequence is kept.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("\x1b[1;31merror\x1b[0m: failed");
+    /// assert_eq!(s.ansi_strip().as_ref(), "error: failed");
+    /// ```
+    pub fn ansi_strip(&self) -> Str {
+        let s: &str = self.as_ref();
+        let bytes = s.as_bytes();
+        let mut out = String::with_capacity(s.len());
+        let mut start = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            match csi_len(&bytes[i..]) {
+                Some(len) => {
+                    out.push_str(&s[start..i]);
+                    i += len;
+                    start = i;
+                }
+                None => i += 1,
+            }
+        }
+        out.push_str(&s[start..]);
+        Str::new(&out)
+    }
+
+    /// Returns the number of characters left after removing ANSI escape
+    /// sequences with [`Str::ansi_strip`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("\x1b[32mok\x1b[0m").ansi_visible_len(), 2);
+    /// ```
+    #[inline]
+    pub fn ansi_visible_len(&self) -> usize {
+        self.ansi_strip().char_count()
+    }
+
+    /// Translates the string to Pig Latin.
+    ///
+    /// Each run of ASCII letters is a word: words starting with a vowel get
+    /// `ay` appended, otherwise the leading consonants are moved to the end
+    /// before appending `ay`. `y` is a vowel unless it starts the word, and
+    /// a word without vowels is moved whole, so it only gets `ay`. A
+    /// capitalized word stays capitalized. Everything that is not a letter,
+    /// such as punctuation or whitespace, is copied as is.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("Hello, world!");
+    /// assert_eq!(s.to_pig_latin().as_ref(), "Ellohay, orldway!");
+    /// ```
+    pub fn to_pig_latin(&self) -> Str {
+        let s: &str = self.as_ref();
+        let mut out = String::with_capacity(s.len() + s.len() / 2);
+        let mut rest = s;
+        while !rest.is_empty() {
+            let start = rest
+                .find(|c: char| c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let end = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            if end > 0 {
+                push_pig_latin_word(&mut out, &rest[..end]);
+            }
+            rest = &rest[end..];
+        }
+        Str::new(&out)
+    }
+
+    /// Returns the lower cased characters of the string sorted by Unicode
+    /// scalar value, so that all anagrams share the same key.
+    ///
+    /// Every character counts, including whitespace and punctuation.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("Listen").to_anagram_key().as_ref(), "eilnst");
+    /// ```
+    pub fn to_anagram_key(&self) -> Str {
+        let mut chars: Vec<char> =
+            self.as_ref().to_lowercase().chars().collect();
+        chars.sort_unstable();
+        Str::new(&chars.into_iter().collect::<String>())
+    }
+
+    /// Returns `true` if `other` is an anagram of the string, comparing
+    /// their [`Str::to_anagram_key`] keys.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert!(Str::new("listen").is_anagram_of("Silent"));
+    /// assert!(!Str::new("hello").is_anagram_of("world"));
+    /// ```
+    #[inline]
+    pub fn is_anagram_of(&self, other: &str) -> bool {
+        self.to_anagram_key() == Str::new(other).to_anagram_key()
+    }
+
+    /// Replaces every run of whitespace with a single ASCII space.
+    ///
+    /// Whitespace is anything matching [`char::is_whitespace`], such as
+    /// no-break space (U+00A0) or en space (U+2002), plus zero width space
+    /// (U+200B), which Unicode does not class as whitespace but which is
+    /// used as one. Leading and trailing runs are collapsed, not removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("a\u{A0}\u{2002} b\u{200B}c");
+    /// assert_eq!(s.collapse_unicode_spaces().as_ref(), "a b c");
+    /// ```
+    pub fn collapse_unicode_spaces(&self) -> Str {
+        let mut out = String::with_capacity(self.len());
+        let mut in_space = false;
+        for c in self.as_ref().chars() {
+            if c.is_whitespace() || c == '\u{200B}' {
+                if !in_space {
+                    out.push(' ');
+                }
+                in_space = true;
+            } else {
+                out.push(c);
+                in_space = false;
+            }
+        }
+        Str::new(&out)
+    }
+
+    /// Replaces every control character with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("a\0b\n");
+    /// assert_eq!(s.sanitize_for_display().as_ref(), "a\u{FFFD}b\u{FFFD}");
+    /// ```
+    pub fn sanitize_for_display(&self) -> Str {
+        let out: String = self
+            .as_ref()
+            .chars()
+            .map(|c| if c.is_control() { '\u{FFFD}' } else { c })
+            .collect();
+        Str::new(&out)
+    }
+
+    /// Removes diacritics by decomposing the string to Unicode NFD and
+    /// dropping the nonspacing marks that attach to the character before
+    /// them, so `é` becomes `e`.
+    ///
+    /// Marks are dropped only if they have a nonzero canonical combining
+    /// class, which keeps spacing and enclosing marks such as the vowel
+    /// signs of Devanagari and other Indic scripts. Letters without a
+    /// canonical decomposition, such as `ø` or `ł`, are kept as is.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("crème brûlée").strip_diacritics().as_ref(), "creme brulee");
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    pub fn strip_diacritics(&self) -> Str {
+        use unicode_normalization::char::{
+            canonical_combining_class, is_combining_mark,
+        };
+        use unicode_normalization::UnicodeNormalization;
+
+        let out: String = self
+            .as_ref()
+            .nfd()
+            .filter(|&c| {
+                !is_combining_mark(c) || canonical_combining_class(c) == 0
+            })
+            .collect();
+        Str::new(&out)
+    }
+
+    /// Returns `true` if the string contains at least one emoji.
+    ///
+    /// Emoji are detected with a fixed table of Unicode 15.0 code point
+    /// ranges covering pictographs, skin tone modifiers and the regional
+    /// indicators used by flags.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert!(Str::new("party 🎉").contains_emoji());
+    /// assert!(!Str::new("party").contains_emoji());
+    /// ```
+    #[inline]
+    pub fn contains_emoji(&self) -> bool {
+        self.as_ref().chars().any(is_emoji)
+    }
+
+    /// Returns the number of emoji code points in the string.
+    ///
+    /// Every code point of an emoji sequence is counted, so a flag counts
+    /// as two and an emoji with a skin tone modifier as two, while joiners
+    /// and variation selectors are not counted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("🎉 and 🌍").count_emoji(), 2);
+    /// ```
+    #[inline]
+    pub fn count_emoji(&self) -> usize {
+        self.as_ref().chars().filter(|&c| is_emoji(c)).count()
+    }
+
+    /// Removes every emoji from the string, together with the joiners,
+    /// variation selectors and tags that follow them in emoji sequences.
+    ///
+    /// Surrounding whitespace is kept.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("hi 👋🏻!").strip_emoji().as_ref(), "hi !");
+    /// ```
+    pub fn strip_emoji(&self) -> Str {
+        let mut out = String::with_capacity(self.len());
+        let mut in_emoji = false;
+        for c in self.as_ref().chars() {
+            if is_emoji(c) || (in_emoji && is_emoji_component(c)) {
+                in_emoji = true;
+            } else {
+                in_emoji = false;
+                out.push(c);
+            }
+        }
+        Str::new(&out)
+    }
+
+    /// Returns a concise, unambiguous representation of the string for
+    /// debug output.
+    ///
+    /// A string without control characters or quotes is used as is. Any
+    /// other string is rendered as its UTF-8 bytes in hex, prefixed with
+    /// `0x`. Either form longer than `max_len` characters is cut to
+    /// `max_len` characters, the last of which is `…`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// assert_eq!(Str::new("plain").to_compact_repr(16).as_ref(), "plain");
+    /// assert_eq!(Str::new("a\nb").to_compact_repr(16).as_ref(), "0x610a62");
+    /// assert_eq!(Str::new("abcdefgh").to_compact_repr(4).as_ref(), "abc…");
+    /// ```
+    pub fn to_compact_repr(&self, max_len: usize) -> Str {
+        let s: &str = self.as_ref();
+        let safe = !s.chars().any(|c| c.is_control() || c == '"' || c == '\'');
+        let repr = if safe {
+            s.to_string()
+        } else {
+            let mut hex = String::from("0x");
+            for b in s.bytes() {
+                hex.push_str(&format!("{:02x}", b));
+            }
+            hex
+        };
+
+        if repr.chars().count() <= max_len {
+            return Str::new(&repr);
+        }
+        let mut out: String =
+            repr.chars().take(max_len.saturating_sub(1)).collect();
+        if max_len > 0 {
+            out.push('…');
+        }
+        Str::new(&out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains_only_printable() {
+        assert!(Str::new("").contains_only_printable());
+        assert!(Str::new("Hello, 世界! 🎉").contains_only_printable());
+        assert!(Str::new("a b\u{A0}c").contains_only_printable());
+        assert!(!Str::new("nul\0").contains_only_printable());
+        assert!(!Str::new("line\n").contains_only_printable());
+        assert!(!Str::new("tab\t").contains_only_printable());
+        assert!(!Str::new("\x1b[0m").contains_only_printable());
+    }
+
+    #[test]
+    fn test_contains_only_visible() {
+        assert!(Str::new("").contains_only_visible());
+        assert!(Str::new("Hello,世界!").contains_only_visible());
+        assert!(!Str::new("a b").contains_only_visible());
+        assert!(!Str::new("a\u{A0}b").contains_only_visible());
+        assert!(!Str::new("a\nb").contains_only_visible());
+        assert!(!Str::new("a\0b").contains_only_visible());
+    }
+
+    #[test]
+    fn test_mask() {
+        let s = Str::new("1234567890");
+        assert_eq!(s.mask(3, 7, '*').as_ref(), "123****890");
+        assert_eq!(s.mask(0, 6, '#').as_ref(), "######7890");
+        assert_eq!(s.mask(6, 10, 'x').as_ref(), "123456xxxx");
+        assert_eq!(s.mask(6, 100, 'x').as_ref(), "123456xxxx");
+        assert_eq!(s.mask(0, 10, '•').char_count(), 10);
+    }
+
+    #[test]
+    fn test_mask_unicode() {
+        let s = Str::new("日本語テキスト");
+        assert_eq!(s.mask(1, 3, '*').as_ref(), "日**テキスト");
+        assert_eq!(Str::new("abc").mask(1, 2, '世').as_ref(), "a世c");
+    }
+
+    #[test]
+    fn test_mask_empty_range() {
+        let s = Str::new("secret");
+        assert_eq!(s.mask(2, 2, '*'), s);
+        assert_eq!(s.mask(4, 1, '*'), s);
+        assert_eq!(s.mask(10, 20, '*'), s);
+        assert_eq!(Str::new("").mask(0, 5, '*').as_ref(), "");
+    }
+
+    #[test]
+    fn test_redact() {
+        let line =
+            Str::new("GET /v1?api_key=AKIA1234 key=AKIA1234 from 10.0.0.1");
+        assert_eq!(
+            line.redact("AKIA1234", "<key>").as_ref(),
+            "GET /v1?api_key=<key> key=<key> from 10.0.0.1"
+        );
+        assert_eq!(
+            line.redact("10.0.0.1", "x.x.x.x").as_ref(),
+            "GET /v1?api_key=AKIA1234 key=AKIA1234 from x.x.x.x"
+        );
+        assert_eq!(line.redact("missing", "x"), line);
+        assert_eq!(line.redact("", "x"), line);
+        assert_eq!(Str::new("aaaa").redact("aa", "b").as_ref(), "bb");
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_redact_regex() {
+        let line = Str::new(
+            "user=bob@example.com ip=192.168.1.20 token=ghp_abcDEF123 \
+             cc=alice.b@mail.example.org peer=8.8.8.8",
+        );
+
+        let email = regex::Regex::new(r"[\w.+-]+@[\w-]+(\.[\w-]+)+").unwrap();
+        let ip = regex::Regex::new(r"\b\d{1,3}(\.\d{1,3}){3}\b").unwrap();
+        let key = regex::Regex::new(r"ghp_[A-Za-z0-9]+").unwrap();
+
+        let redacted = line
+            .redact_regex(&email, "<email>")
+            .redact_regex(&ip, "<ip>")
+            .redact_regex(&key, "<token>");
+        assert_eq!(
+            redacted.as_ref(),
+            "user=<email> ip=<ip> token=<token> cc=<email> peer=<ip>"
+        );
+
+        let dollar = regex::Regex::new("(b)ob").unwrap();
+        assert_eq!(Str::new("bob").redact_regex(&dollar, "$1").as_ref(), "$1");
+    }
+
+    #[test]
+    fn test_ansi_strip() {
+        let strip = |s: &str| Str::new(s).ansi_strip().to_string();
+        assert_eq!(strip("\x1b[31mred\x1b[0m"), "red");
+        assert_eq!(strip("\x1b[38;5;208morange\x1b[39m"), "orange");
+        assert_eq!(
+            strip("\x1b[1mbold\x1b[22m \x1b[4munder\x1b[24m"),
+            "bold under"
+        );
+        assert_eq!(
+            strip("up\x1b[2Adown\x1b[10;20Hclear\x1b[2J\x1b[K"),
+            "updownclear"
+        );
+        assert_eq!(strip("\x1b[?25l世界\x1b[?25h"), "世界");
+        assert_eq!(strip("plain"), "plain");
+    }
+
+    #[test]
+    fn test_ansi_strip_keeps_incomplete_sequences() {
+        let strip = |s: &str| Str::new(s).ansi_strip().to_string();
+        assert_eq!(strip("\x1b"), "\x1b");
+        assert_eq!(strip("a\x1bb"), "a\x1bb");
+        assert_eq!(strip("\x1b[31"), "\x1b[31");
+        assert_eq!(strip("\x1b[31\x1b[0m!"), "\x1b[31!");
+        assert_eq!(strip("\x1b[\u{e9}"), "\x1b[\u{e9}");
+    }
+
+    #[test]
+    fn test_ansi_visible_len() {
+        assert_eq!(Str::new("\x1b[31mred\x1b[0m").ansi_visible_len(), 3);
+        assert_eq!(Str::new("\x1b[1m世界\x1b[0m").ansi_visible_len(), 2);
+        assert_eq!(Str::new("").ansi_visible_len(), 0);
+    }
+
+    #[test]
+    fn test_to_pig_latin() {
+        let pig = |s: &str| Str::new(s).to_pig_latin().to_string();
+        assert_eq!(pig("hello world"), "ellohay orldway");
+        assert_eq!(pig("apple egg"), "appleay eggay");
+        assert_eq!(pig("string glove"), "ingstray oveglay");
+        assert_eq!(pig("rhythm yellow"), "ythmrhay ellowyay");
+    }
+
+    #[test]
+    fn test_to_pig_latin_edge_cases() {
+        let pig = |s: &str| Str::new(s).to_pig_latin().to_string();
+        assert_eq!(pig("psst hmm"), "psstay hmmay");
+        assert_eq!(pig("Hello, World!"), "Ellohay, Orldway!");
+        assert_eq!(pig("Apple"), "Appleay");
+        assert_eq!(pig("don't  stop."), "onday'tay  opstay.");
+        assert_eq!(pig("  42 "), "  42 ");
+        assert_eq!(pig(""), "");
+    }
+
+    #[test]
+    fn test_to_anagram_key() {
+        assert_eq!(Str::new("silent").to_anagram_key().as_ref(), "eilnst");
+        assert_eq!(Str::new("baNAna").to_anagram_key().as_ref(), "aaabnn");
+        assert_eq!(Str::new("").to_anagram_key().as_ref(), "");
+    }
+
+    #[test]
+    fn test_is_anagram_of() {
+        assert!(Str::new("listen").is_anagram_of("silent"));
+        assert!(Str::new("Dormitory").is_anagram_of("dirtyroom"));
+        assert!(!Str::new("hello").is_anagram_of("world"));
+        assert!(!Str::new("aab").is_anagram_of("abb"));
+        assert!(Str::new("").is_anagram_of(""));
+        assert!(Str::new("世界和平").is_anagram_of("和平世界"));
+        assert!(Str::new("Ölçü").is_anagram_of("üçöl"));
+        assert!(!Str::new("dirty room").is_anagram_of("dormitory"));
+    }
+
+    #[test]
+    fn test_collapse_unicode_spaces() {
+        let collapse = |s: &str| Str::new(s).collapse_unicode_spaces();
+        assert_eq!(collapse("a\u{A0}b").as_ref().as_bytes(), b"a b");
+        assert_eq!(
+            collapse("en\u{2002}\u{2002}hair\u{200A}end")
+                .as_ref()
+                .as_bytes(),
+            b"en hair end"
+        );
+        assert_eq!(
+            collapse("zero\u{200B}width").as_ref().as_bytes(),
+            b"zero width"
+        );
+        assert_eq!(
+            collapse("\u{3000}\t mixed \r\n\u{2028}runs\u{A0}").as_ref(),
+            " mixed runs "
+        );
+        assert_eq!(collapse("plain text").as_ref(), "plain text");
+        assert_eq!(collapse("").as_ref(), "");
+    }
+
+    #[test]
+    fn test_sanitize_for_display() {
+        let s = Str::new("ok \0\n\t\x7f世界");
+        assert_eq!(
+            s.sanitize_for_display().as_ref(),
+            "ok \u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}世界"
+        );
+        assert!(s.sanitize_for_display().contains_only_printable());
+
+        let clean = Str::new("Hello, 世界!");
+        assert_eq!(clean.sanitize_for_display(), clean);
+    }
+
+    #[test]
+    fn test_to_compact_repr_identity() {
+        assert_eq!(Str::new("hello").to_compact_repr(10).as_ref(), "hello");
+        assert_eq!(Str::new("").to_compact_repr(0).as_ref(), "");
+        assert_eq!(
+            Str::new("Hello, 世界!").to_compact_repr(10).as_ref(),
+            "Hello, 世界!"
+        );
+    }
+
+    #[test]
+    fn test_to_compact_repr_hex() {
+        assert_eq!(Str::new("\0\t").to_compact_repr(10).as_ref(), "0x0009");
+        assert_eq!(Str::new("\"q\"").to_compact_repr(10).as_ref(), "0x227122");
+        assert_eq!(Str::new("é\n").to_compact_repr(10).as_ref(), "0xc3a90a");
+        assert_eq!(
+            Str::new("line one\n").to_compact_repr(8).as_ref(),
+            "0x6c696…"
+        );
+    }
+
+    #[test]
+    fn test_to_compact_repr_truncated() {
+        let long = "a".repeat(100);
+        let repr = Str::new(&long).to_compact_repr(10);
+        assert_eq!(repr.as_ref(), "aaaaaaaaa…");
+        assert_eq!(repr.char_count(), 10);
+        assert_eq!(Str::new("世界世界").to_compact_repr(3).as_ref(), "世界…");
+        assert_eq!(Str::new("abc").to_compact_repr(0).as_ref(), "");
+    }
+
+    #[test]
+    fn test_contains_emoji() {
+        assert!(Str::new("🎉").contains_emoji());
+        assert!(Str::new("hello 🌍").contains_emoji());
+        assert!(Str::new("👋🏻").contains_emoji());
+        assert!(Str::new("\u{1F3FB}").contains_emoji());
+        assert!(Str::new("🇩🇪").contains_emoji());
+        assert!(Str::new("I ❤\u{FE0F} it").contains_emoji());
+        assert!(!Str::new("").contains_emoji());
+        assert!(!Str::new("plain text, 世界 & ©").contains_emoji());
+        assert!(!Str::new("\u{200D}\u{FE0F}").contains_emoji());
+    }
+
+    #[test]
+    fn test_count_emoji() {
+        assert_eq!(Str::new("no emoji").count_emoji(), 0);
+        assert_eq!(Str::new("🎉🎉 🌍").count_emoji(), 3);
+        assert_eq!(Str::new("👋🏻").count_emoji(), 2);
+        assert_eq!(Str::new("🇩🇪").count_emoji(), 2);
+        assert_eq!(Str::new("👩\u{200D}👩\u{200D}👧").count_emoji(), 3);
+    }
+
+    #[test]
+    fn test_strip_emoji() {
+        let strip = |s| Str::new(s).strip_emoji().to_string();
+        assert_eq!(strip("🎉 party 🌍!"), " party !");
+        assert_eq!(strip("wave👋🏻"), "wave");
+        assert_eq!(strip("family: 👩\u{200D}👩\u{200D}👧."), "family: .");
+        assert_eq!(strip("I ❤\u{FE0F} it"), "I  it");
+        assert_eq!(strip("flag 🏴\u{E0067}\u{E0062}\u{E007F}"), "flag ");
+        assert_eq!(strip("no emoji, 世界"), "no emoji, 世界");
+        assert_eq!(strip("क्\u{200D}ष"), "क्\u{200D}ष");
+        assert_eq!(strip(""), "");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-normalization")]
+    fn test_strip_diacritics() {
+        let strip = |s| Str::new(s).strip_diacritics().to_string();
+        assert_eq!(strip("café"), "cafe");
+        assert_eq!(strip("naïve"), "naive");
+        assert_eq!(strip("señor"), "senor");
+        assert_eq!(strip("Ångström"), "Angstrom");
+        assert_eq!(strip("cafe\u{301}"), "cafe");
+        assert_eq!(strip("Øresund"), "Øresund");
+        assert_eq!(strip("世界"), "世界");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-normalization")]
+    fn test_strip_diacritics_keeps_spacing_marks() {
+        let strip = |s| Str::new(s).strip_diacritics().to_string();
+        // The vowel signs ि and ी are spacing marks and stay, the virama ्
+        // is a nonspacing mark and goes.
+        assert_eq!(strip("हिन्दी"), "हिनदी");
+        assert_eq!(strip("नमस्ते"), "नमसते");
+        assert_eq!(strip("தமிழ்"), "தமிழ");
+        assert_eq!(strip("a\u{20DD}"), "a\u{20DD}");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-normalization")]
+    fn test_strip_diacritics_ascii_and_length() {
+        for input in ["", "plain ascii 123", "Zürich", "Tiếng Việt", "ﬁ é"]
+        {
+            let stripped = Str::new(input).strip_diacritics();
+            assert!(stripped.len() <= input.len(), "{}", input);
+            if input.is_ascii() {
+                assert_eq!(stripped.as_ref(), input);
+            }
+        }
+        assert_eq!(
+            Str::new("Tiếng Việt").strip_diacritics().as_ref(),
+            "Tieng Viet"
+        );
+    }
+
+    #[test]
+    fn test_multi_replace() {
+        let s = Str::new("The cat sat on the mat.");
+        let replaced =
+            s.multi_replace(&[("cat", "dog"), ("mat", "rug"), ("sat", "lay")]);
+        assert_eq!(replaced.as_ref(), "The dog lay on the rug.");
+        assert_eq!(Str::new("").multi_replace(&[("a", "b")]).as_ref(), "");
+        assert_eq!(Str::new("abc").multi_replace(&[]).as_ref(), "abc");
+        assert_eq!(Str::new("abc").multi_replace(&[("", "x")]).as_ref(), "abc");
+    }
+
+    #[test]
+    fn test_multi_replace_is_simultaneous() {
+        let s = Str::new("a b");
+        let swapped = s.multi_replace(&[("a", "b"), ("b", "a")]);
+        assert_eq!(swapped.as_ref(), "b a");
+
+        let sequential = "a b".replace('a', "b").replace('b', "a");
+        assert_eq!(sequential, "a a");
+
+        let html = Str::new("<a href=\"x\">&</a>").multi_replace(&[
+            ("&", "&amp;"),
+            ("<", "&lt;"),
+            (">", "&gt;"),
+            ("\"", "&quot;"),
+            ("'", "&#39;"),
+        ]);
+        assert_eq!(
+            html.as_ref(),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_multi_replace_overlapping() {
+        let s = Str::new("abcd abc ab");
+        let replaced =
+            s.multi_replace(&[("ab", "1"), ("abc", "2"), ("bcd", "3")]);
+        assert_eq!(replaced.as_ref(), "2d 2 1");
+
+        let s = Str::new("aaaa");
+        assert_eq!(s.multi_replace(&[("aa", "b")]).as_ref(), "bb");
+        assert_eq!(
+            Str::new("世界")
+                .multi_replace(&[("界", "!"), ("世界", "?")])
+                .as_ref(),
+            "?"
+        );
+        assert_eq!(
+            Str::new("ab")
+                .multi_replace(&[("ab", "1"), ("ab", "2")])
+                .as_ref(),
+            "1"
+        );
+    }
+
+    #[test]
+    fn test_apply_char_map_turkish() {
+        let upper = [('i', 'İ'), ('ı', 'I')];
+        let s = Str::new("iğne ılık");
+        assert_eq!(s.apply_char_map(&upper).as_ref(), "İğne IlIk");
+
+        let lower = [('I', 'ı'), ('İ', 'i')];
+        let s = Str::new("ISPARTA İZMİR");
+        assert_eq!(s.apply_char_map(&lower).as_ref(), "ıSPARTA iZMiR");
+    }
+
+    #[test]
+    fn test_apply_char_map_typography() {
+        let table = [
+            ('“', '"'),
+            ('”', '"'),
+            ('‘', '\''),
+            ('’', '\''),
+            ('ſ', 's'),
+            ('–', '-'),
+        ];
+        let s = Str::new("“Don’t” – the Congreſs");
+        assert_eq!(
+            s.apply_char_map(&table).as_ref(),
+            "\"Don't\" - the Congress"
+        );
+    }
+
+    #[test]
+    fn test_apply_char_map_unmapped_unchanged() {
+        let s = Str::new("Hello, 世界! 🎉");
+        assert_eq!(s.apply_char_map(&[]), s);
+        assert_eq!(s.apply_char_map(&[('x', 'y'), ('ö', 'o')]), s);
+        assert_eq!(
+            Str::new("aa")
+                .apply_char_map(&[('a', 'b'), ('a', 'c')])
+                .as_ref(),
+            "bb"
+        );
+    }
+
+    #[test]
+    fn test_apply_char_removal() {
+        let s = Str::new("h-e-l-l-o 世界!");
+        assert_eq!(s.apply_char_removal(&['-', '!']).as_ref(), "hello 世界");
+        assert_eq!(
+            s.apply_char_removal(&['界', '世', ' ']).as_ref(),
+            "h-e-l-l-o!"
+        );
+        assert_eq!(s.apply_char_removal(&[]), s);
+        assert_eq!(Str::new("").apply_char_removal(&['a']).as_ref(), "");
+    }
+
+    #[test]
+    fn test_qwerty_to_dvorak() {
+        let dvorak = |s: &str| Str::new(s).qwerty_to_dvorak().to_string();
+        assert_eq!(dvorak("q"), "'");
+        assert_eq!(dvorak("s"), "o");
+        assert_eq!(dvorak("z"), ";");
+        assert_eq!(dvorak("Q"), "\"");
+        assert_eq!(dvorak("[]"), "/=");
+        assert_eq!(dvorak("hello world"), "d.nnr ,rpne");
+        assert_eq!(dvorak("a 世界!"), "a 世界!");
+        assert_eq!(
+            Str::new("Dvorak")
+                .qwerty_to_dvorak()
+                .dvorak_to_qwerty()
+                .as_ref(),
+            "Dvorak"
+        );
+    }
+
+    #[test]
+    fn test_dvorak_to_qwerty() {
+        let qwerty = |s: &str| Str::new(s).dvorak_to_qwerty().to_string();
+        assert_eq!(qwerty("'"), "q");
+        assert_eq!(qwerty("o"), "s");
+        assert_eq!(qwerty("Ekrpat"), "Dvorak");
+        assert_eq!(qwerty("d.nnr ,rpne"), "hello world");
+
+        let all: String = QWERTY_KEYS.chars().collect();
+        let s = Str::new(&all);
+        assert_eq!(s.qwerty_to_dvorak().as_ref(), DVORAK_KEYS);
+        assert_eq!(s.qwerty_to_dvorak().dvorak_to_qwerty(), s);
+    }
+
+    #[test]
+    fn test_remap_keyboard() {
+        let azerty = [('q', 'a'), ('a', 'q'), ('w', 'z'), ('z', 'w')];
+        let s = Str::new("aqwz");
+        assert_eq!(s.remap_keyboard(&[], &azerty).as_ref(), "qazw");
+        assert_eq!(s.remap_keyboard(&azerty, &azerty), s);
+        assert_eq!(s.remap_keyboard(&[], &[]), s);
+        assert_eq!(
+            Str::new("qa")
+                .remap_keyboard(&azerty, &dvorak_layout())
+                .as_ref(),
+            "a'"
+        );
+    }
+
+    #[test]
+    fn test_highlight_matches() {
+        let hl = |s: &str, p| Str::new(s).highlight_matches(p, "<b>", "</b>");
+        assert_eq!(hl("no match here", "xyz").as_ref(), "no match here");
+        assert_eq!(hl("find me", "me").as_ref(), "find <b>me</b>");
+        assert_eq!(
+            hl("cat, Cat, cat", "cat").as_ref(),
+            "<b>cat</b>, Cat, <b>cat</b>"
+        );
+        assert_eq!(hl("aaaa", "aa").as_ref(), "<b>aa</b><b>aa</b>");
+        assert_eq!(hl("aaa", "aa").as_ref(), "<b>aa</b>a");
+        assert_eq!(hl("abababa", "aba").as_ref(), "<b>aba</b>b<b>aba</b>");
+        assert_eq!(hl("世界 世界", "界").as_ref(), "世<b>界</b> 世<b>界</b>");
+        assert_eq!(hl("", "x").as_ref(), "");
+    }
+
+    #[test]
+    fn test_highlight_matches_empty_pattern() {
+        let s = Str::new("ab界");
+        assert_eq!(s.highlight_matches("", "[", "]").as_ref(), "[a][b][界]");
+        assert_eq!(Str::new("").highlight_matches("", "[", "]").as_ref(), "");
+    }
+
+    #[test]
+    fn test_highlight_matches_ansi() {
+        let s = Str::new("error: disk error");
+        assert_eq!(
+            s.highlight_matches("error", "\x1b[31m", "\x1b[0m").as_ref(),
+            "\x1b[31merror\x1b[0m: disk \x1b[31merror\x1b[0m"
+        );
+    }
+}
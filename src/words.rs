@@ -0,0 +1,264 @@
+use crate::Str;
+
+/// Returns `true` if `c` can be part of a word: alphanumeric or `_`.
+#[inline]
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Returns `true` if a word starts at byte `at` of `s`, which must be a char
+/// boundary.
+fn is_word_start(s: &str, at: usize) -> bool {
+    let starts_word = s[at..].chars().next().is_some_and(is_word_char);
+    starts_word && !s[..at].chars().next_back().is_some_and(is_word_char)
+}
+
+impl Str {
+    /// Returns `true` if a word starts at `byte_offset`: the character there
+    /// is a word character and the one before it, if any, is not.
+    ///
+    /// Word characters are alphanumeric characters and `_`. Returns `false`
+    /// if `byte_offset` is out of range or not on a char boundary.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("hello world");
+    /// assert!(s.is_word_boundary_at(6));
+    /// assert!(!s.is_word_boundary_at(7));
+    /// ```
+    pub fn is_word_boundary_at(&self, byte_offset: usize) -> bool {
+        let s: &str = self.as_ref();
+        s.is_char_boundary(byte_offset) && is_word_start(s, byte_offset)
+    }
+
+    /// Returns the byte offset of the first word start after `from`, as
+    /// defined by [`Str::is_word_boundary_at`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("hello big world");
+    /// assert_eq!(s.find_next_word_start(0), Some(6));
+    /// assert_eq!(s.find_next_word_start(10), None);
+    /// ```
+    pub fn find_next_word_start(&self, from: usize) -> Option<usize> {
+        let s: &str = self.as_ref();
+        s.char_indices()
+            .map(|(at, _)| at)
+            .skip_while(|&at| at <= from)
+            .find(|&at| is_word_start(s, at))
+    }
+
+    /// Returns the byte offset of the last word start before `from`, as
+    /// defined by [`Str::is_word_boundary_at`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("hello big world");
+    /// assert_eq!(s.find_prev_word_start(8), Some(6));
+    /// assert_eq!(s.find_prev_word_start(6), Some(0));
+    /// ```
+    pub fn find_prev_word_start(&self, from: usize) -> Option<usize> {
+        let s: &str = self.as_ref();
+        s.char_indices()
+            .map(|(at, _)| at)
+            .take_while(|&at| at < from)
+            .filter(|&at| is_word_start(s, at))
+            .last()
+    }
+
+    /// Returns the word containing the character at `char_offset`, together
+    /// with its start and end character offsets, the end being exclusive.
+    ///
+    /// A word is a maximal run of word characters, as defined by
+    /// [`Str::is_word_boundary_at`]. Returns `None` if the character is not
+    /// a word character or `char_offset` is out of range.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("hello world");
+    /// assert_eq!(s.word_at_char(8), Some(("world", 6, 11)));
+    /// assert_eq!(s.word_at_char(5), None);
+    /// ```
+    pub fn word_at_char(
+        &self,
+        char_offset: usize,
+    ) -> Option<(&str, usize, usize)> {
+        let s: &str = self.as_ref();
+        let (at, c) = s.char_indices().nth(char_offset)?;
+        if !is_word_char(c) {
+            return None;
+        }
+
+        let head = &s[..at];
+        let tail = &s[at..];
+        let start = head
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| !is_word_char(c))
+            .map_or(0, |(before, c)| before + c.len_utf8());
+        let end = at + tail.find(|c| !is_word_char(c)).unwrap_or(tail.len());
+
+        let word = &s[start..end];
+        let start_char = char_offset - s[start..at].chars().count();
+        Some((word, start_char, start_char + word.chars().count()))
+    }
+
+    /// Returns the first `max_words` whitespace-separated words of the
+    /// string, joined by single spaces, followed by `"..."` if the string
+    /// has more words.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("The quick brown fox");
+    /// assert_eq!(s.abbreviate_words(2).as_ref(), "The quick...");
+    /// assert_eq!(s.abbreviate_words(4).as_ref(), "The quick brown fox");
+    /// ```
+    pub fn abbreviate_words(&self, max_words: usize) -> Str {
+        let s: &str = self.as_ref();
+        let mut words = s.split_whitespace();
+        let mut out =
+            words.by_ref().take(max_words).collect::<Vec<_>>().join(" ");
+        if words.next().is_some() {
+            out.push_str("...");
+        }
+        Str::new(&out)
+    }
+
+    /// Returns the upper cased first character of every whitespace-separated
+    /// word of the string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ostr::Str;
+    /// let s = Str::new("Transmission Control Protocol");
+    /// assert_eq!(s.to_initials().as_ref(), "TCP");
+    /// ```
+    pub fn to_initials(&self) -> Str {
+        let s: &str = self.as_ref();
+        let out: String = s
+            .split_whitespace()
+            .filter_map(|word| word.chars().next())
+            .flat_map(char::to_uppercase)
+            .collect();
+        Str::new(&out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_starts(s: &str) -> Vec<usize> {
+        let s = Str::new(s);
+        (0..=s.len())
+            .filter(|&at| s.is_word_boundary_at(at))
+            .collect()
+    }
+
+    #[test]
+    fn test_is_word_boundary_at() {
+        assert_eq!(word_starts("hello world"), [0, 6]);
+        assert_eq!(word_starts("  padded  text  "), [2, 10]);
+        assert_eq!(word_starts("snake_case, kebab-case"), [0, 12, 18]);
+        assert_eq!(word_starts("camelCaseIdent"), [0]);
+        assert_eq!(word_starts("été 世界"), [0, 6]);
+        assert!(word_starts("").is_empty());
+        assert!(word_starts(" ... ").is_empty());
+
+        let s = Str::new("été");
+        assert!(!s.is_word_boundary_at(1));
+        assert!(!s.is_word_boundary_at(100));
+    }
+
+    #[test]
+    fn test_find_next_word_start() {
+        let s = Str::new("hello world");
+        assert_eq!(s.find_next_word_start(0), Some(6));
+        assert_eq!(s.find_next_word_start(5), Some(6));
+        assert_eq!(s.find_next_word_start(6), None);
+
+        let s = Str::new("  lead trail  ");
+        assert_eq!(s.find_next_word_start(0), Some(2));
+        assert_eq!(s.find_next_word_start(2), Some(7));
+        assert_eq!(s.find_next_word_start(7), None);
+
+        let s = Str::new("parseXml(input)");
+        assert_eq!(s.find_next_word_start(0), Some(9));
+        assert_eq!(s.find_next_word_start(100), None);
+    }
+
+    #[test]
+    fn test_find_prev_word_start() {
+        let s = Str::new("hello world");
+        assert_eq!(s.find_prev_word_start(11), Some(6));
+        assert_eq!(s.find_prev_word_start(6), Some(0));
+        assert_eq!(s.find_prev_word_start(0), None);
+
+        let s = Str::new("  lead trail  ");
+        assert_eq!(s.find_prev_word_start(100), Some(7));
+        assert_eq!(s.find_prev_word_start(2), None);
+
+        let s = Str::new("世界 été");
+        assert_eq!(s.find_prev_word_start(8), Some(7));
+        assert_eq!(s.find_prev_word_start(7), Some(0));
+    }
+
+    #[test]
+    fn test_word_at_char() {
+        let s = Str::new("hello world");
+        assert_eq!(s.word_at_char(3), Some(("hello", 0, 5)));
+        assert_eq!(s.word_at_char(0), Some(("hello", 0, 5)));
+        assert_eq!(s.word_at_char(4), Some(("hello", 0, 5)));
+        assert_eq!(s.word_at_char(5), None);
+        assert_eq!(s.word_at_char(6), Some(("world", 6, 11)));
+        assert_eq!(s.word_at_char(10), Some(("world", 6, 11)));
+        assert_eq!(s.word_at_char(11), None);
+    }
+
+    #[test]
+    fn test_word_at_char_unicode_and_punctuation() {
+        let s = Str::new("(été snake_case) 世界!");
+        assert_eq!(s.word_at_char(0), None);
+        assert_eq!(s.word_at_char(2), Some(("été", 1, 4)));
+        assert_eq!(s.word_at_char(9), Some(("snake_case", 5, 15)));
+        assert_eq!(s.word_at_char(18), Some(("世界", 17, 19)));
+        assert_eq!(s.word_at_char(19), None);
+    }
+
+    #[test]
+    fn test_word_at_char_whitespace() {
+        let s = Str::new(" \t \n ");
+        assert!((0..=s.char_count()).all(|at| s.word_at_char(at).is_none()));
+        assert_eq!(Str::new("").word_at_char(0), None);
+    }
+
+    #[test]
+    fn test_abbreviate_words() {
+        let s = Str::new("  The quick\tbrown\n fox  ");
+        assert_eq!(s.abbreviate_words(0).as_ref(), "...");
+        assert_eq!(s.abbreviate_words(1).as_ref(), "The...");
+        assert_eq!(s.abbreviate_words(3).as_ref(), "The quick brown...");
+        assert_eq!(s.abbreviate_words(4).as_ref(), "The quick brown fox");
+        assert_eq!(s.abbreviate_words(10).as_ref(), "The quick brown fox");
+        assert_eq!(Str::new("été 世界").abbreviate_words(1).as_ref(), "été...");
+        assert_eq!(Str::new(" \t ").abbreviate_words(0).as_ref(), "");
+        assert_eq!(Str::new("").abbreviate_words(2).as_ref(), "");
+    }
+
+    #[test]
+    fn test_to_initials() {
+        let initials = |s: &str| Str::new(s).to_initials();
+        assert_eq!(initials("Transmission Control Protocol").as_ref(), "TCP");
+        assert_eq!(initials("as soon as possible").as_ref(), "ASAP");
+        assert_eq!(initials("  portable\tnetwork\ngraphics ").as_ref(), "PNG");
+        assert_eq!(initials("électricité de France").as_ref(), "ÉDF");
+        assert_eq!(initials("straße").as_ref(), "S");
+        assert_eq!(initials("ßig").as_ref(), "SS");
+        assert_eq!(initials("").as_ref(), "");
+    }
+}
@@ -0,0 +1,60 @@
+//! Checks that `LazyClone` only allocates when it is turned into a `Str`.
+//!
+//! This lives in its own test binary because it replaces the global
+//! allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use ostr::Str;
+
+/// Passes allocations through to the system allocator, counting them per
+/// thread so that tests running in parallel do not interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn test_lazy_clone_does_not_allocate_until_into_str() {
+    let s = Str::new("a string that is never copied");
+
+    let before = allocations();
+    let lazy = s.lazy_clone();
+    let copy = lazy;
+    assert_eq!(copy.as_str().len(), s.len());
+    assert!(copy.as_ref().starts_with("a string"));
+    assert_eq!(allocations(), before);
+
+    let owned = copy.into_str();
+    assert_eq!(allocations(), before + 1);
+    assert_eq!(owned, s);
+}
+
+#[test]
+fn test_clone_allocates_eagerly() {
+    let s = Str::new("compared with a plain clone");
+    let before = allocations();
+    let cloned = s.clone();
+    assert_eq!(allocations(), before + 1);
+    assert_eq!(cloned, s);
+}